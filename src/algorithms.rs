@@ -1,8 +1,16 @@
+pub mod closure;
+pub mod connectivity;
+pub mod densest_subgraph;
+pub mod disjoint_paths;
+pub mod dynamic_flow;
+pub mod generalized_flow;
 pub mod gomory_hu;
 pub mod maximum_bipartite_matching;
 pub mod maximum_flow;
 pub mod maximum_matching;
 pub mod minimum_cost_flow;
+pub mod minimum_cut;
+pub mod multi_commodity_flow;
 pub mod shortest_path;
 pub mod spanning_tree;
 pub mod branching;
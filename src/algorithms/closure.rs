@@ -0,0 +1,2 @@
+pub mod maximum_weight_closure;
+pub mod prelude;
@@ -0,0 +1,71 @@
+use crate::prelude::maximum_flow::MaximumFlowError;
+use crate::{
+    algorithms::maximum_flow::{graph::MaximumFlowGraph, solvers::dinic::Dinic, solvers::solver::MaximumFlowSolver},
+    core::numeric::FlowNum,
+    graph::{direction::Directed, graph::Graph, ids::NodeId, node::Node},
+};
+use num_traits::Signed;
+use std::marker::PhantomData;
+
+// maximum weight closure (Picard), a node set S with no edge leaving it and maximum total profit
+// an edge (u, v) means that u requires v; the reduction to a minimum cut joins the source to the nodes
+// with positive profit and the nodes with negative profit to the sink, and makes the edges uncuttable
+// S is the maximum flow solver
+pub struct MaximumWeightClosure<F, S = Dinic<F>> {
+    profits: Vec<F>,
+    edges: Vec<(NodeId, NodeId)>,
+    phantom: PhantomData<S>,
+}
+
+impl<F, S> MaximumWeightClosure<F, S>
+where
+    F: FlowNum + Signed,
+    S: MaximumFlowSolver<F>,
+{
+    // the node data is the profit
+    pub fn new<E>(graph: &Graph<Directed, F, E>) -> Self {
+        Self::new_with(graph, |node| node.data)
+    }
+
+    pub fn new_with<N, E, PF>(graph: &Graph<Directed, N, E>, profit_fn: PF) -> Self
+    where
+        PF: Fn(&Node<N>) -> F,
+    {
+        let profits = graph.nodes().map(profit_fn).collect();
+        let edges = graph.edges().map(|e| (e.u, e.v)).collect();
+        Self { profits, edges, phantom: PhantomData }
+    }
+
+    // returns the maximum profit and the smallest closure attaining it
+    // the sum of the positive profits plus one must fit in F
+    pub fn solve(&self) -> Result<(F, Vec<bool>), MaximumFlowError> {
+        let n = self.profits.len();
+        let positive = self
+            .profits
+            .iter()
+            .filter(|p| p.is_positive())
+            .fold(F::zero(), |sum, &p| sum + p);
+
+        let mut graph = MaximumFlowGraph::new_directed();
+        let nodes = graph.add_nodes(n);
+        let (source, sink) = (graph.add_node(), graph.add_node());
+        for (u, &profit) in self.profits.iter().enumerate() {
+            if profit.is_positive() {
+                graph.add_edge(source, nodes[u], profit);
+            } else if profit.is_negative() {
+                graph.add_edge(nodes[u], sink, -profit);
+            }
+        }
+        // larger than any minimum cut
+        let infinity = positive + F::one();
+        for &(u, v) in self.edges.iter() {
+            graph.add_edge(u, v, infinity);
+        }
+
+        let mut solver = S::new(&graph);
+        let cut = solver.solve(source, sink)?;
+        let mut side = solver.minimum_cut()?;
+        side.truncate(n);
+        Ok((positive - cut, side))
+    }
+}
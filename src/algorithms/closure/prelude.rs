@@ -0,0 +1 @@
+pub use super::maximum_weight_closure::MaximumWeightClosure;
@@ -0,0 +1,2 @@
+pub mod even;
+pub mod prelude;
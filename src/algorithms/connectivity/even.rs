@@ -0,0 +1,159 @@
+use crate::prelude::maximum_flow::MaximumFlowError;
+use crate::{
+    algorithms::maximum_flow::{graph::MaximumFlowGraph, solvers::dinic::Dinic, solvers::solver::MaximumFlowSolver},
+    graph::{direction::Direction, graph::Graph, ids::NodeId},
+};
+use std::{collections::HashSet, marker::PhantomData};
+
+// edge connectivity λ(G), vertex connectivity κ(G) and minimum vertex separators with Even's algorithm
+// for directed graphs these are the edges or nodes whose removal leaves a graph which is not strongly connected
+// S is the maximum flow solver
+pub struct Connectivity<S = Dinic<usize>> {
+    num_nodes: usize,
+    is_directed: bool,
+    edges: Vec<(NodeId, NodeId)>,
+    // ordered pairs (u, v) with an edge from u to v, in both orders for undirected graphs
+    adjacent: HashSet<(usize, usize)>,
+    phantom: PhantomData<S>,
+}
+
+impl<S> Connectivity<S>
+where
+    S: MaximumFlowSolver<usize>,
+{
+    pub fn new<D: Direction, N, E>(graph: &Graph<D, N, E>) -> Self {
+        let edges: Vec<_> = graph.edges().map(|e| (e.u, e.v)).filter(|&(u, v)| u != v).collect();
+        let mut adjacent = HashSet::new();
+        for &(u, v) in edges.iter() {
+            adjacent.insert((u.index(), v.index()));
+            if !D::IS_DIRECTED {
+                adjacent.insert((v.index(), u.index()));
+            }
+        }
+        Self { num_nodes: graph.num_nodes(), is_directed: D::IS_DIRECTED, edges, adjacent, phantom: PhantomData }
+    }
+
+    // O(n) maximum flows, 0 if the graph has fewer than two nodes
+    pub fn edge_connectivity(&self) -> Result<usize, MaximumFlowError> {
+        let n = self.num_nodes;
+        if n < 2 {
+            return Ok(0);
+        }
+        let mut graph = MaximumFlowGraph::new_directed();
+        graph.add_nodes(n);
+        for &(u, v) in self.edges.iter() {
+            graph.add_edge(u, v, 1);
+            if !self.is_directed {
+                graph.add_edge(v, u, 1);
+            }
+        }
+
+        // every cut separates node 0 from some other node
+        let mut solver = S::new(&graph);
+        let mut best = self.edges.len();
+        for v in (1..n).map(NodeId) {
+            best = best.min(solver.solve_with_limit(NodeId(0), v, best)?);
+            if self.is_directed {
+                best = best.min(solver.solve_with_limit(v, NodeId(0), best)?);
+            }
+        }
+        Ok(best)
+    }
+
+    // Even's algorithm, O(κ n) maximum flows on the split graph
+    // n - 1 for complete graphs and 0 for graphs with fewer than two nodes
+    pub fn vertex_connectivity(&self) -> Result<usize, MaximumFlowError> {
+        Ok(self
+            .global_separator()?
+            .map_or(self.num_nodes.saturating_sub(1), |(value, _)| value))
+    }
+
+    // a smallest node set whose removal leaves no path from source to sink,
+    // None if there is an edge from source to sink, as no node set separates them then
+    pub fn minimum_vertex_separator(
+        &self,
+        source: NodeId,
+        sink: NodeId,
+    ) -> Result<Option<Vec<NodeId>>, MaximumFlowError> {
+        let n = self.num_nodes;
+        if source.index() >= n || sink.index() >= n || source == sink {
+            return Err(MaximumFlowError::InvalidTerminal { source, sink, num_nodes: n });
+        }
+        if self.adjacent.contains(&(source.index(), sink.index())) {
+            return Ok(None);
+        }
+        let mut solver = S::new(&self.split_graph());
+        self.separator(&mut solver, source, sink, n)
+            .map(|(_, separator)| Some(separator))
+    }
+
+    // a smallest node set whose removal disconnects the graph, None if there is none as the graph is complete
+    pub fn global_minimum_vertex_separator(&self) -> Result<Option<Vec<NodeId>>, MaximumFlowError> {
+        Ok(self.global_separator()?.map(|(_, separator)| separator))
+    }
+
+    fn global_separator(&self) -> Result<Option<(usize, Vec<NodeId>)>, MaximumFlowError> {
+        let n = self.num_nodes;
+        if n < 2 {
+            return Ok(None);
+        }
+
+        // a minimum separator misses one of the first κ + 1 nodes, and the nodes before it are all in the separator,
+        // so it separates that node from a later one
+        let mut solver = S::new(&self.split_graph());
+        let mut best: Option<(usize, Vec<NodeId>)> = None;
+        let mut i = 0;
+        while i < n && best.as_ref().is_none_or(|(value, _)| i <= *value) {
+            for j in i + 1..n {
+                for (s, t) in [(i, j), (j, i)].into_iter().take(if self.is_directed { 2 } else { 1 }) {
+                    if self.adjacent.contains(&(s, t)) {
+                        continue;
+                    }
+                    let limit = best.as_ref().map_or(n, |(value, _)| *value);
+                    let (value, separator) = self.separator(&mut solver, NodeId(s), NodeId(t), limit)?;
+                    if value < limit {
+                        best = Some((value, separator));
+                    }
+                }
+            }
+            i += 1;
+        }
+        Ok(best)
+    }
+
+    // node u is split into the in-node u and the out-node u + n joined by an arc of capacity one,
+    // and edges have capacity n so that only split arcs are cut
+    fn split_graph(&self) -> MaximumFlowGraph<usize> {
+        let n = self.num_nodes;
+        let mut graph = MaximumFlowGraph::new_directed();
+        graph.add_nodes(2 * n);
+        for u in 0..n {
+            graph.add_edge(NodeId(u), NodeId(u + n), 1);
+        }
+        for &(u, v) in self.edges.iter() {
+            graph.add_edge(NodeId(u.index() + n), v, n);
+            if !self.is_directed {
+                graph.add_edge(NodeId(v.index() + n), u, n);
+            }
+        }
+        graph
+    }
+
+    // the minimum source-sink separator for non-adjacent terminals, or only its size if it is limit or more
+    fn separator(
+        &self,
+        solver: &mut S,
+        source: NodeId,
+        sink: NodeId,
+        limit: usize,
+    ) -> Result<(usize, Vec<NodeId>), MaximumFlowError> {
+        let n = self.num_nodes;
+        let value = solver.solve_with_limit(NodeId(source.index() + n), sink, limit)?;
+        if value >= limit {
+            return Ok((value, Vec::new()));
+        }
+        let side = solver.minimum_cut()?;
+        let separator = (0..n).filter(|&u| side[u] && !side[u + n]).map(NodeId).collect();
+        Ok((value, separator))
+    }
+}
@@ -0,0 +1,2 @@
+pub mod goldberg;
+pub mod prelude;
@@ -0,0 +1,124 @@
+use crate::prelude::maximum_flow::MaximumFlowError;
+use crate::{
+    algorithms::maximum_flow::{graph::MaximumFlowGraph, solvers::dinic::Dinic, solvers::solver::MaximumFlowSolver},
+    core::numeric::FlowNum,
+    graph::{direction::Undirected, edge::Edge, graph::Graph, node::Node},
+};
+use num_traits::{CheckedAdd, CheckedMul, Num};
+use std::marker::PhantomData;
+
+// densest subgraph of an undirected graph, a node set S maximizing w(E(S)) / c(S), with Goldberg's network
+// for a guess g = p / q, the cut around the source and S in Goldberg's network has capacity
+// q W n + 2 (p c(S) - q w(E(S))), so a minimum cut finds the set maximizing w(E(S)) - g c(S);
+// instead of a binary search the guess is moved to the density of that set until no set beats it (Dinkelbach),
+// which keeps every capacity an exact integer
+// S is the maximum flow solver
+pub struct DensestSubgraph<F, S = Dinic<F>> {
+    node_weights: Vec<F>,
+    edges: Vec<(usize, usize, F)>,
+    phantom: PhantomData<S>,
+}
+
+impl<F, S> DensestSubgraph<F, S>
+where
+    F: FlowNum + Num + CheckedAdd + CheckedMul,
+    S: MaximumFlowSolver<F>,
+{
+    // every node and edge has weight one, so the density is |E(S)| / |S|
+    pub fn new<N, E>(graph: &Graph<Undirected, N, E>) -> Self {
+        Self::new_with(graph, |_| F::one(), |_| F::one())
+    }
+
+    // node weights must be positive and edge weights non-negative; a self-loop counts once in w(E(S))
+    pub fn new_with<N, E, NF, EF>(graph: &Graph<Undirected, N, E>, node_weight_fn: NF, edge_weight_fn: EF) -> Self
+    where
+        NF: Fn(&Node<N>) -> F,
+        EF: Fn(&Edge<E>) -> F,
+    {
+        let node_weights = graph.nodes().map(node_weight_fn).collect();
+        let edges = graph
+            .edges()
+            .map(|e| (e.u.index(), e.v.index(), edge_weight_fn(e)))
+            .collect();
+        Self { node_weights, edges, phantom: PhantomData }
+    }
+
+    // returns a densest node set S with w(E(S)) and c(S), whose ratio is the density,
+    // or None if the graph has no nodes; InvalidParameter if a weight is out of range or a capacity overflows
+    pub fn solve(&self) -> Result<Option<(Vec<bool>, F, F)>, MaximumFlowError> {
+        let n = self.node_weights.len();
+        if n == 0 {
+            return Ok(None);
+        }
+        if self.node_weights.iter().any(|&c| c <= F::zero()) || self.edges.iter().any(|&(_, _, w)| w < F::zero()) {
+            return Err(MaximumFlowError::InvalidParameter);
+        }
+
+        // weighted degrees, with self-loops counted twice so that the degrees in S sum to 2 w(E(S)) + w(δ(S))
+        let mut degrees = vec![F::zero(); n];
+        for &(u, v, w) in self.edges.iter() {
+            degrees[u] = add(degrees[u], w)?;
+            degrees[v] = add(degrees[v], w)?;
+        }
+        let total = degrees.iter().try_fold(F::zero(), |sum, &d| add(sum, d))?;
+
+        let mut best = vec![true; n];
+        let mut density = self.density(&best)?;
+        loop {
+            let (p, q) = density;
+            let mut graph = MaximumFlowGraph::new_directed();
+            let nodes = graph.add_nodes(n);
+            let (source, sink) = (graph.add_node(), graph.add_node());
+            let q_total = mul(q, total)?;
+            for u in 0..n {
+                // q W >= q d(u), so the capacity is non-negative
+                graph.add_edge(source, nodes[u], q_total);
+                let upper = add(q_total, mul(add(p, p)?, self.node_weights[u])?)? - q * degrees[u];
+                graph.add_edge(nodes[u], sink, upper);
+            }
+            for &(u, v, w) in self.edges.iter().filter(|&&(u, v, _)| u != v) {
+                graph.add_edge(nodes[u], nodes[v], q * w);
+                graph.add_edge(nodes[v], nodes[u], q * w);
+            }
+
+            let mut solver = S::new(&graph);
+            solver.solve(source, sink)?;
+            let mut side = solver.minimum_cut()?;
+            side.truncate(n);
+
+            // the set found beats the guess unless it is empty
+            if side.iter().all(|&s| !s) {
+                break;
+            }
+            let candidate = self.density(&side)?;
+            if mul(candidate.0, q)? <= mul(p, candidate.1)? {
+                break;
+            }
+            (best, density) = (side, candidate);
+        }
+        Ok(Some((best, density.0, density.1)))
+    }
+
+    fn density(&self, side: &[bool]) -> Result<(F, F), MaximumFlowError> {
+        let edges = self
+            .edges
+            .iter()
+            .filter(|&&(u, v, _)| side[u] && side[v])
+            .try_fold(F::zero(), |sum, &(_, _, w)| add(sum, w))?;
+        let nodes = self
+            .node_weights
+            .iter()
+            .zip(side)
+            .filter(|(_, s)| **s)
+            .try_fold(F::zero(), |sum, (&c, _)| add(sum, c))?;
+        Ok((edges, nodes))
+    }
+}
+
+fn add<F: CheckedAdd>(a: F, b: F) -> Result<F, MaximumFlowError> {
+    a.checked_add(&b).ok_or(MaximumFlowError::InvalidParameter)
+}
+
+fn mul<F: CheckedMul>(a: F, b: F) -> Result<F, MaximumFlowError> {
+    a.checked_mul(&b).ok_or(MaximumFlowError::InvalidParameter)
+}
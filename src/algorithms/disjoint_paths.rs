@@ -0,0 +1,2 @@
+pub mod menger;
+pub mod prelude;
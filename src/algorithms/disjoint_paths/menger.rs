@@ -0,0 +1,160 @@
+use crate::prelude::maximum_flow::MaximumFlowError;
+use crate::{
+    algorithms::maximum_flow::{graph::MaximumFlowGraph, solvers::dinic::Dinic, solvers::solver::MaximumFlowSolver},
+    graph::{
+        direction::Direction,
+        graph::Graph,
+        ids::{EdgeId, NodeId},
+    },
+};
+use std::marker::PhantomData;
+
+// edge- and vertex-disjoint s-t paths (Menger) from a unit capacity maximum flow
+// undirected edges may be traversed in either direction; S is the maximum flow solver
+pub struct DisjointPaths<S = Dinic<usize>> {
+    num_nodes: usize,
+    is_directed: bool,
+    edges: Vec<(NodeId, NodeId)>,
+    phantom: PhantomData<S>,
+}
+
+impl<S> DisjointPaths<S>
+where
+    S: MaximumFlowSolver<usize>,
+{
+    pub fn new<D: Direction, N, E>(graph: &Graph<D, N, E>) -> Self {
+        let edges = graph.edges().map(|e| (e.u, e.v)).collect();
+        Self { num_nodes: graph.num_nodes(), is_directed: D::IS_DIRECTED, edges, phantom: PhantomData }
+    }
+
+    // paths sharing no edge, as many as possible if k is None and at most k otherwise
+    // each path is a simple path given by the edge ids from source to sink
+    pub fn edge_disjoint(
+        &self,
+        source: NodeId,
+        sink: NodeId,
+        k: Option<usize>,
+    ) -> Result<Vec<Vec<EdgeId>>, MaximumFlowError> {
+        self.validate(source, sink)?;
+        self.solve(source, sink, k, false)
+    }
+
+    // paths sharing no node other than source and sink, as many as possible if k is None and at most k otherwise
+    // every node but the terminals is split into an in-node and an out-node joined by an arc of capacity one
+    pub fn vertex_disjoint(
+        &self,
+        source: NodeId,
+        sink: NodeId,
+        k: Option<usize>,
+    ) -> Result<Vec<Vec<EdgeId>>, MaximumFlowError> {
+        self.validate(source, sink)?;
+        self.solve(source, sink, k, true)
+    }
+
+    fn validate(&self, source: NodeId, sink: NodeId) -> Result<(), MaximumFlowError> {
+        if source.index() >= self.num_nodes || sink.index() >= self.num_nodes || source == sink {
+            return Err(MaximumFlowError::InvalidTerminal { source, sink, num_nodes: self.num_nodes });
+        }
+        Ok(())
+    }
+
+    fn solve(
+        &self,
+        source: NodeId,
+        sink: NodeId,
+        k: Option<usize>,
+        split: bool,
+    ) -> Result<Vec<Vec<EdgeId>>, MaximumFlowError> {
+        let n = self.num_nodes;
+        // with splitting, u is the in-node and u + n the out-node
+        let out_node = |u: NodeId| if split { NodeId(u.index() + n) } else { u };
+
+        let mut graph = MaximumFlowGraph::new_directed();
+        graph.add_nodes(if split { 2 * n } else { n });
+        if split {
+            for u in (0..n).map(NodeId).filter(|&u| u != source && u != sink) {
+                graph.add_edge(u, out_node(u), 1);
+            }
+        }
+
+        // arcs entering the source or leaving the sink are never on a simple s-t path
+        let mut arc_to_edge = Vec::new();
+        for (edge_id, &(u, v)) in self.edges.iter().enumerate() {
+            let directions = if self.is_directed {
+                &[(u, v)][..]
+            } else {
+                &[(u, v), (v, u)][..]
+            };
+            for &(a, b) in directions {
+                if a != b && a != sink && b != source {
+                    graph.add_edge(out_node(a), b, 1);
+                    arc_to_edge.push((EdgeId(edge_id), a, b));
+                }
+            }
+        }
+
+        let mut solver = S::new(&graph);
+        let value = match k {
+            Some(k) => solver.solve_with_limit(out_node(source), sink, k)?,
+            None => solver.solve(out_node(source), sink)?,
+        };
+        let flows = solver.flows()?;
+
+        // arcs carrying flow, grouped by tail
+        let offset = if split { n - 2 } else { 0 };
+        let mut used: Vec<Vec<(EdgeId, NodeId)>> = vec![Vec::new(); n];
+        for (i, &(edge_id, a, b)) in arc_to_edge.iter().enumerate() {
+            if flows[offset + i] > 0 {
+                used[a.index()].push((edge_id, b));
+            }
+        }
+        // an undirected edge carrying flow both ways is a cycle of length two, which is dropped
+        if !self.is_directed {
+            for a in 0..n {
+                let mut i = 0;
+                while i < used[a].len() {
+                    let (edge_id, b) = used[a][i];
+                    if let Some(j) = used[b.index()]
+                        .iter()
+                        .position(|&(e, c)| e == edge_id && c.index() == a)
+                    {
+                        used[b.index()].swap_remove(j);
+                        used[a].swap_remove(i);
+                    } else {
+                        i += 1;
+                    }
+                }
+            }
+        }
+
+        // follows used arcs from the source, dropping the cycles closed on the way
+        let mut paths = Vec::with_capacity(value);
+        let mut on_path = vec![usize::MAX; n];
+        for _ in 0..value {
+            let mut nodes = vec![source];
+            let mut path = Vec::new();
+            on_path[source.index()] = 0;
+            let mut u = source;
+            while u != sink {
+                let (edge_id, v) = used[u.index()].pop().unwrap();
+                if on_path[v.index()] != usize::MAX {
+                    let len = on_path[v.index()];
+                    for w in nodes.drain(len + 1..) {
+                        on_path[w.index()] = usize::MAX;
+                    }
+                    path.truncate(len);
+                } else {
+                    on_path[v.index()] = nodes.len();
+                    nodes.push(v);
+                    path.push(edge_id);
+                }
+                u = v;
+            }
+            for w in nodes {
+                on_path[w.index()] = usize::MAX;
+            }
+            paths.push(path);
+        }
+        Ok(paths)
+    }
+}
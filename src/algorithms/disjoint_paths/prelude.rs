@@ -0,0 +1 @@
+pub use super::menger::DisjointPaths;
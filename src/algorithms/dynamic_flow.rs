@@ -0,0 +1,2 @@
+pub mod flows_over_time;
+pub mod prelude;
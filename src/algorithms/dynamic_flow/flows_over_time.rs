@@ -0,0 +1,283 @@
+use crate::prelude::minimum_cost_flow::{MinimumCostFlowSolver, PrimalNetworkSimplex};
+use crate::{
+    algorithms::minimum_cost_flow::{
+        edge::MinimumCostFlowEdge, error::MinimumCostFlowError, graph::MinimumCostFlowGraph,
+    },
+    core::numeric::CostNum,
+    graph::{
+        direction::Directed,
+        edge::Edge,
+        graph::Graph,
+        ids::{EdgeId, NodeId},
+    },
+};
+use num_traits::NumCast;
+use std::marker::PhantomData;
+
+// flows over time in discrete time: flow entering edge e at step θ arrives at its head at step θ + τ_e,
+// and at most c_e units enter e per step; flow within horizon T reaches the sink by step T - 1
+// S is the minimum cost flow solver
+pub struct FlowsOverTime<F, S = PrimalNetworkSimplex<F>> {
+    num_nodes: usize,
+    // (u, v, capacity, transit time)
+    edges: Vec<(NodeId, NodeId, F, F)>,
+    phantom: PhantomData<S>,
+}
+
+// a static flow sent along each of its paths at every step from which the path still reaches the sink in time,
+// that is horizon - transit time steps; paths holds (edges, rate, transit time)
+pub struct TemporallyRepeatedFlow<F> {
+    pub value: F,
+    pub flows: Vec<F>,
+    pub paths: Vec<(Vec<EdgeId>, F, F)>,
+}
+
+// flows[e][θ] enters edge e at step θ, and flow may wait at the nodes;
+// arrivals[θ] is the amount which has reached the sink by step θ, the largest possible for every θ at once
+pub struct EarliestArrivalFlow<F> {
+    pub value: F,
+    pub flows: Vec<Vec<F>>,
+    pub arrivals: Vec<F>,
+}
+
+impl<F, S> FlowsOverTime<F, S>
+where
+    F: CostNum + Default + NumCast,
+    S: MinimumCostFlowSolver<F>,
+{
+    // upper is the capacity per step and cost the transit time; lower bounds are ignored
+    pub fn new<N>(graph: &Graph<Directed, N, MinimumCostFlowEdge<F>>) -> Self {
+        Self::new_with(graph, |e| e.data.upper, |e| e.data.cost)
+    }
+
+    // capacities and transit times must be non-negative, or the solve methods return InvalidParameter
+    pub fn new_with<N, E, CF, TF>(graph: &Graph<Directed, N, E>, capacity_fn: CF, transit_fn: TF) -> Self
+    where
+        CF: Fn(&Edge<E>) -> F,
+        TF: Fn(&Edge<E>) -> F,
+    {
+        let edges = graph
+            .edges()
+            .map(|e| (e.u, e.v, capacity_fn(e), transit_fn(e)))
+            .collect();
+        Self { num_nodes: graph.num_nodes(), edges, phantom: PhantomData }
+    }
+
+    // maximum flow within the horizon, temporally repeated (Ford and Fulkerson): a static flow x maximizing
+    // horizon * |x| - sum_e τ_e x_e, found as a minimum cost circulation with a return arc of cost -horizon
+    pub fn maximum_flow(
+        &self,
+        source: NodeId,
+        sink: NodeId,
+        horizon: usize,
+    ) -> Result<TemporallyRepeatedFlow<F>, MinimumCostFlowError> {
+        self.validate(source, sink)?;
+        let m = self.edges.len();
+        let mut graph = MinimumCostFlowGraph::default();
+        graph.add_nodes(self.num_nodes);
+        for &(u, v, capacity, transit) in self.edges.iter() {
+            graph.add_edge(u, v, F::zero(), capacity, transit);
+        }
+        graph.add_edge(sink, source, F::zero(), self.source_capacity(source), -Self::cast(horizon));
+
+        let mut solver = S::new(&graph);
+        let value = -solver.solve()?;
+        let mut flows = solver.flows()?;
+        flows.truncate(m);
+        let paths = self.decompose(source, sink, &flows);
+        Ok(TemporallyRepeatedFlow { value, flows, paths })
+    }
+
+    // the smallest horizon within which demand reaches the sink, with a temporally repeated flow sending it,
+    // or None if the sink cannot be reached; the horizon is found by doubling and then binary search
+    pub fn quickest_flow(
+        &self,
+        source: NodeId,
+        sink: NodeId,
+        demand: F,
+    ) -> Result<Option<(usize, TemporallyRepeatedFlow<F>)>, MinimumCostFlowError> {
+        self.validate(source, sink)?;
+        if demand <= F::zero() {
+            return Ok(Some((0, self.maximum_flow(source, sink, 0)?)));
+        }
+        if !self.reachable(source, sink) {
+            return Ok(None);
+        }
+
+        let mut hi = 1;
+        let mut best = self.maximum_flow(source, sink, hi)?;
+        while best.value < demand {
+            hi *= 2;
+            best = self.maximum_flow(source, sink, hi)?;
+        }
+        // the value is non-decreasing in the horizon, so lo never suffices and hi always does
+        let mut lo = hi / 2;
+        while hi - lo > 1 {
+            let mid = lo + (hi - lo) / 2;
+            let flow = self.maximum_flow(source, sink, mid)?;
+            if flow.value >= demand {
+                (hi, best) = (mid, flow);
+            } else {
+                lo = mid;
+            }
+        }
+        Ok(Some((hi, best)))
+    }
+
+    // earliest arrival flow in the time-expanded network, with a copy of every node for each step and
+    // holdover arcs letting flow wait; the arrival arc of step θ costs θ and the return arc -horizon,
+    // so a minimum cost circulation maximizes sum_θ arrivals[θ], which an earliest arrival flow maximizes termwise
+    pub fn earliest_arrival_flow(
+        &self,
+        source: NodeId,
+        sink: NodeId,
+        horizon: usize,
+    ) -> Result<EarliestArrivalFlow<F>, MinimumCostFlowError> {
+        self.validate(source, sink)?;
+        let (n, m) = (self.num_nodes, self.edges.len());
+        let copy = |u: NodeId, step: usize| NodeId(step * n + u.index());
+        let mut graph = MinimumCostFlowGraph::default();
+        graph.add_nodes(n * horizon);
+        let super_sink = graph.add_node();
+
+        // larger than the total flow over all steps
+        let infinity = (0..horizon).fold(F::zero(), |sum, _| sum + self.source_capacity(source));
+        let mut arcs = vec![Vec::new(); m];
+        for step in 0..horizon {
+            for (e, &(u, v, capacity, transit)) in self.edges.iter().enumerate() {
+                let arrival = step + transit.to_usize().unwrap();
+                if arrival < horizon {
+                    let arc = graph.add_edge(copy(u, step), copy(v, arrival), F::zero(), capacity, F::zero());
+                    arcs[e].push((step, arc.unwrap()));
+                }
+            }
+            for u in (0..n).map(NodeId) {
+                if step + 1 < horizon {
+                    graph.add_edge(copy(u, step), copy(u, step + 1), F::zero(), infinity, F::zero());
+                }
+            }
+        }
+        let arrival_arcs: Vec<_> = (0..horizon)
+            .map(|step| graph.add_edge(copy(sink, step), super_sink, F::zero(), infinity, Self::cast(step)))
+            .collect();
+        if horizon > 0 {
+            graph.add_edge(super_sink, copy(source, 0), F::zero(), infinity, -Self::cast(horizon));
+        }
+
+        let mut solver = S::new(&graph);
+        solver.solve()?;
+        let mut flows = vec![vec![F::zero(); horizon]; m];
+        for (e, arcs) in arcs.iter().enumerate() {
+            for &(step, arc) in arcs.iter() {
+                flows[e][step] = solver.flow(arc)?;
+            }
+        }
+        let mut arrivals = Vec::with_capacity(horizon);
+        let mut value = F::zero();
+        for arc in arrival_arcs {
+            value += solver.flow(arc.unwrap())?;
+            arrivals.push(value);
+        }
+        Ok(EarliestArrivalFlow { value, flows, arrivals })
+    }
+
+    fn validate(&self, source: NodeId, sink: NodeId) -> Result<(), MinimumCostFlowError> {
+        if source.index() >= self.num_nodes || sink.index() >= self.num_nodes || source == sink {
+            return Err(MinimumCostFlowError::InvalidTerminal { source, sink, num_nodes: self.num_nodes });
+        }
+        // transit times index the steps of the time-expanded network
+        if self
+            .edges
+            .iter()
+            .any(|&(_, _, capacity, transit)| capacity < F::zero() || transit.to_usize().is_none())
+        {
+            return Err(MinimumCostFlowError::InvalidParameter);
+        }
+        Ok(())
+    }
+
+    fn cast(x: usize) -> F {
+        F::from(x).unwrap()
+    }
+
+    // an upper bound on the static flow
+    fn source_capacity(&self, source: NodeId) -> F {
+        self.edges
+            .iter()
+            .filter(|&&(u, _, _, _)| u == source)
+            .fold(F::zero(), |sum, &(_, _, capacity, _)| sum + capacity)
+    }
+
+    fn reachable(&self, source: NodeId, sink: NodeId) -> bool {
+        let mut seen = vec![false; self.num_nodes];
+        let mut stack = vec![source];
+        seen[source.index()] = true;
+        while let Some(u) = stack.pop() {
+            for &(_, v, _, _) in self.edges.iter().filter(|&&(x, _, c, _)| x == u && c > F::zero()) {
+                if !seen[v.index()] {
+                    seen[v.index()] = true;
+                    stack.push(v);
+                }
+            }
+        }
+        seen[sink.index()]
+    }
+
+    // splits the static flow into source-sink paths with their rates and transit times, dropping cycles
+    fn decompose(&self, source: NodeId, sink: NodeId, flows: &[F]) -> Vec<(Vec<EdgeId>, F, F)> {
+        let mut remaining = flows.to_vec();
+        let mut out_edges = vec![Vec::new(); self.num_nodes];
+        for (e, &(u, _, _, _)) in self.edges.iter().enumerate() {
+            out_edges[u.index()].push(e);
+        }
+        let mut current = vec![0; self.num_nodes];
+        let mut next_edge = |u: NodeId, remaining: &[F]| {
+            let edges = &out_edges[u.index()];
+            while current[u.index()] < edges.len() && remaining[edges[current[u.index()]]] <= F::zero() {
+                current[u.index()] += 1;
+            }
+            edges.get(current[u.index()]).copied()
+        };
+
+        let mut paths = Vec::new();
+        // walks from the source along edges with remaining flow, cancelling the cycles it closes
+        let mut position = vec![None; self.num_nodes];
+        while let Some(e) = next_edge(source, &remaining) {
+            let mut walk = vec![e];
+            position[source.index()] = Some(0);
+            let mut u = self.edges[e].1;
+            while u != sink {
+                if let Some(start) = position[u.index()] {
+                    let cycle: Vec<usize> = walk.drain(start..).collect();
+                    let rate = cycle.iter().map(|&e| remaining[e]).min().unwrap();
+                    for &e in cycle.iter() {
+                        remaining[e] -= rate;
+                        position[self.edges[e].1.index()] = None;
+                    }
+                    position[u.index()] = Some(start);
+                } else {
+                    position[u.index()] = Some(walk.len());
+                }
+                // conservation leaves flow on some edge leaving u, unless u is the source and only cycles are left
+                let Some(e) = next_edge(u, &remaining) else {
+                    break;
+                };
+                walk.push(e);
+                u = self.edges[e].1;
+            }
+            if u != sink {
+                position[source.index()] = None;
+                continue;
+            }
+            let rate = walk.iter().map(|&e| remaining[e]).min().unwrap();
+            let mut transit = F::zero();
+            for &e in walk.iter() {
+                remaining[e] -= rate;
+                transit += self.edges[e].3;
+                position[self.edges[e].0.index()] = None;
+            }
+            paths.push((walk.into_iter().map(EdgeId).collect(), rate, transit));
+        }
+        paths
+    }
+}
@@ -0,0 +1 @@
+pub use super::flows_over_time::{EarliestArrivalFlow, FlowsOverTime, TemporallyRepeatedFlow};
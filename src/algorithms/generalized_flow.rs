@@ -0,0 +1,4 @@
+pub mod edge;
+pub mod prelude;
+pub mod simplex;
+pub mod truemper;
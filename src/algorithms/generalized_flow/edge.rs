@@ -0,0 +1,7 @@
+// flow g entering the edge at its tail leaves it at its head as gain * g, and upper bounds g
+#[derive(Clone, Debug)]
+pub struct GeneralizedFlowEdge<F> {
+    pub upper: F,
+    pub gain: F,
+    pub cost: F,
+}
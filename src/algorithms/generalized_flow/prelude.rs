@@ -0,0 +1,3 @@
+pub use super::edge::GeneralizedFlowEdge;
+pub use super::simplex::{GeneralizedMinimumCostFlow, GeneralizedMinimumCostFlowResult};
+pub use super::truemper::{GeneralizedMaximumFlow, GeneralizedMaximumFlowResult};
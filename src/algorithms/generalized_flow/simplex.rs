@@ -0,0 +1,246 @@
+use crate::{
+    algorithms::{
+        generalized_flow::edge::GeneralizedFlowEdge,
+        minimum_cost_flow::{error::MinimumCostFlowError, node::MinimumCostFlowNode},
+    },
+    core::numeric::GainNum,
+    graph::{direction::Directed, edge::Edge, graph::Graph, ids::NodeId, node::Node},
+};
+use num_traits::Signed;
+
+// generalized minimum cost flow: every node v satisfies sum_out g_e - sum_in gain_e g_e = b(v) with 0 <= g_e <= upper_e,
+// and sum_e cost_e g_e is minimized
+// gains take away the tree structure of bases that the network simplex relies on, so this is the bounded variable
+// primal simplex on a dense tableau of the generalized node-arc matrix; phase 1 drives out one artificial variable
+// per node and phase 2 minimizes the cost, both with Bland's rule against cycling
+// the tableau keeps n rows of m + n columns, so it takes O(n (m + n)) memory, and each pivot spends O(n (m + n))
+// on pricing the reduced costs and on the row updates; this suits small instances, not large sparse networks
+pub struct GeneralizedMinimumCostFlow<F> {
+    supplies: Vec<F>,
+    // (u, v, upper, gain, cost)
+    edges: Vec<(NodeId, NodeId, F, F, F)>,
+    epsilon: F,
+}
+
+pub struct GeneralizedMinimumCostFlowResult<F> {
+    pub cost: F,
+    // flow_in[e] enters e at its tail and flow_out[e] = gain * flow_in[e] leaves it at its head
+    pub flow_in: Vec<F>,
+    pub flow_out: Vec<F>,
+    // optimal node prices p: cost_e - p(u) + gain_e p(v) is non-negative on every edge below its upper bound
+    // and non-positive on every edge with positive flow
+    pub potentials: Vec<F>,
+}
+
+struct Tableau<F> {
+    rows: Vec<Vec<F>>,
+    values: Vec<F>,
+    uppers: Vec<Option<F>>,
+    basis: Vec<usize>,
+    is_basic: Vec<bool>,
+    at_upper: Vec<bool>,
+}
+
+impl<F> GeneralizedMinimumCostFlow<F>
+where
+    F: GainNum + Signed,
+{
+    pub fn new(graph: &Graph<Directed, MinimumCostFlowNode<F>, GeneralizedFlowEdge<F>>) -> Self {
+        Self::new_with(graph, |node| node.data.b, |e| e.data.upper, |e| e.data.gain, |e| e.data.cost)
+    }
+
+    // capacities must be non-negative and gains positive
+    pub fn new_with<N, E, BF, UF, GF, CF>(
+        graph: &Graph<Directed, N, E>,
+        supply_fn: BF,
+        upper_fn: UF,
+        gain_fn: GF,
+        cost_fn: CF,
+    ) -> Self
+    where
+        BF: Fn(&Node<N>) -> F,
+        UF: Fn(&Edge<E>) -> F,
+        GF: Fn(&Edge<E>) -> F,
+        CF: Fn(&Edge<E>) -> F,
+    {
+        let supplies = graph.nodes().map(supply_fn).collect();
+        let edges = graph
+            .edges()
+            .map(|e| (e.u, e.v, upper_fn(e), gain_fn(e), cost_fn(e)))
+            .collect();
+        Self { supplies, edges, epsilon: F::zero() }
+    }
+
+    // values within epsilon of zero count as zero; zero by default, which is exact for rational numbers,
+    // while floating point numbers need a small epsilon
+    pub fn set_epsilon(mut self, epsilon: F) -> Self {
+        self.epsilon = epsilon;
+        self
+    }
+
+    pub fn solve(&self) -> Result<GeneralizedMinimumCostFlowResult<F>, MinimumCostFlowError> {
+        let (n, m) = (self.supplies.len(), self.edges.len());
+        if self
+            .edges
+            .iter()
+            .any(|&(_, _, upper, gain, _)| upper < F::zero() || gain <= F::zero())
+        {
+            return Err(MinimumCostFlowError::InvalidParameter);
+        }
+
+        // rows are negated where the supply is negative, so that the artificial variables start feasible
+        let signs: Vec<F> = self
+            .supplies
+            .iter()
+            .map(|&b| if b < F::zero() { -F::one() } else { F::one() })
+            .collect();
+        let mut rows = vec![vec![F::zero(); m + n]; n];
+        for (e, &(u, v, _, gain, _)) in self.edges.iter().enumerate() {
+            rows[u.index()][e] = rows[u.index()][e] + signs[u.index()];
+            rows[v.index()][e] = rows[v.index()][e] - signs[v.index()] * gain;
+        }
+        for (v, row) in rows.iter_mut().enumerate() {
+            row[m + v] = F::one();
+        }
+        let mut values = vec![F::zero(); m + n];
+        for (v, &b) in self.supplies.iter().enumerate() {
+            values[m + v] = b.abs();
+        }
+        let mut uppers: Vec<_> = self.edges.iter().map(|&(_, _, upper, _, _)| Some(upper)).collect();
+        uppers.resize(m + n, None);
+        let mut tableau = Tableau {
+            rows,
+            values,
+            uppers,
+            basis: (m..m + n).collect(),
+            is_basic: (0..m + n).map(|j| j >= m).collect(),
+            at_upper: vec![false; m + n],
+        };
+
+        // phase 1
+        let mut costs = vec![F::zero(); m + n];
+        costs[m..].fill(F::one());
+        self.simplex(&mut tableau, &costs, m + n)?;
+        if tableau.values[m..].iter().any(|&a| a > self.epsilon) {
+            return Err(MinimumCostFlowError::Infeasible);
+        }
+
+        // phase 2, with the artificial variables fixed at zero
+        tableau.values[m..].fill(F::zero());
+        tableau.uppers[m..].fill(Some(F::zero()));
+        for (e, &(_, _, _, _, cost)) in self.edges.iter().enumerate() {
+            costs[e] = cost;
+        }
+        costs[m..].fill(F::zero());
+        self.simplex(&mut tableau, &costs, m)?;
+
+        // the column of an artificial variable is the inverse of the basis applied to a unit vector
+        let potentials = (0..n)
+            .map(|v| {
+                let price = (0..n).fold(F::zero(), |sum, i| sum + costs[tableau.basis[i]] * tableau.rows[i][m + v]);
+                signs[v] * price
+            })
+            .collect();
+        let flow_in: Vec<F> = tableau.values[..m].to_vec();
+        let flow_out = flow_in
+            .iter()
+            .zip(self.edges.iter())
+            .map(|(&f, &(_, _, _, gain, _))| f * gain)
+            .collect();
+        let cost = flow_in
+            .iter()
+            .zip(self.edges.iter())
+            .fold(F::zero(), |sum, (&f, &(_, _, _, _, cost))| sum + f * cost);
+        Ok(GeneralizedMinimumCostFlowResult { cost, flow_in, flow_out, potentials })
+    }
+
+    // only the first num_candidates variables may enter the basis
+    fn simplex(&self, t: &mut Tableau<F>, costs: &[F], num_candidates: usize) -> Result<(), MinimumCostFlowError> {
+        let eps = self.epsilon;
+        loop {
+            // Bland's rule: the eligible variable of smallest index enters
+            let entering = (0..num_candidates).find(|&j| {
+                if t.is_basic[j] || t.uppers[j] == Some(F::zero()) {
+                    return false;
+                }
+                let reduced = (0..t.rows.len()).fold(costs[j], |d, i| d - costs[t.basis[i]] * t.rows[i][j]);
+                if t.at_upper[j] { reduced > eps } else { reduced < -eps }
+            });
+            let Some(j) = entering else {
+                return Ok(());
+            };
+            let direction = if t.at_upper[j] { -F::one() } else { F::one() };
+
+            // the step is bounded by the range of j and by the first basic variable reaching a bound,
+            // ties going to the smallest index
+            let mut step = t.uppers[j];
+            let mut leaving: Option<(usize, bool)> = None;
+            for (i, row) in t.rows.iter().enumerate() {
+                let alpha = direction * row[j];
+                let basic = t.basis[i];
+                let (limit, to_upper) = if alpha > eps {
+                    (t.values[basic] / alpha, false)
+                } else if alpha < -eps {
+                    match t.uppers[basic] {
+                        Some(upper) => ((upper - t.values[basic]) / -alpha, true),
+                        None => continue,
+                    }
+                } else {
+                    continue;
+                };
+                let better = match (step, leaving) {
+                    (None, _) => true,
+                    (Some(s), None) => limit < s,
+                    (Some(s), Some((r, _))) => limit < s || (limit == s && basic < t.basis[r]),
+                };
+                if better {
+                    step = Some(limit);
+                    leaving = Some((i, to_upper));
+                }
+            }
+            // every variable is bounded in phase 2 and the phase 1 objective is bounded below
+            let Some(step) = step else {
+                return Err(MinimumCostFlowError::Infeasible);
+            };
+            let step = if step < F::zero() { F::zero() } else { step };
+
+            t.values[j] = t.values[j] + direction * step;
+            for i in 0..t.rows.len() {
+                let basic = t.basis[i];
+                t.values[basic] = t.values[basic] - direction * step * t.rows[i][j];
+            }
+
+            let Some((r, to_upper)) = leaving else {
+                t.at_upper[j] = !t.at_upper[j];
+                continue;
+            };
+            let leaving = t.basis[r];
+            t.values[leaving] = if to_upper {
+                t.uppers[leaving].unwrap()
+            } else {
+                F::zero()
+            };
+            t.is_basic[leaving] = false;
+            t.at_upper[leaving] = to_upper;
+            t.is_basic[j] = true;
+            t.at_upper[j] = false;
+            t.basis[r] = j;
+
+            let pivot = t.rows[r][j];
+            let pivot_row: Vec<F> = t.rows[r].iter().map(|&x| x / pivot).collect();
+            for (i, row) in t.rows.iter_mut().enumerate() {
+                if i == r {
+                    row.clone_from(&pivot_row);
+                    continue;
+                }
+                let factor = row[j];
+                if factor == F::zero() {
+                    continue;
+                }
+                for (x, &p) in row.iter_mut().zip(pivot_row.iter()) {
+                    *x = *x - factor * p;
+                }
+            }
+        }
+    }
+}
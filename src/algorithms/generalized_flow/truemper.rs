@@ -0,0 +1,236 @@
+use crate::prelude::maximum_flow::MaximumFlowError;
+use crate::{
+    algorithms::generalized_flow::edge::GeneralizedFlowEdge,
+    core::numeric::GainNum,
+    graph::{direction::Directed, edge::Edge, graph::Graph, ids::NodeId},
+};
+use num_traits::Signed;
+
+// generalized maximum flow: the source has unlimited supply, the flow on an edge is multiplied by its gain, and the
+// flow reaching the sink is maximized; nodes other than the source may keep an excess, which is lost
+// flow-generating cycles from which the sink can be reached are cancelled first, leaving their surplus as excess;
+// then flow is augmented from the source or an excess along highest gain paths to the sink (Truemper), which keeps
+// the residual network free of generating cycles, until no node with supply reaches the sink
+pub struct GeneralizedMaximumFlow<F> {
+    num_nodes: usize,
+    // (u, v, upper, gain)
+    edges: Vec<(NodeId, NodeId, F, F)>,
+    epsilon: F,
+}
+
+pub struct GeneralizedMaximumFlowResult<F> {
+    pub value: F,
+    // flow_in[e] enters e at its tail and flow_out[e] = gain * flow_in[e] leaves it at its head
+    pub flow_in: Vec<F>,
+    pub flow_out: Vec<F>,
+    // the largest gain of a residual path from each node to the sink, that is the worth of a unit of flow at the node
+    // in units at the sink; None if the sink cannot be reached
+    pub labels: Vec<Option<F>>,
+}
+
+// the residual network: arc 2e runs along edge e with its gain and arc 2e + 1 against it with the inverse gain;
+// residual capacities are measured at the tail of an arc
+struct Residual<'a, F> {
+    edges: &'a [(NodeId, NodeId, F, F)],
+    flows: Vec<F>,
+    excesses: Vec<F>,
+}
+
+impl<F> GeneralizedMaximumFlow<F>
+where
+    F: GainNum + Signed,
+{
+    pub fn new<N>(graph: &Graph<Directed, N, GeneralizedFlowEdge<F>>) -> Self {
+        Self::new_with(graph, |e| e.data.upper, |e| e.data.gain)
+    }
+
+    // capacities must be non-negative and gains positive
+    pub fn new_with<N, E, UF, GF>(graph: &Graph<Directed, N, E>, upper_fn: UF, gain_fn: GF) -> Self
+    where
+        UF: Fn(&Edge<E>) -> F,
+        GF: Fn(&Edge<E>) -> F,
+    {
+        let edges = graph.edges().map(|e| (e.u, e.v, upper_fn(e), gain_fn(e))).collect();
+        Self { num_nodes: graph.num_nodes(), edges, epsilon: F::zero() }
+    }
+
+    // residual capacities up to epsilon count as zero, and gains must beat 1 + epsilon to improve a path;
+    // zero by default, which is exact for rational numbers, while floating point numbers need a small epsilon
+    pub fn set_epsilon(mut self, epsilon: F) -> Self {
+        self.epsilon = epsilon;
+        self
+    }
+
+    pub fn solve(&self, source: NodeId, sink: NodeId) -> Result<GeneralizedMaximumFlowResult<F>, MaximumFlowError> {
+        let n = self.num_nodes;
+        if source.index() >= n || sink.index() >= n || source == sink {
+            return Err(MaximumFlowError::InvalidTerminal { source, sink, num_nodes: n });
+        }
+        if self
+            .edges
+            .iter()
+            .any(|&(_, _, upper, gain)| upper < F::zero() || gain <= F::zero())
+        {
+            return Err(MaximumFlowError::InvalidParameter);
+        }
+
+        let mut residual =
+            Residual { edges: &self.edges, flows: vec![F::zero(); self.edges.len()], excesses: vec![F::zero(); n] };
+        let labels = loop {
+            let Paths { labels, pred, cycle } = self.highest_gain_paths(&residual, sink);
+            if let Some(u) = cycle {
+                // sends as much flow as possible around the cycle, leaving the surplus at u
+                let mut arcs = Vec::new();
+                let mut v = u;
+                while arcs.is_empty() || v != u {
+                    let arc = pred[v.index()].unwrap();
+                    arcs.push(arc);
+                    v = residual.head(arc);
+                }
+                residual.send(u, &arcs, None);
+                continue;
+            }
+
+            let start = if labels[source.index()].is_some() {
+                Some(source)
+            } else {
+                (0..n)
+                    .map(NodeId)
+                    .find(|&u| u != sink && residual.excesses[u.index()] > self.epsilon && labels[u.index()].is_some())
+            };
+            let Some(start) = start else {
+                break labels;
+            };
+
+            let mut path = Vec::new();
+            let mut u = start;
+            while u != sink {
+                let arc = pred[u.index()].unwrap();
+                path.push(arc);
+                u = residual.head(arc);
+            }
+            let supply = if start == source {
+                None
+            } else {
+                Some(residual.excesses[start.index()])
+            };
+            residual.send(start, &path, supply);
+        };
+
+        let flow_in = residual.flows;
+        let flow_out = flow_in
+            .iter()
+            .zip(self.edges.iter())
+            .map(|(&f, &(_, _, _, gain))| f * gain)
+            .collect();
+        Ok(GeneralizedMaximumFlowResult { value: residual.excesses[sink.index()], flow_in, flow_out, labels })
+    }
+
+    // Bellman-Ford toward the sink on products of gains, giving the labels and the arc leaving every node on its
+    // best path; a label still improving after n rounds reveals a generating cycle
+    fn highest_gain_paths(&self, residual: &Residual<F>, sink: NodeId) -> Paths<F> {
+        let n = self.num_nodes;
+        let mut labels = vec![None; n];
+        let mut pred = vec![None; n];
+        labels[sink.index()] = Some(F::one());
+        for round in 0..n {
+            let mut updated = None;
+            for arc in 0..2 * self.edges.len() {
+                let (u, v) = (residual.tail(arc), residual.head(arc));
+                let Some(label) = labels[v.index()] else {
+                    continue;
+                };
+                if residual.capacity(arc) <= self.epsilon {
+                    continue;
+                }
+                let candidate = residual.gain(arc) * label;
+                if labels[u.index()].is_none_or(|old| candidate > old * (F::one() + self.epsilon)) {
+                    labels[u.index()] = Some(candidate);
+                    pred[u.index()] = Some(arc);
+                    updated = Some(u);
+                }
+            }
+            match updated {
+                None => break,
+                Some(u) if round + 1 == n => {
+                    // n steps back along the pointers end up on a cycle
+                    let mut u = u;
+                    for _ in 0..n {
+                        u = residual.head(pred[u.index()].unwrap());
+                    }
+                    return Paths { labels, pred, cycle: Some(u) };
+                }
+                _ => {}
+            }
+        }
+        Paths { labels, pred, cycle: None }
+    }
+}
+
+struct Paths<F> {
+    labels: Vec<Option<F>>,
+    pred: Vec<Option<usize>>,
+    // a node on a generating cycle of the pointers
+    cycle: Option<NodeId>,
+}
+
+impl<F> Residual<'_, F>
+where
+    F: GainNum + Signed,
+{
+    fn tail(&self, arc: usize) -> NodeId {
+        let (u, v, _, _) = self.edges[arc / 2];
+        if arc.is_multiple_of(2) { u } else { v }
+    }
+
+    fn head(&self, arc: usize) -> NodeId {
+        let (u, v, _, _) = self.edges[arc / 2];
+        if arc.is_multiple_of(2) { v } else { u }
+    }
+
+    fn gain(&self, arc: usize) -> F {
+        let gain = self.edges[arc / 2].3;
+        if arc.is_multiple_of(2) { gain } else { F::one() / gain }
+    }
+
+    fn capacity(&self, arc: usize) -> F {
+        let (_, _, upper, gain) = self.edges[arc / 2];
+        let flow = self.flows[arc / 2];
+        if arc.is_multiple_of(2) { upper - flow } else { gain * flow }
+    }
+
+    // sends flow from start along the arcs, as much as the capacities and the supply of start allow
+    fn send(&mut self, start: NodeId, arcs: &[usize], supply: Option<F>) {
+        // the flow entering the i-th arc per unit leaving start
+        let mut factors = Vec::with_capacity(arcs.len());
+        let mut factor = F::one();
+        let mut amount = supply;
+        for &arc in arcs {
+            factors.push(factor);
+            let limit = self.capacity(arc) / factor;
+            amount = Some(amount.map_or(limit, |a| if limit < a { limit } else { a }));
+            factor = factor * self.gain(arc);
+        }
+        let amount = amount.unwrap();
+
+        for (&arc, &factor) in arcs.iter().zip(factors.iter()) {
+            let (_, _, upper, gain) = self.edges[arc / 2];
+            let flow = &mut self.flows[arc / 2];
+            if arc.is_multiple_of(2) {
+                *flow = *flow + amount * factor;
+                // keeps the flow within its bounds against rounding
+                if *flow > upper {
+                    *flow = upper;
+                }
+            } else {
+                *flow = *flow - amount * factor / gain;
+                if *flow < F::zero() {
+                    *flow = F::zero();
+                }
+            }
+        }
+        let end = self.head(*arcs.last().unwrap());
+        self.excesses[start.index()] = self.excesses[start.index()] - amount;
+        self.excesses[end.index()] = self.excesses[end.index()] + amount * factor;
+    }
+}
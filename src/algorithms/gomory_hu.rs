@@ -1 +1,2 @@
-mod gomory_hu_tree;
\ No newline at end of file
+pub mod gomory_hu_tree;
+pub mod prelude;
@@ -1,97 +1,231 @@
+use crate::prelude::maximum_flow::MaximumFlowError;
 use crate::{
     algorithms::maximum_flow::{edge::MaximumFlowEdge, solvers::dinic::Dinic, solvers::solver::MaximumFlowSolver},
     core::numeric::FlowNum,
-    direction::{Directed, Undirected},
+    direction::Undirected,
     graph::{graph::Graph, ids::NodeId},
 };
-use crate::prelude::maximum_flow::MaximumFlowError;
+use std::marker::PhantomData;
 
-pub struct GomoryHu<F> {
-    solver: Dinic<F>,
+// Gomory-Hu trees with Gusfield's algorithm, n - 1 maximum flow computations on the original graph
+// S is the maximum flow solver used for the s-t cuts
+pub struct GomoryHu<F, S = Dinic<F>> {
+    solver: S,
     n: usize,
+    phantom: PhantomData<F>,
 }
 
-impl<F> GomoryHu<F>
+impl<F, S> GomoryHu<F, S>
 where
     F: FlowNum,
+    S: MaximumFlowSolver<F>,
 {
     pub fn new<N>(graph: &Graph<Undirected, N, MaximumFlowEdge<F>>) -> Self {
-        let dg = to_bidirected(graph);
-        let solver = Dinic::<F>::new(&dg);
-        Self { solver, n: graph.num_nodes() }
+        Self { solver: S::new(graph), n: graph.num_nodes(), phantom: PhantomData }
     }
 
-    /// 戻り値: (i, parent[i], weight[i]) の列（i=1..n-1）
-    pub fn build(&mut self) -> Result<Vec<(NodeId, NodeId, F)>, MaximumFlowError> {
-        let n = self.n;
-        if n == 0 {
-            return Ok(vec![]);
-        }
+    // cut tree: removing the lightest edge on the tree path between s and t splits the nodes into a minimum s-t cut
+    pub fn build(&mut self) -> Result<GomoryHuTree<F>, MaximumFlowError> {
+        let (parent, weight) = self.gusfield(true)?;
+        Ok(GomoryHuTree { tree: Tree::new(parent, weight) })
+    }
+
+    // equivalent flow tree: only the minimum cut values are preserved, which needs fewer tree updates
+    pub fn build_equivalent_flow_tree(&mut self) -> Result<EquivalentFlowTree<F>, MaximumFlowError> {
+        let (parent, weight) = self.gusfield(false)?;
+        Ok(EquivalentFlowTree { tree: Tree::new(parent, weight) })
+    }
 
-        let root = NodeId(0);
-        let mut parent = vec![root; n];
+    fn gusfield(&mut self, cut_tree: bool) -> Result<(Vec<usize>, Vec<F>), MaximumFlowError> {
+        let n = self.n;
+        let mut parent = vec![0; n];
         let mut weight = vec![F::zero(); n];
 
-        for s_idx in 1..n {
-            let s = NodeId(s_idx);
-            let t = parent[s_idx];
+        for s in 1..n {
+            let t = parent[s];
 
-            // 1) s-t mincut (= maxflow)
-            let w = self.solver.solve(s, t)?;
+            // s-t minimum cut
+            let w = self.solver.solve(NodeId(s), NodeId(t))?;
             let source_side = self.solver.minimum_cut()?;
 
-            // 2) parent 更新
-            for v_idx in (s_idx + 1)..n {
-                if parent[v_idx] == t && source_side[v_idx] {
-                    parent[v_idx] = s;
+            // nodes on the side of s hang from s instead of t
+            // the equivalent flow tree only moves the nodes not processed yet
+            let first = if cut_tree { 0 } else { s + 1 };
+            for v in first..n {
+                if v != s && parent[v] == t && source_side[v] {
+                    parent[v] = s;
                 }
             }
+            weight[s] = w;
 
-            // 3) swap 規則
-            let t_idx = t.index();
-            if t != root && source_side[parent[t_idx].index()] {
-                let pt = parent[t_idx];
-                parent[s_idx] = pt;
-                parent[t_idx] = s;
-
-                let tmp = weight[t_idx];
-                weight[t_idx] = w;
-                weight[s_idx] = tmp;
-            } else {
-                weight[s_idx] = w;
+            // s takes the place of t if the parent of t is on the side of s
+            if cut_tree && t != 0 && source_side[parent[t]] {
+                parent[s] = parent[t];
+                parent[t] = s;
+                weight.swap(s, t);
             }
         }
+        Ok((parent, weight))
+    }
+}
 
-        let mut tree = Vec::with_capacity(n.saturating_sub(1));
-        for i in 1..n {
-            tree.push((NodeId(i), parent[i], weight[i]));
-        }
-        Ok(tree)
+pub struct GomoryHuTree<F> {
+    tree: Tree<F>,
+}
+
+impl<F: FlowNum> GomoryHuTree<F> {
+    // (u, parent of u, minimum u-parent cut value) for every node u except the root
+    pub fn edges(&self) -> Vec<(NodeId, NodeId, F)> {
+        self.tree.edges()
+    }
+
+    // O(n), None if s == t or either node is out of range
+    pub fn minimum_cut_value(&self, s: NodeId, t: NodeId) -> Option<F> {
+        self.tree.lightest_edge(s, t).map(|u| self.tree.weight[u])
+    }
+
+    // O(n), the side of s of a minimum s-t cut
+    pub fn minimum_cut(&self, s: NodeId, t: NodeId) -> Option<Vec<bool>> {
+        let u = self.tree.lightest_edge(s, t)?;
+        let subtree = self.tree.subtree(u);
+        Some(if subtree[s.index()] {
+            subtree
+        } else {
+            subtree.iter().map(|&b| !b).collect()
+        })
+    }
+
+    // O(n^2), the diagonal is F::max_value()
+    pub fn all_pairs_minimum_cut_values(&self) -> Vec<Vec<F>> {
+        self.tree.all_pairs()
     }
 }
 
-fn to_bidirected<F: FlowNum, N>(
-    g: &Graph<Undirected, N, MaximumFlowEdge<F>>,
-) -> Graph<Directed, (), MaximumFlowEdge<F>> {
-    let mut dg = Graph::<Directed, (), MaximumFlowEdge<F>>::new_directed();
-    dg.add_nodes_with(std::iter::repeat(()).take(g.num_nodes()));
-
-    for e in g.edges() {
-        let cap = e.data.upper;
-        // u -> v
-        dg.add_edge(e.u, e.v, MaximumFlowEdge { upper: cap });
-        // v -> u
-        dg.add_edge(e.v, e.u, MaximumFlowEdge { upper: cap });
+pub struct EquivalentFlowTree<F> {
+    tree: Tree<F>,
+}
+
+impl<F: FlowNum> EquivalentFlowTree<F> {
+    // (u, parent of u, minimum u-parent cut value) for every node u except the root
+    pub fn edges(&self) -> Vec<(NodeId, NodeId, F)> {
+        self.tree.edges()
+    }
+
+    // O(n), None if s == t or either node is out of range
+    pub fn minimum_cut_value(&self, s: NodeId, t: NodeId) -> Option<F> {
+        self.tree.lightest_edge(s, t).map(|u| self.tree.weight[u])
+    }
+
+    // O(n^2), the diagonal is F::max_value()
+    pub fn all_pairs_minimum_cut_values(&self) -> Vec<Vec<F>> {
+        self.tree.all_pairs()
+    }
+}
+
+// tree rooted at node 0, the edge (u, parent[u]) has weight weight[u]
+struct Tree<F> {
+    parent: Vec<usize>,
+    weight: Vec<F>,
+    depth: Vec<usize>,
+    // nodes in bfs order from the root
+    order: Vec<usize>,
+    adjacency: Vec<Vec<usize>>,
+}
+
+impl<F: FlowNum> Tree<F> {
+    fn new(parent: Vec<usize>, weight: Vec<F>) -> Self {
+        let n = parent.len();
+        let mut adjacency = vec![Vec::new(); n];
+        let mut children = vec![Vec::new(); n];
+        for u in 1..n {
+            adjacency[u].push(parent[u]);
+            adjacency[parent[u]].push(u);
+            children[parent[u]].push(u);
+        }
+
+        let mut depth = vec![0; n];
+        let mut order = Vec::with_capacity(n);
+        if n > 0 {
+            order.push(0);
+        }
+        let mut i = 0;
+        while i < order.len() {
+            let u = order[i];
+            for &v in children[u].iter() {
+                depth[v] = depth[u] + 1;
+                order.push(v);
+            }
+            i += 1;
+        }
+        Self { parent, weight, depth, order, adjacency }
+    }
+
+    fn edges(&self) -> Vec<(NodeId, NodeId, F)> {
+        (1..self.parent.len())
+            .map(|u| (NodeId(u), NodeId(self.parent[u]), self.weight[u]))
+            .collect()
+    }
+
+    // the node u whose edge (u, parent[u]) is the lightest on the path between s and t
+    fn lightest_edge(&self, s: NodeId, t: NodeId) -> Option<usize> {
+        let n = self.parent.len();
+        if s == t || s.index() >= n || t.index() >= n {
+            return None;
+        }
+
+        let (mut u, mut v) = (s.index(), t.index());
+        let mut best: Option<usize> = None;
+        while u != v {
+            if self.depth[u] < self.depth[v] {
+                std::mem::swap(&mut u, &mut v);
+            }
+            if best.is_none_or(|b| self.weight[u] < self.weight[b]) {
+                best = Some(u);
+            }
+            u = self.parent[u];
+        }
+        best
+    }
+
+    fn subtree(&self, root: usize) -> Vec<bool> {
+        let mut in_subtree = vec![false; self.parent.len()];
+        in_subtree[root] = true;
+        for &u in self.order.iter().filter(|&&u| u != 0) {
+            in_subtree[u] |= in_subtree[self.parent[u]];
+        }
+        in_subtree
+    }
+
+    fn all_pairs(&self) -> Vec<Vec<F>> {
+        let n = self.parent.len();
+        let mut values = vec![vec![F::max_value(); n]; n];
+        let mut stack = Vec::new();
+        for (s, row) in values.iter_mut().enumerate() {
+            // row[u] is the lightest edge weight on the path from s to u
+            stack.push((s, usize::MAX));
+            while let Some((u, from)) = stack.pop() {
+                for &v in self.adjacency[u].iter().filter(|&&v| v != from) {
+                    let w = if self.parent[v] == u {
+                        self.weight[v]
+                    } else {
+                        self.weight[u]
+                    };
+                    row[v] = row[u].min(w);
+                    stack.push((v, u));
+                }
+            }
+        }
+        values
     }
-    dg
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::core::random::lcg_next;
+    use crate::algorithms::maximum_flow::edge::MaximumFlowEdge;
     use crate::direction::Undirected;
     use crate::graph::{graph::Graph, ids::NodeId};
-    use crate::algorithms::maximum_flow::edge::MaximumFlowEdge;
 
     fn build_tree_adj(n: usize, tree: &[(NodeId, NodeId, i64)]) -> Vec<Vec<(usize, i64)>> {
         let mut adj = vec![Vec::<(usize, i64)>::new(); n];
@@ -176,7 +310,7 @@ mod tests {
 
     fn make_graph(n: usize, edges: &[(usize, usize, i64)]) -> Graph<Undirected, (), MaximumFlowEdge<i64>> {
         let mut g = Graph::<Undirected, (), MaximumFlowEdge<i64>>::new_undirected();
-        g.add_nodes_with(std::iter::repeat(()).take(n));
+        g.add_nodes_with(std::iter::repeat_n((), n));
         for &(u, v, cap) in edges {
             g.add_edge(NodeId(u), NodeId(v), MaximumFlowEdge { upper: cap })
                 .expect("add_edge failed");
@@ -191,7 +325,7 @@ mod tests {
         let g = make_graph(5, &[(0, 1, 3), (1, 2, 2), (2, 3, 4), (3, 4, 1), (0, 4, 5), (1, 3, 2)]);
 
         let mut gh = GomoryHu::<i64>::new(&g);
-        let tree = gh.build().expect("gomory-hu build failed");
+        let tree = gh.build().expect("gomory-hu build failed").edges();
 
         assert_eq!(tree.len(), g.num_nodes().saturating_sub(1));
 
@@ -206,12 +340,6 @@ mod tests {
         }
     }
 
-    // 依存を増やさないために簡易LCGで疑似乱数
-    fn lcg_next(x: &mut u64) -> u64 {
-        *x = x.wrapping_mul(6364136223846793005).wrapping_add(1);
-        *x
-    }
-
     #[test]
     fn gomory_hu_random_small_graphs_match_bruteforce() {
         let n = 7; // 2^7=128 なので全カット列挙が軽い
@@ -243,7 +371,7 @@ mod tests {
             let g = make_graph(n, &edges);
 
             let mut gh = GomoryHu::<i64>::new(&g);
-            let tree = gh.build().expect("gomory-hu build failed");
+            let tree = gh.build().expect("gomory-hu build failed").edges();
             assert_eq!(tree.len(), n - 1);
 
             let adj = build_tree_adj(n, &tree);
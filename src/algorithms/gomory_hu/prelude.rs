@@ -0,0 +1 @@
+pub use super::gomory_hu_tree::{EquivalentFlowTree, GomoryHu, GomoryHuTree};
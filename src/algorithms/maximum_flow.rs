@@ -1,8 +1,16 @@
 pub mod edge;
 pub mod graph;
+pub mod lexicographic_minimum_cut;
+pub mod minimum_cut_dag;
+pub mod node_capacitated;
+pub mod parametric;
+pub mod planar;
 pub mod prelude;
 pub mod residual_network;
 pub mod status;
 mod validate;
+mod verify;
 pub mod solvers;
 mod error;
+
+pub use self::verify::{Violation, verify};
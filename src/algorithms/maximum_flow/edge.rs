@@ -1,4 +1,4 @@
 #[derive(Clone, Debug)]
 pub struct MaximumFlowEdge<F> {
     pub upper: F,
-}
\ No newline at end of file
+}
@@ -12,7 +12,15 @@ pub enum MaximumFlowError {
     InvalidEdgeId {
         edge_id: EdgeId,
     },
+    InvalidNodeId {
+        node_id: NodeId,
+    },
+    InvalidCommodity {
+        commodity: usize,
+    },
     NotSolved,
+    InvalidParameter,
+    UnsignedFlowOnUndirectedGraph,
 }
 
 impl fmt::Display for MaximumFlowError {
@@ -22,7 +30,18 @@ impl fmt::Display for MaximumFlowError {
                 write!(f, "invalid source/sink (source={source:?}, sink={sink:?}, num_nodes={num_nodes})")
             }
             Self::InvalidEdgeId { edge_id } => write!(f, "invalid edge id (edge id={edge_id:?})"),
+            Self::InvalidNodeId { node_id } => write!(f, "invalid node id (node id={node_id:?})"),
+            Self::InvalidCommodity { commodity } => write!(f, "invalid commodity (commodity={commodity})"),
             Self::NotSolved => write!(f, "solver has not been run yet"),
+            Self::InvalidParameter => {
+                write!(f, "invalid parameter (capacities must be non-negative and parameters non-decreasing)")
+            }
+            Self::UnsignedFlowOnUndirectedGraph => {
+                write!(
+                    f,
+                    "undirected graphs need a signed flow type, as flow against an edge's orientation is negative"
+                )
+            }
         }
     }
 }
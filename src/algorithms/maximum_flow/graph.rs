@@ -1,17 +1,16 @@
 use crate::{
     algorithms::maximum_flow::edge::MaximumFlowEdge,
     graph::{
-        direction::Directed,
+        direction::{Directed, Direction, Undirected},
         graph::Graph,
         ids::{EdgeId, NodeId},
     },
 };
 use std::ops::{Deref, DerefMut};
 
-#[derive(Default)]
-pub struct MaximumFlowGraph<F>(Graph<Directed, (), MaximumFlowEdge<F>>);
+pub struct MaximumFlowGraph<F, D: Direction = Directed>(Graph<D, (), MaximumFlowEdge<F>>);
 
-impl<F> MaximumFlowGraph<F> {
+impl<F, D: Direction> MaximumFlowGraph<F, D> {
     pub fn add_edge(&mut self, u: NodeId, v: NodeId, upper: F) -> Option<EdgeId> {
         if u.index() >= self.0.num_nodes() || v.index() >= self.0.num_nodes() {
             return None;
@@ -20,14 +19,34 @@ impl<F> MaximumFlowGraph<F> {
     }
 }
 
-impl<F> Deref for MaximumFlowGraph<F> {
-    type Target = Graph<Directed, (), MaximumFlowEdge<F>>;
+impl<F> MaximumFlowGraph<F, Directed> {
+    pub fn new_directed() -> Self {
+        Self::default()
+    }
+}
+
+impl<F> MaximumFlowGraph<F, Undirected> {
+    // flow against the orientation of an edge is negative, so the solvers reject an unsigned F with
+    // UnsignedFlowOnUndirectedGraph
+    pub fn new_undirected() -> Self {
+        Self::default()
+    }
+}
+
+impl<F, D: Direction> Default for MaximumFlowGraph<F, D> {
+    fn default() -> Self {
+        Self(Graph::default())
+    }
+}
+
+impl<F, D: Direction> Deref for MaximumFlowGraph<F, D> {
+    type Target = Graph<D, (), MaximumFlowEdge<F>>;
     fn deref(&self) -> &Self::Target {
         &self.0
     }
 }
 
-impl<F> DerefMut for MaximumFlowGraph<F> {
+impl<F, D: Direction> DerefMut for MaximumFlowGraph<F, D> {
     fn deref_mut(&mut self) -> &mut Self::Target {
         &mut self.0
     }
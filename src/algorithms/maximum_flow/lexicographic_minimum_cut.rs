@@ -0,0 +1,143 @@
+use crate::{
+    algorithms::maximum_flow::{
+        edge::MaximumFlowEdge,
+        error::MaximumFlowError,
+        graph::MaximumFlowGraph,
+        solvers::{dinic::Dinic, solver::MaximumFlowSolver},
+    },
+    core::numeric::FlowNum,
+    graph::{
+        direction::Direction,
+        edge::Edge,
+        graph::Graph,
+        ids::{EdgeId, NodeId},
+    },
+};
+use num_traits::{CheckedAdd, One};
+use std::marker::PhantomData;
+
+// the minimum s-t cut with the smallest total weight of its edges among all minimum cuts
+// the minimum cuts are the closed sets of the minimum cut dag of a maximum flow, so a second minimum cut on the dag,
+// with an arc of capacity w(e) for every edge e and arcs closing the sets, picks the lightest one; the closing arcs
+// only need to exceed the weight of one minimum cut, so no capacity is scaled or combined with a weight
+// S is the maximum flow solver run on both graphs
+pub struct LexicographicMinimumCut<F, S = Dinic<F>> {
+    num_nodes: usize,
+    is_directed: bool,
+    // (u, v, upper, weight)
+    edges: Vec<(NodeId, NodeId, F, F)>,
+    source_side: Option<Vec<bool>>,
+    phantom: PhantomData<S>,
+}
+
+impl<F, S> LexicographicMinimumCut<F, S>
+where
+    F: FlowNum + One + CheckedAdd,
+    S: MaximumFlowSolver<F>,
+{
+    // every edge weighs one, which gives the minimum cut with the fewest edges
+    pub fn new<D: Direction, N>(graph: &Graph<D, N, MaximumFlowEdge<F>>) -> Self {
+        Self::new_with(graph, |e| e.data.upper, |_| F::one())
+    }
+
+    // weights must be non-negative
+    pub fn new_with<D: Direction, N, E, UF, WF>(graph: &Graph<D, N, E>, upper_fn: UF, weight_fn: WF) -> Self
+    where
+        UF: Fn(&Edge<E>) -> F,
+        WF: Fn(&Edge<E>) -> F,
+    {
+        let edges = graph.edges().map(|e| (e.u, e.v, upper_fn(e), weight_fn(e))).collect();
+        Self {
+            num_nodes: graph.num_nodes(),
+            is_directed: D::IS_DIRECTED,
+            edges,
+            source_side: None,
+            phantom: PhantomData,
+        }
+    }
+
+    // returns the capacity and the weight of the cut; InvalidParameter if a weight is negative or the weight of the
+    // minimum cut closest to the source does not fit in F
+    pub fn solve(&mut self, source: NodeId, sink: NodeId) -> Result<(F, F), MaximumFlowError> {
+        if self.edges.iter().any(|&(_, _, _, weight)| weight < F::zero()) {
+            return Err(MaximumFlowError::InvalidParameter);
+        }
+        let mut graph = MaximumFlowGraph::new_directed();
+        graph.add_nodes(self.num_nodes);
+        for &(u, v, upper, _) in self.edges.iter() {
+            graph.add_edge(u, v, upper);
+            if !self.is_directed {
+                graph.add_edge(v, u, upper);
+            }
+        }
+        let mut solver = S::new(&graph);
+        let value = solver.solve(source, sink)?;
+        let dag = solver.minimum_cut_dag()?;
+
+        // the components on the source side of every minimum cut become the source and those on the sink side the
+        // sink; the others are the nodes of the second graph
+        let (s, t) = (NodeId(0), NodeId(1));
+        let mut node = vec![NodeId(usize::MAX); dag.num_components()];
+        let mut num_nodes = 2;
+        for (c, x) in node.iter_mut().enumerate() {
+            *x = match dag.forced(c) {
+                Some(true) => s,
+                Some(false) => t,
+                None => {
+                    num_nodes += 1;
+                    NodeId(num_nodes - 1)
+                }
+            };
+        }
+        let node_of = |u: NodeId| node[dag.component(u)];
+
+        // the minimum cut closest to the source is one, and bounds the second minimum cut
+        let mut bound = F::zero();
+        for &(u, v, _, weight) in self.edges.iter() {
+            if (node_of(u) == s) != (node_of(v) == s) && (node_of(u) == s || !self.is_directed) {
+                bound = bound.checked_add(&weight).ok_or(MaximumFlowError::InvalidParameter)?;
+            }
+        }
+        let infinity = bound.checked_add(&F::one()).ok_or(MaximumFlowError::InvalidParameter)?;
+
+        let mut second = MaximumFlowGraph::new_directed();
+        second.add_nodes(num_nodes);
+        for &(u, v, _, weight) in self.edges.iter() {
+            let (x, y) = (node_of(u), node_of(v));
+            if x != y {
+                second.add_edge(x, y, weight);
+                if !self.is_directed {
+                    second.add_edge(y, x, weight);
+                }
+            }
+        }
+        for c in 0..dag.num_components() {
+            for &d in dag.successors(c) {
+                if node[c] != node[d] {
+                    second.add_edge(node[c], node[d], infinity);
+                }
+            }
+        }
+        let mut solver = S::new(&second);
+        let weight = solver.solve(s, t)?;
+        let side = solver.minimum_cut()?;
+        self.source_side = Some((0..self.num_nodes).map(|u| side[node_of(NodeId(u)).index()]).collect());
+        Ok((value, weight))
+    }
+
+    pub fn minimum_cut(&self) -> Result<Vec<bool>, MaximumFlowError> {
+        self.source_side.clone().ok_or(MaximumFlowError::NotSolved)
+    }
+
+    // the edges from the source side to the sink side, and for undirected edges also the other way
+    pub fn cut_edges(&self) -> Result<Vec<EdgeId>, MaximumFlowError> {
+        let side = self.source_side.as_ref().ok_or(MaximumFlowError::NotSolved)?;
+        Ok(self
+            .edges
+            .iter()
+            .enumerate()
+            .filter(|&(_, &(u, v, _, _))| side[u.index()] != side[v.index()] && (side[u.index()] || !self.is_directed))
+            .map(|(e, _)| EdgeId(e))
+            .collect())
+    }
+}
@@ -0,0 +1,197 @@
+use crate::{
+    algorithms::maximum_flow::{error::MaximumFlowError, residual_network::ResidualNetwork},
+    core::numeric::FlowNum,
+    graph::ids::{EdgeId, NodeId},
+};
+
+// Picard–Queyranne representation of all minimum s-t cuts.
+// Every minimum cut corresponds to a closed set (closed under residual arcs) of the strongly connected components
+// of the residual network of a maximum flow that contains the source and does not contain the sink.
+pub struct MinimumCutDag {
+    num_components: usize,
+    component: Box<[usize]>,
+    successors: Box<[Vec<usize>]>,
+
+    // Some(true): source side of every minimum cut, Some(false): sink side of every minimum cut
+    forced: Box<[Option<bool>]>,
+    is_directed: bool,
+    edges: Box<[(NodeId, NodeId)]>,
+}
+
+impl MinimumCutDag {
+    // sources/sinks are the nodes that are reachable from the source / can reach the sink by implicit arcs
+    pub(crate) fn from_residual_network<F: FlowNum>(
+        rn: &ResidualNetwork<F>,
+        sources: &[NodeId],
+        sinks: &[NodeId],
+    ) -> Self {
+        let (num_components, component) = rn.strongly_connected_components();
+
+        let mut successors = vec![Vec::new(); num_components].into_boxed_slice();
+        for u in 0..rn.num_nodes {
+            for arc_id in rn.neighbors(NodeId(u)) {
+                let v = rn.to[arc_id.index()];
+                if rn.residual_capacities[arc_id.index()] > F::zero() && component[u] != component[v.index()] {
+                    successors[component[u]].push(component[v.index()]);
+                }
+            }
+        }
+        for succ in successors.iter_mut() {
+            succ.sort_unstable();
+            succ.dedup();
+        }
+
+        // component ids are topologically sorted, so successors always have larger ids
+        let mut forced = vec![None; num_components].into_boxed_slice();
+        for &source in sources {
+            forced[component[source.index()]] = Some(true);
+        }
+        for c in 0..num_components {
+            if forced[c] == Some(true) {
+                for &d in successors[c].iter() {
+                    forced[d] = Some(true);
+                }
+            }
+        }
+        for &sink in sinks {
+            forced[component[sink.index()]] = Some(false);
+        }
+        for c in (0..num_components).rev() {
+            if successors[c].iter().any(|&d| forced[d] == Some(false)) {
+                forced[c] = Some(false);
+            }
+        }
+
+        let edges = (0..rn.num_edges)
+            .map(|edge_id| {
+                let arc_id = rn.edge_id_to_arc_id[edge_id];
+                (rn.to[rn.rev[arc_id.index()].index()], rn.to[arc_id.index()])
+            })
+            .collect();
+
+        Self {
+            num_components,
+            component: component.into_boxed_slice(),
+            successors,
+            forced,
+            is_directed: rn.is_directed,
+            edges,
+        }
+    }
+
+    pub fn num_components(&self) -> usize {
+        self.num_components
+    }
+
+    pub fn component(&self, u: NodeId) -> usize {
+        self.component[u.index()]
+    }
+
+    // components that must be on the source side whenever the component c is
+    pub fn successors(&self, c: usize) -> &[usize] {
+        &self.successors[c]
+    }
+
+    // Some(true) if the component c is on the source side of every minimum cut, Some(false) if on the sink side
+    pub fn forced(&self, c: usize) -> Option<bool> {
+        self.forced[c]
+    }
+
+    // iterates over the source sides of all minimum cuts, starting from the one closest to the source
+    pub fn minimum_cuts(&self) -> MinimumCuts<'_> {
+        MinimumCuts { dag: self, included: Vec::new(), done: false }
+    }
+
+    // an undirected edge is in a cut if its endpoints are separated in either orientation
+    pub fn is_edge_in_every_minimum_cut(&self, edge_id: EdgeId) -> Result<bool, MaximumFlowError> {
+        let (u, v) = self.endpoints(edge_id)?;
+        Ok(self.separated_in_every_minimum_cut(u, v)
+            || (!self.is_directed && self.separated_in_every_minimum_cut(v, u)))
+    }
+
+    pub fn is_edge_in_some_minimum_cut(&self, edge_id: EdgeId) -> Result<bool, MaximumFlowError> {
+        let (u, v) = self.endpoints(edge_id)?;
+        Ok(self.separated_in_some_minimum_cut(u, v) || (!self.is_directed && self.separated_in_some_minimum_cut(v, u)))
+    }
+
+    // u is on the source side and v is on the sink side
+    fn separated_in_every_minimum_cut(&self, u: NodeId, v: NodeId) -> bool {
+        self.forced[self.component(u)] == Some(true) && self.forced[self.component(v)] == Some(false)
+    }
+
+    fn separated_in_some_minimum_cut(&self, u: NodeId, v: NodeId) -> bool {
+        let (cu, cv) = (self.component(u), self.component(v));
+        if cu == cv || self.forced[cu] == Some(false) || self.forced[cv] == Some(true) {
+            return false;
+        }
+
+        // u can be on the source side while v is on the sink side iff v is not reachable from u
+        let mut visited = vec![false; self.num_components];
+        let mut stack = vec![cu];
+        visited[cu] = true;
+        while let Some(c) = stack.pop() {
+            if c == cv {
+                return false;
+            }
+            for &d in self.successors[c].iter() {
+                if d <= cv && !visited[d] {
+                    visited[d] = true;
+                    stack.push(d);
+                }
+            }
+        }
+        true
+    }
+
+    fn endpoints(&self, edge_id: EdgeId) -> Result<(NodeId, NodeId), MaximumFlowError> {
+        self.edges
+            .get(edge_id.index())
+            .copied()
+            .ok_or(MaximumFlowError::InvalidEdgeId { edge_id })
+    }
+
+    fn source_side(&self, included: &[bool]) -> Vec<bool> {
+        self.component.iter().map(|&c| included[c]).collect()
+    }
+}
+
+pub struct MinimumCuts<'a> {
+    dag: &'a MinimumCutDag,
+    included: Vec<bool>,
+    done: bool,
+}
+
+impl Iterator for MinimumCuts<'_> {
+    type Item = Vec<bool>;
+
+    // components are decided in descending order of id (successors first), like an odometer
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        let dag = self.dag;
+        if self.included.is_empty() {
+            self.included = dag.forced.iter().map(|&f| f == Some(true)).collect();
+            self.done = dag.num_components == 0;
+            return Some(dag.source_side(&self.included));
+        }
+
+        let next = (0..dag.num_components).find(|&c| {
+            dag.forced[c].is_none() && !self.included[c] && dag.successors[c].iter().all(|&d| self.included[d])
+        });
+        match next {
+            Some(c) => {
+                self.included[c] = true;
+                for d in 0..c {
+                    self.included[d] = dag.forced[d] == Some(true);
+                }
+                Some(dag.source_side(&self.included))
+            }
+            None => {
+                self.done = true;
+                None
+            }
+        }
+    }
+}
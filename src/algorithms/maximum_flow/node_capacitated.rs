@@ -0,0 +1,157 @@
+use crate::{
+    algorithms::maximum_flow::{
+        edge::MaximumFlowEdge,
+        error::MaximumFlowError,
+        graph::MaximumFlowGraph,
+        solvers::{dinic::Dinic, solver::MaximumFlowSolver},
+    },
+    core::numeric::FlowNum,
+    graph::{
+        direction::Direction,
+        edge::Edge,
+        graph::Graph,
+        ids::{EdgeId, NodeId},
+    },
+};
+
+// maximum flow with node capacities
+// every capacitated node u is split into u, receiving the arcs entering u, and a new node sending the arcs leaving u,
+// joined by an arc of capacity c(u); undirected edges become a pair of opposite arcs
+// S is the maximum flow solver run on the split graph
+pub struct NodeCapacitated<F, S = Dinic<F>> {
+    num_nodes: usize,
+    is_directed: bool,
+    edges: Vec<(NodeId, NodeId, F)>,
+    capacities: Vec<Option<F>>,
+
+    // built by solve
+    solver: Option<S>,
+    // the node sending the arcs leaving u, u itself if it is not split
+    out_node: Vec<NodeId>,
+    // the arc of each edge in the split graph, and the opposite arc for undirected edges
+    arcs: Vec<(EdgeId, Option<EdgeId>)>,
+}
+
+impl<F, S> NodeCapacitated<F, S>
+where
+    F: FlowNum,
+    S: MaximumFlowSolver<F>,
+{
+    pub fn new<D: Direction, N>(graph: &Graph<D, N, MaximumFlowEdge<F>>) -> Self {
+        Self::new_with(graph, |e| e.data.upper)
+    }
+
+    pub fn new_with<D: Direction, N, E, UF>(graph: &Graph<D, N, E>, upper_fn: UF) -> Self
+    where
+        UF: Fn(&Edge<E>) -> F,
+    {
+        Self {
+            num_nodes: graph.num_nodes(),
+            is_directed: D::IS_DIRECTED,
+            edges: graph.edges().map(|e| (e.u, e.v, upper_fn(e))).collect(),
+            capacities: vec![None; graph.num_nodes()],
+            solver: None,
+            out_node: Vec::new(),
+            arcs: Vec::new(),
+        }
+    }
+
+    // bounds the total flow passing through u, including the flow leaving the source or entering the sink;
+    // the capacity must be non-negative
+    pub fn set_node_capacity(&mut self, u: NodeId, capacity: F) -> Result<(), MaximumFlowError> {
+        if u.index() >= self.num_nodes {
+            return Err(MaximumFlowError::InvalidNodeId { node_id: u });
+        }
+        if capacity < F::zero() {
+            return Err(MaximumFlowError::InvalidParameter);
+        }
+        self.capacities[u.index()] = Some(capacity);
+        self.solver = None;
+        Ok(())
+    }
+
+    pub fn node_capacity(&self, u: NodeId) -> Option<F> {
+        self.capacities.get(u.index()).copied().flatten()
+    }
+
+    pub fn solve(&mut self, source: NodeId, sink: NodeId) -> Result<F, MaximumFlowError> {
+        self.validate(source, sink)?;
+        let (solver, out_sink) = self.build(sink);
+        solver.solve(source, out_sink)
+    }
+
+    pub fn solve_with_limit(&mut self, source: NodeId, sink: NodeId, limit: F) -> Result<F, MaximumFlowError> {
+        self.validate(source, sink)?;
+        let (solver, out_sink) = self.build(sink);
+        solver.solve_with_limit(source, out_sink, limit)
+    }
+
+    // flow on an undirected edge is negative if it runs from v to u, as with the other solvers
+    pub fn flow(&self, edge_id: EdgeId) -> Result<F, MaximumFlowError> {
+        if edge_id.index() >= self.edges.len() {
+            return Err(MaximumFlowError::InvalidEdgeId { edge_id });
+        }
+        let solver = self.solver.as_ref().ok_or(MaximumFlowError::NotSolved)?;
+        let (arc, opposite) = self.arcs[edge_id.index()];
+        let flow = solver.flow(arc)?;
+        match opposite {
+            Some(opposite) => Ok(flow - solver.flow(opposite)?),
+            None => Ok(flow),
+        }
+    }
+
+    pub fn flows(&self) -> Result<Vec<F>, MaximumFlowError> {
+        let flows = self.solver.as_ref().ok_or(MaximumFlowError::NotSolved)?.flows()?;
+        Ok(self
+            .arcs
+            .iter()
+            .map(|&(arc, opposite)| match opposite {
+                Some(opposite) => flows[arc.index()] - flows[opposite.index()],
+                None => flows[arc.index()],
+            })
+            .collect())
+    }
+
+    // the source side of a minimum cut and the nodes whose capacity is in the cut;
+    // the cut consists of these nodes and of the edges from the other source side nodes to the sink side
+    pub fn minimum_cut(&mut self) -> Result<(Vec<bool>, Vec<NodeId>), MaximumFlowError> {
+        let side = self.solver.as_mut().ok_or(MaximumFlowError::NotSolved)?.minimum_cut()?;
+        let cut_nodes = (0..self.num_nodes)
+            .filter(|&u| side[u] && !side[self.out_node[u].index()])
+            .map(NodeId)
+            .collect();
+        Ok((side[..self.num_nodes].to_vec(), cut_nodes))
+    }
+
+    fn validate(&self, source: NodeId, sink: NodeId) -> Result<(), MaximumFlowError> {
+        if source.index() >= self.num_nodes || sink.index() >= self.num_nodes || source == sink {
+            return Err(MaximumFlowError::InvalidTerminal { source, sink, num_nodes: self.num_nodes });
+        }
+        Ok(())
+    }
+
+    // the solver on the split graph, and the node of the split graph taking the flow into the sink
+    fn build(&mut self, sink: NodeId) -> (&mut S, NodeId) {
+        if self.solver.is_none() {
+            let mut split = MaximumFlowGraph::new_directed();
+            self.out_node = split.add_nodes(self.num_nodes);
+            for u in (0..self.num_nodes).filter(|&u| self.capacities[u].is_some()) {
+                self.out_node[u] = split.add_node();
+            }
+
+            self.arcs = Vec::with_capacity(self.edges.len());
+            for &(u, v, upper) in self.edges.iter() {
+                let arc = split.add_edge(self.out_node[u.index()], v, upper).unwrap();
+                let opposite = (!self.is_directed).then(|| split.add_edge(self.out_node[v.index()], u, upper).unwrap());
+                self.arcs.push((arc, opposite));
+            }
+            for (u, &capacity) in self.capacities.iter().enumerate() {
+                if let Some(capacity) = capacity {
+                    split.add_edge(NodeId(u), self.out_node[u], capacity);
+                }
+            }
+            self.solver = Some(S::new(&split));
+        }
+        (self.solver.as_mut().unwrap(), self.out_node[sink.index()])
+    }
+}
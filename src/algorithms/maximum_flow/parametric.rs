@@ -0,0 +1,320 @@
+use crate::{
+    algorithms::maximum_flow::{
+        edge::MaximumFlowEdge,
+        error::MaximumFlowError,
+        graph::MaximumFlowGraph,
+        solvers::{push_relabel_highest_label::PushRelabelHighestLabel, solver::MaximumFlowSolver},
+    },
+    core::numeric::FlowNum,
+    graph::{
+        direction::Direction,
+        edge::Edge,
+        graph::Graph,
+        ids::{EdgeId, NodeId},
+    },
+};
+use num_traits::Signed;
+
+// parametric maximum flow
+// arcs leaving the source have capacity constant + λ * slope and arcs entering the sink have capacity constant - λ * slope,
+// so that the minimal minimum cuts are nested and grow with λ
+// everything continues a single push-relabel run at the smallest parameter (Gallo–Grigoriadis–Tarjan): raising λ
+// saturates the arcs leaving the source and returns flow over the arcs entering the sink, which keeps the preflow and
+// the distance labels valid, so phase 1 resumes from them instead of starting over
+// solve walks a sorted list of parameters, while breakpoints intersects lines of cut capacities (Eisner–Severance);
+// each probe at λ = p / q resumes a copy of the run at lambda_min with its preflow scaled by q, and a range with
+// k breakpoints takes at most 2k + 1 probes
+pub struct ParametricMaximumFlow<F> {
+    num_nodes: usize,
+    edges: Vec<(NodeId, NodeId, F)>,
+    source_arcs: Vec<(NodeId, F, F)>,
+    sink_arcs: Vec<(NodeId, F, F)>,
+}
+
+// source_sides[i] is the minimal minimum cut between breakpoints[i - 1] and breakpoints[i],
+// and its capacity is capacities[i].0 + λ * capacities[i].1
+pub struct ParametricMinimumCuts<F> {
+    pub breakpoints: Vec<(F, F)>, // (numerator, denominator)
+    pub source_sides: Vec<Vec<bool>>,
+    pub capacities: Vec<(F, F)>,
+}
+
+impl<F> ParametricMaximumFlow<F>
+where
+    F: FlowNum + Signed,
+{
+    pub fn new<D: Direction, N>(graph: &Graph<D, N, MaximumFlowEdge<F>>) -> Self {
+        Self::new_with(graph, |e| e.data.upper)
+    }
+
+    pub fn new_with<D: Direction, N, E, UF>(graph: &Graph<D, N, E>, upper_fn: UF) -> Self
+    where
+        UF: Fn(&Edge<E>) -> F,
+    {
+        let mut edges = Vec::with_capacity(graph.num_edges());
+        for e in graph.edges() {
+            edges.push((e.u, e.v, upper_fn(e)));
+            if !D::IS_DIRECTED {
+                edges.push((e.v, e.u, upper_fn(e)));
+            }
+        }
+        Self { num_nodes: graph.num_nodes(), edges, source_arcs: Vec::new(), sink_arcs: Vec::new() }
+    }
+
+    // arc from the source to u with capacity constant + λ * slope
+    pub fn add_source_arc(&mut self, u: NodeId, constant: F, slope: F) -> Result<(), MaximumFlowError> {
+        if u.index() >= self.num_nodes {
+            return Err(MaximumFlowError::InvalidNodeId { node_id: u });
+        }
+        if slope < F::zero() {
+            return Err(MaximumFlowError::InvalidParameter);
+        }
+        self.source_arcs.push((u, constant, slope));
+        Ok(())
+    }
+
+    // arc from u to the sink with capacity constant - λ * slope
+    pub fn add_sink_arc(&mut self, u: NodeId, constant: F, slope: F) -> Result<(), MaximumFlowError> {
+        if u.index() >= self.num_nodes {
+            return Err(MaximumFlowError::InvalidNodeId { node_id: u });
+        }
+        if slope < F::zero() {
+            return Err(MaximumFlowError::InvalidParameter);
+        }
+        self.sink_arcs.push((u, constant, slope));
+        Ok(())
+    }
+
+    // maximum flow values and minimal minimum cuts for non-decreasing parameters in a single push-relabel run
+    pub fn solve(&self, source: NodeId, sink: NodeId, lambdas: &[F]) -> Result<Vec<(F, Vec<bool>)>, MaximumFlowError> {
+        if lambdas.is_empty() {
+            return Ok(Vec::new());
+        }
+        if lambdas.windows(2).any(|w| w[0] > w[1]) {
+            return Err(MaximumFlowError::InvalidParameter);
+        }
+        self.validate(source, sink, lambdas[0], lambdas[lambdas.len() - 1])?;
+
+        let (mut solver, arc_ids) = self.network(source, sink, lambdas[0])?;
+        let mut results = Vec::with_capacity(lambdas.len());
+        results.push((solver.solve(source, sink)?, solver.minimum_cut()?));
+        for &lambda in lambdas[1..].iter() {
+            let value = self.raise(&mut solver, source, sink, &arc_ids, (lambda, F::one()));
+            results.push((value, solver.minimum_cut()?));
+        }
+        Ok(results)
+    }
+
+    // every breakpoint of the minimum cut capacity in (lambda_min, lambda_max)
+    pub fn breakpoints(
+        &self,
+        source: NodeId,
+        sink: NodeId,
+        lambda_min: F,
+        lambda_max: F,
+    ) -> Result<ParametricMinimumCuts<F>, MaximumFlowError> {
+        if lambda_min > lambda_max {
+            return Err(MaximumFlowError::InvalidParameter);
+        }
+        self.validate(source, sink, lambda_min, lambda_max)?;
+
+        let (mut base, arc_ids) = self.network(source, sink, lambda_min)?;
+        base.solve(source, sink)?;
+        let mut first = base.minimum_cut()?;
+        let (_, last) = self.resume_at(&base, source, sink, &arc_ids, (lambda_max, F::one()))?;
+
+        let mut found = Vec::new();
+        if self.line(&first).1 > self.line(&last).1 {
+            // (lower, upper): minimal minimum cuts at the ends of an interval containing at least one breakpoint
+            let mut stack = vec![(first.clone(), last)];
+            while let Some((lower, upper)) = stack.pop() {
+                let (lower_line, upper_line) = (self.line(&lower), self.line(&upper));
+                let lambda = intersection(lower_line, upper_line);
+                let (value, middle) = self.resume_at(&base, source, sink, &arc_ids, lambda)?;
+                if value == lambda.1 * lower_line.0 + lambda.0 * lower_line.1 {
+                    found.push((lambda, upper));
+                } else {
+                    stack.push((middle.clone(), upper));
+                    stack.push((lower, middle));
+                }
+            }
+        }
+
+        // a breakpoint at an end of the range only changes the minimal cut of the adjacent piece
+        found.sort_by(|a, b| (a.0.0 * b.0.1).cmp(&(b.0.0 * a.0.1)));
+        if found
+            .first()
+            .is_some_and(|(lambda, _)| lambda.0 <= lambda_min * lambda.1)
+        {
+            first = found.remove(0).1;
+        }
+        found.retain(|(lambda, _)| lambda.0 < lambda_max * lambda.1);
+        Ok(self.pieces(found, first))
+    }
+
+    fn pieces(&self, found: Vec<((F, F), Vec<bool>)>, first: Vec<bool>) -> ParametricMinimumCuts<F> {
+        let mut result = ParametricMinimumCuts {
+            breakpoints: Vec::with_capacity(found.len()),
+            capacities: vec![self.line(&first)],
+            source_sides: vec![first],
+        };
+        for (lambda, source_side) in found {
+            result.breakpoints.push(lambda);
+            result.capacities.push(self.line(&source_side));
+            result.source_sides.push(source_side);
+        }
+        result
+    }
+
+    fn validate(&self, source: NodeId, sink: NodeId, lambda_min: F, lambda_max: F) -> Result<(), MaximumFlowError> {
+        if source.index() >= self.num_nodes || sink.index() >= self.num_nodes || source == sink {
+            return Err(MaximumFlowError::InvalidTerminal { source, sink, num_nodes: self.num_nodes });
+        }
+        let valid = self.edges.iter().all(|&(_, _, upper)| upper >= F::zero())
+            && self
+                .source_arcs
+                .iter()
+                .all(|&(_, constant, slope)| constant + lambda_min * slope >= F::zero())
+            && self
+                .sink_arcs
+                .iter()
+                .all(|&(_, constant, slope)| constant - lambda_max * slope >= F::zero());
+        if valid {
+            Ok(())
+        } else {
+            Err(MaximumFlowError::InvalidParameter)
+        }
+    }
+
+    // capacity of the cut as constant + λ * slope
+    fn line(&self, source_side: &[bool]) -> (F, F) {
+        let mut line = (F::zero(), F::zero());
+        for &(u, v, upper) in self.edges.iter() {
+            if source_side[u.index()] && !source_side[v.index()] {
+                line.0 += upper;
+            }
+        }
+        for &(u, constant, slope) in self.source_arcs.iter() {
+            if !source_side[u.index()] {
+                line = (line.0 + constant, line.1 + slope);
+            }
+        }
+        for &(u, constant, slope) in self.sink_arcs.iter() {
+            if source_side[u.index()] {
+                line = (line.0 + constant, line.1 - slope);
+            }
+        }
+        line
+    }
+
+    // value of the minimum cut at λ = p / q scaled by q and its minimal source side, from a copy of the run at
+    // lambda_min; scaling its capacities and its preflow by q keeps the distance labels valid
+    fn resume_at(
+        &self,
+        base: &PushRelabelHighestLabel<F>,
+        source: NodeId,
+        sink: NodeId,
+        arc_ids: &TerminalArcs,
+        lambda: (F, F),
+    ) -> Result<(F, Vec<bool>), MaximumFlowError> {
+        let mut solver = base.clone();
+        let rn = solver.residual_network_mut();
+        for x in rn
+            .upper
+            .iter_mut()
+            .chain(rn.residual_capacities.iter_mut())
+            .chain(rn.excesses.iter_mut())
+        {
+            *x = *x * lambda.1;
+        }
+        let value = self.raise(&mut solver, source, sink, arc_ids, lambda);
+        Ok((value, solver.minimum_cut()?))
+    }
+
+    // sets the arcs leaving the source and entering the sink to their capacities at λ = p / q scaled by q and resumes
+    // phase 1; the capacities of the other arcs must already be scaled by q and λ must not be below the current one
+    fn raise(
+        &self,
+        solver: &mut PushRelabelHighestLabel<F>,
+        source: NodeId,
+        sink: NodeId,
+        (source_arc_ids, sink_arc_ids): &TerminalArcs,
+        (numerator, denominator): (F, F),
+    ) -> F {
+        let rn = solver.residual_network_mut();
+        for (&(u, constant, slope), edge_id) in self.source_arcs.iter().zip(source_arc_ids.iter()) {
+            let arc_id = rn.edge_id_to_arc_id[edge_id.index()];
+            let upper = denominator * constant + numerator * slope;
+            rn.residual_capacities[arc_id.index()] += upper - rn.upper[arc_id.index()];
+            rn.upper[arc_id.index()] = upper;
+
+            let delta = rn.residual_capacity(arc_id);
+            rn.push_flow_without_excess(source, arc_id, delta);
+            rn.excesses[u.index()] += delta;
+        }
+        for (&(_, constant, slope), edge_id) in self.sink_arcs.iter().zip(sink_arc_ids.iter()) {
+            let arc_id = rn.edge_id_to_arc_id[edge_id.index()];
+            let upper = denominator * constant - numerator * slope;
+            let flow = rn.upper[arc_id.index()] - rn.residual_capacity(arc_id);
+            if flow > upper {
+                rn.push_flow(sink, rn.rev[arc_id.index()], flow - upper);
+            }
+            rn.residual_capacities[arc_id.index()] -= rn.upper[arc_id.index()] - upper;
+            rn.upper[arc_id.index()] = upper;
+        }
+        solver.resume(source, sink)
+    }
+
+    // a value-only push-relabel solver on the network at λ, not run yet
+    fn network(
+        &self,
+        source: NodeId,
+        sink: NodeId,
+        lambda: F,
+    ) -> Result<(PushRelabelHighestLabel<F>, TerminalArcs), MaximumFlowError> {
+        let invalid_terminal = MaximumFlowError::InvalidTerminal { source, sink, num_nodes: self.num_nodes };
+        let mut graph = MaximumFlowGraph::new_directed();
+        graph.add_nodes(self.num_nodes);
+
+        for &(u, v, upper) in self.edges.iter() {
+            graph.add_edge(u, v, upper);
+        }
+        let source_arc_ids = self
+            .source_arcs
+            .iter()
+            .map(|&(u, constant, slope)| {
+                graph
+                    .add_edge(source, u, constant + lambda * slope)
+                    .ok_or(invalid_terminal.clone())
+            })
+            .collect::<Result<_, _>>()?;
+        let sink_arc_ids = self
+            .sink_arcs
+            .iter()
+            .map(|&(u, constant, slope)| {
+                graph
+                    .add_edge(u, sink, constant - lambda * slope)
+                    .ok_or(invalid_terminal.clone())
+            })
+            .collect::<Result<_, _>>()?;
+        let solver = PushRelabelHighestLabel::new_with(&graph, |e| e.data.upper).set_value_only(true);
+        Ok((solver, (source_arc_ids, sink_arc_ids)))
+    }
+}
+
+// the ids of the arcs leaving the source and of those entering the sink
+type TerminalArcs = (Vec<EdgeId>, Vec<EdgeId>);
+
+// λ where the two cut capacities are equal, as a reduced fraction with a positive denominator
+fn intersection<F: FlowNum + Signed>(lower: (F, F), upper: (F, F)) -> (F, F) {
+    let (numerator, denominator) = (upper.0 - lower.0, lower.1 - upper.1);
+    let (mut a, mut b) = (numerator.abs(), denominator);
+    while b != F::zero() {
+        (a, b) = (b, a % b);
+    }
+    if a == F::zero() {
+        (numerator, denominator)
+    } else {
+        (numerator / a, denominator / a)
+    }
+}
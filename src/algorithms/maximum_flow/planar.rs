@@ -0,0 +1,375 @@
+use crate::prelude::shortest_path::{Dijkstra, ShortestPathSolver};
+use crate::{
+    algorithms::maximum_flow::{edge::MaximumFlowEdge, error::MaximumFlowError},
+    core::numeric::FlowNum,
+    data_structures::{LinkCutTree, UndirectedLinkCutTree, UnionFind},
+    graph::{
+        direction::{Directed, Direction},
+        edge::Edge,
+        graph::Graph,
+        ids::{EdgeId, NodeId},
+    },
+};
+
+// maximum flow in a planar graph given with an embedding, as the counterclockwise order of the edges around each node
+// a flow is a circulation plus the flow on an imaginary edge from the sink back to the source, and a circulation in a
+// plane graph is the difference of potentials of the faces on either side of each edge; if the source and the sink
+// lie on a common face, the imaginary edge splits it into two and the maximum flow is the shortest path between them
+// in the dual graph, a single run of Dijkstra (Hassin)
+// otherwise the algorithm of Borradaile and Klein, as a parametric shortest path in the dual (Erickson), starts from
+// the circulation given by a shortest path tree T of the dual, whose complement is a spanning tree C of the graph: the
+// flow is augmented along the path from the source to the sink in C, and the dart this saturates pivots into T, where
+// it displaces a dart whose edge takes its place in C, until the saturated dart would close a cycle in T, which is then
+// a saturated cut; C is kept in a link-cut tree, O(n log n) in total
+pub struct PlanarMaximumFlow<F> {
+    num_nodes: usize,
+    // (u, v, capacity from u to v, capacity from v to u)
+    edges: Vec<(NodeId, NodeId, F, F)>,
+    // dart 2e runs along edge e and dart 2e + 1 against it; next is the dart after each dart on the boundary of the
+    // face to its left, and face that face; self-loops have no darts, usize::MAX
+    next: Vec<usize>,
+    face: Vec<usize>,
+    num_faces: usize,
+    // the connected component of every node
+    component: Vec<usize>,
+    // the source and the flow on every edge
+    solution: Option<(NodeId, Vec<F>)>,
+}
+
+impl<F> PlanarMaximumFlow<F>
+where
+    F: FlowNum,
+{
+    pub fn new<D: Direction, N>(
+        graph: &Graph<D, N, MaximumFlowEdge<F>>,
+        rotation: &[Vec<EdgeId>],
+    ) -> Result<Self, MaximumFlowError> {
+        Self::new_with(graph, rotation, |e| e.data.upper)
+    }
+
+    // rotation[u] lists the edges incident to u in counterclockwise order, self-loops being optional;
+    // InvalidParameter if it is not a planar embedding of the graph or a capacity is negative,
+    // UnsignedFlowOnUndirectedGraph if the graph is undirected and F unsigned
+    pub fn new_with<D: Direction, N, E, UF>(
+        graph: &Graph<D, N, E>,
+        rotation: &[Vec<EdgeId>],
+        upper_fn: UF,
+    ) -> Result<Self, MaximumFlowError>
+    where
+        UF: Fn(&Edge<E>) -> F,
+    {
+        let (n, m) = (graph.num_nodes(), graph.num_edges());
+        if !D::IS_DIRECTED && F::min_value() >= F::zero() {
+            return Err(MaximumFlowError::UnsignedFlowOnUndirectedGraph);
+        }
+        let mut edges = Vec::with_capacity(m);
+        for e in graph.edges() {
+            let upper = upper_fn(e);
+            if upper < F::zero() {
+                return Err(MaximumFlowError::InvalidParameter);
+            }
+            edges.push((e.u, e.v, upper, if D::IS_DIRECTED { F::zero() } else { upper }));
+        }
+        if rotation.len() != n {
+            return Err(MaximumFlowError::InvalidParameter);
+        }
+
+        // the darts leaving each node in counterclockwise order, and the position of each dart in its node's order
+        let mut darts: Vec<Vec<usize>> = vec![Vec::new(); n];
+        let mut position = vec![usize::MAX; 2 * m];
+        for (u, order) in rotation.iter().enumerate() {
+            for &edge_id in order {
+                let &(x, y, _, _) = edges
+                    .get(edge_id.index())
+                    .ok_or(MaximumFlowError::InvalidEdgeId { edge_id })?;
+                if x == y {
+                    continue;
+                }
+                let dart = if x.index() == u {
+                    2 * edge_id.index()
+                } else if y.index() == u {
+                    2 * edge_id.index() + 1
+                } else {
+                    return Err(MaximumFlowError::InvalidParameter);
+                };
+                if position[dart] != usize::MAX {
+                    return Err(MaximumFlowError::InvalidParameter);
+                }
+                position[dart] = darts[u].len();
+                darts[u].push(dart);
+            }
+        }
+        let loops = edges.iter().filter(|&&(u, v, _, _)| u == v).count();
+        if darts.iter().map(|d| d.len()).sum::<usize>() != 2 * (m - loops) {
+            return Err(MaximumFlowError::InvalidParameter);
+        }
+
+        // the face to the left of a dart into y continues with the dart after it clockwise around y
+        let head = |dart: usize| {
+            let (u, v, _, _) = edges[dart / 2];
+            if dart.is_multiple_of(2) { v } else { u }
+        };
+        let mut next = vec![usize::MAX; 2 * m];
+        for dart in (0..2 * m).filter(|&dart| position[dart] != usize::MAX) {
+            let around = &darts[head(dart).index()];
+            let reverse = position[dart ^ 1];
+            next[dart] = around[(reverse + around.len() - 1) % around.len()];
+        }
+        let mut face = vec![usize::MAX; 2 * m];
+        let mut num_faces = 0;
+        for start in 0..2 * m {
+            if next[start] == usize::MAX || face[start] != usize::MAX {
+                continue;
+            }
+            let mut dart = start;
+            while face[dart] == usize::MAX {
+                face[dart] = num_faces;
+                dart = next[dart];
+            }
+            num_faces += 1;
+        }
+
+        // genus zero: n - m + f = 2 on every connected component with an edge
+        let mut components = UnionFind::new(n);
+        for &(u, v, _, _) in edges.iter() {
+            components.union(u.index(), v.index());
+        }
+        let mut characteristic = vec![0_isize; n];
+        for u in (0..n).filter(|&u| !darts[u].is_empty()) {
+            characteristic[components.find(u)] += 1;
+        }
+        for &(u, _, _, _) in edges.iter().filter(|&&(u, v, _, _)| u != v) {
+            characteristic[components.find(u.index())] -= 1;
+        }
+        let mut counted = vec![false; num_faces];
+        for dart in (0..2 * m).filter(|&dart| face[dart] != usize::MAX) {
+            if !counted[face[dart]] {
+                counted[face[dart]] = true;
+                let (u, _, _, _) = edges[dart / 2];
+                characteristic[components.find(u.index())] += 1;
+            }
+        }
+        if (0..n).any(|u| !darts[u].is_empty() && components.find(u) == u && characteristic[u] != 2) {
+            return Err(MaximumFlowError::InvalidParameter);
+        }
+
+        let component = (0..n).map(|u| components.find(u)).collect();
+        Ok(Self { num_nodes: n, edges, next, face, num_faces, component, solution: None })
+    }
+
+    pub fn solve(&mut self, source: NodeId, sink: NodeId) -> Result<F, MaximumFlowError> {
+        if source.index() >= self.num_nodes || sink.index() >= self.num_nodes || source == sink {
+            return Err(MaximumFlowError::InvalidTerminal { source, sink, num_nodes: self.num_nodes });
+        }
+        // no flow passes between connected components
+        if self.component[source.index()] != self.component[sink.index()] {
+            self.solution = Some((source, vec![F::zero(); self.edges.len()]));
+            return Ok(F::zero());
+        }
+        let (value, flows) = match self.common_face(source, sink) {
+            Some((boundary, into_source, into_sink)) => self.hassin(&boundary, into_source, into_sink),
+            None => self.borradaile_klein(source, sink),
+        };
+        self.solution = Some((source, flows));
+        Ok(value)
+    }
+
+    // flow on an undirected edge is negative if it runs from v to u, as with the other solvers
+    pub fn flow(&self, edge_id: EdgeId) -> Result<F, MaximumFlowError> {
+        let (_, flows) = self.solution.as_ref().ok_or(MaximumFlowError::NotSolved)?;
+        flows
+            .get(edge_id.index())
+            .copied()
+            .ok_or(MaximumFlowError::InvalidEdgeId { edge_id })
+    }
+
+    pub fn flows(&self) -> Result<Vec<F>, MaximumFlowError> {
+        let (_, flows) = self.solution.as_ref().ok_or(MaximumFlowError::NotSolved)?;
+        Ok(flows.clone())
+    }
+
+    // the nodes reachable from the source in the residual graph
+    pub fn minimum_cut(&self) -> Result<Vec<bool>, MaximumFlowError> {
+        let &(source, ref flows) = self.solution.as_ref().ok_or(MaximumFlowError::NotSolved)?;
+        let mut neighbors = vec![Vec::new(); self.num_nodes];
+        for (&(u, v, forward, backward), &flow) in self.edges.iter().zip(flows.iter()) {
+            if flow < forward {
+                neighbors[u.index()].push(v);
+            }
+            if F::zero() < backward + flow {
+                neighbors[v.index()].push(u);
+            }
+        }
+        let mut side = vec![false; self.num_nodes];
+        let mut stack = vec![source];
+        side[source.index()] = true;
+        while let Some(u) = stack.pop() {
+            for &v in neighbors[u.index()].iter() {
+                if !side[v.index()] {
+                    side[v.index()] = true;
+                    stack.push(v);
+                }
+            }
+        }
+        Ok(side)
+    }
+
+    fn head(&self, dart: usize) -> NodeId {
+        let (u, v, _, _) = self.edges[dart / 2];
+        if dart.is_multiple_of(2) { v } else { u }
+    }
+
+    fn capacity(&self, dart: usize) -> F {
+        let (_, _, forward, backward) = self.edges[dart / 2];
+        if dart.is_multiple_of(2) { forward } else { backward }
+    }
+
+    // the boundary of a face on which both terminals lie, with the positions on it of a dart into each terminal
+    fn common_face(&self, source: NodeId, sink: NodeId) -> Option<(Vec<usize>, usize, usize)> {
+        let mut seen = vec![false; self.num_faces];
+        for start in (0..self.next.len()).filter(|&dart| self.next[dart] != usize::MAX) {
+            if seen[self.face[start]] {
+                continue;
+            }
+            seen[self.face[start]] = true;
+            let mut boundary = vec![start];
+            while self.next[*boundary.last().unwrap()] != start {
+                boundary.push(self.next[*boundary.last().unwrap()]);
+            }
+            let into_source = boundary.iter().position(|&dart| self.head(dart) == source);
+            let into_sink = boundary.iter().position(|&dart| self.head(dart) == sink);
+            if let (Some(into_source), Some(into_sink)) = (into_source, into_sink) {
+                return Some((boundary, into_source, into_sink));
+            }
+        }
+        None
+    }
+
+    // the imaginary edge from the sink to the source splits the common face: the darts from the source to the sink
+    // along the boundary keep the face, to the left of the imaginary edge, and those from the sink to the source get
+    // a new one; a flow x on dart d is p(left of d) - p(right of d), which is at most c(d) if p is a shortest path
+    // potential with an arc of length c(d) from the right to the left of every dart
+    fn hassin(&self, boundary: &[usize], into_source: usize, into_sink: usize) -> (F, Vec<F>) {
+        let (m, len) = (self.edges.len(), boundary.len());
+        let split = self.num_faces;
+        let mut face = self.face.clone();
+        let mut i = (into_sink + 1) % len;
+        loop {
+            face[boundary[i]] = split;
+            if i == into_source {
+                break;
+            }
+            i = (i + 1) % len;
+        }
+
+        let mut dual: Graph<Directed, (), F> = Graph::default();
+        dual.add_nodes(self.num_faces + 1);
+        for dart in (0..2 * m).filter(|&dart| face[dart] != usize::MAX) {
+            dual.add_edge(NodeId(face[dart ^ 1]), NodeId(face[dart]), self.capacity(dart));
+        }
+        let mut dijkstra = Dijkstra::new_graph_with(&dual, |e| e.data);
+        dijkstra.solve(NodeId(split)).unwrap();
+        let value = dijkstra.distance(NodeId(self.face[boundary[into_sink]])).unwrap();
+        // faces farther than the value are pulled back to it, which keeps every difference within the capacities
+        let potential = |f: usize| match dijkstra.distance(NodeId(f)) {
+            Some(d) if d < value => d,
+            _ => value,
+        };
+        let flows = (0..m)
+            .map(|e| match face[2 * e] {
+                usize::MAX => F::zero(),
+                f => potential(f) - potential(face[2 * e + 1]),
+            })
+            .collect();
+        (value, flows)
+    }
+
+    // the darts of T have no residual capacity, so augmenting along C keeps the circulation of T feasible; a dart with
+    // residual capacity r(d) has a dual arc of slack r(d) from the face on its right to the face on its left
+    fn borradaile_klein(&self, source: NodeId, sink: NodeId) -> (F, Vec<F>) {
+        let m = self.edges.len();
+        let root = (0..2 * m)
+            .find(|&dart| self.face[dart] != usize::MAX && self.head(dart) == sink)
+            .map(|dart| self.face[dart])
+            .unwrap();
+        let mut dual: Graph<Directed, (), F> = Graph::default();
+        dual.add_nodes(self.num_faces);
+        let darts: Vec<usize> = (0..2 * m).filter(|&dart| self.face[dart] != usize::MAX).collect();
+        for &dart in darts.iter() {
+            dual.add_edge(NodeId(self.face[dart ^ 1]), NodeId(self.face[dart]), self.capacity(dart));
+        }
+        let mut dijkstra = Dijkstra::new_graph_with(&dual, |e| e.data);
+        dijkstra.solve(NodeId(root)).unwrap();
+
+        // the faces of other components are not reached, and their edges carry no flow
+        let mut residual: Vec<F> = (0..2 * m).map(|dart| self.capacity(dart)).collect();
+        for &dart in darts.iter() {
+            let (right, left) = (NodeId(self.face[dart ^ 1]), NodeId(self.face[dart]));
+            if let (Some(right), Some(left)) = (dijkstra.distance(right), dijkstra.distance(left)) {
+                residual[dart] = self.capacity(dart) + right - left;
+            }
+        }
+        let mut parent_dart = vec![usize::MAX; self.num_faces];
+        let mut tree = LinkCutTree::new(self.num_faces);
+        for (f, parent) in parent_dart.iter_mut().enumerate() {
+            if let Some(edge_id) = dijkstra.last_edge(NodeId(f)) {
+                *parent = darts[edge_id.index()];
+                tree.link(f, self.face[*parent ^ 1], F::zero());
+            }
+        }
+        let mut cotree = UndirectedLinkCutTree::new(self.num_nodes, m);
+        let mut in_cotree = vec![false; m];
+        for (e, &(u, v, _, _)) in self.edges.iter().enumerate() {
+            let in_tree = |dart: usize| parent_dart[self.face[dart]] == dart;
+            if u != v
+                && self.component[u.index()] == self.component[source.index()]
+                && !in_tree(2 * e)
+                && !in_tree(2 * e + 1)
+            {
+                cotree.link(e, u.index(), v.index(), residual[2 * e], residual[2 * e + 1]);
+                in_cotree[e] = true;
+            }
+        }
+
+        let mut value = F::zero();
+        loop {
+            let (delta, e) = cotree.path_min(source.index(), sink.index()).unwrap();
+            cotree.path_sub(source.index(), sink.index(), delta);
+            value += delta;
+
+            // the saturated dart of e runs towards the sink
+            (residual[2 * e], residual[2 * e + 1]) = cotree.cut(e);
+            in_cotree[e] = false;
+            let (u, _, _, _) = self.edges[e];
+            let dart = if cotree.connected(source.index(), u.index()) {
+                2 * e
+            } else {
+                2 * e + 1
+            };
+            let (right, left) = (self.face[dart ^ 1], self.face[dart]);
+            if left == root {
+                break;
+            }
+            tree.cut(left);
+            if tree.find_root(right) == left {
+                break;
+            }
+            tree.link(left, right, F::zero());
+            let displaced = std::mem::replace(&mut parent_dart[left], dart) / 2;
+            let (u, v, _, _) = self.edges[displaced];
+            cotree.link(displaced, u.index(), v.index(), residual[2 * displaced], residual[2 * displaced + 1]);
+            in_cotree[displaced] = true;
+        }
+
+        let mut flows = vec![F::zero(); m];
+        for (e, &(u, _, forward, _)) in self.edges.iter().enumerate() {
+            if in_cotree[e] {
+                residual[2 * e] = cotree.keys(e).0;
+            }
+            if self.face[2 * e] != usize::MAX && self.component[u.index()] == self.component[source.index()] {
+                flows[e] = forward - residual[2 * e];
+            }
+        }
+        (value, flows)
+    }
+}
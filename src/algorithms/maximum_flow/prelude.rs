@@ -1,10 +1,23 @@
 pub use super::graph::MaximumFlowGraph;
 pub use super::error::MaximumFlowError;
+pub use super::lexicographic_minimum_cut::LexicographicMinimumCut;
+pub use super::minimum_cut_dag::{MinimumCutDag, MinimumCuts};
+pub use super::node_capacitated::NodeCapacitated;
+pub use super::parametric::{ParametricMaximumFlow, ParametricMinimumCuts};
+pub use super::planar::PlanarMaximumFlow;
+pub use super::solvers::boykov_kolmogorov::BoykovKolmogorov;
 pub use super::solvers::capacity_scaling::CapacityScaling;
 pub use super::solvers::dinic::Dinic;
+pub use super::solvers::dinic_dynamic_tree::DinicDynamicTree;
 pub use super::solvers::edmonds_karp::EdmondsKarp;
+pub use super::solvers::excess_scaling::ExcessScaling;
 pub use super::solvers::ford_fulkerson::FordFulkerson;
+pub use super::solvers::mpm::Mpm;
+#[cfg(feature = "parallel")]
+pub use super::solvers::parallel_push_relabel::ParallelPushRelabel;
+pub use super::solvers::pseudoflow::Pseudoflow;
 pub use super::solvers::push_relabel_fifo::PushRelabelFifo;
 pub use super::solvers::push_relabel_highest_label::PushRelabelHighestLabel;
 pub use super::solvers::shortest_augmenting_path::ShortestAugmentingPath;
 pub use super::solvers::solver::MaximumFlowSolver;
+pub use super::verify::{Violation, verify};
@@ -10,10 +10,11 @@ use crate::{
 };
 use std::collections::VecDeque;
 
-#[derive(Default)]
+#[derive(Clone, Default)]
 pub(crate) struct ResidualNetwork<F> {
     pub(crate) num_nodes: usize,
     pub(crate) num_edges: usize,
+    pub(crate) is_directed: bool,
     pub(crate) edge_id_to_arc_id: Box<[ArcId]>,
 
     pub(crate) start: Box<[usize]>,
@@ -38,6 +39,7 @@ where
         let mut rn = Self {
             num_nodes: graph.num_nodes(),
             num_edges: graph.num_edges(),
+            is_directed: D::IS_DIRECTED,
             edge_id_to_arc_id: vec![INVALID_ARC_ID; graph.num_edges()].into_boxed_slice(),
             start: vec![0; graph.num_nodes() + 1].into_boxed_slice(),
             upper: vec![F::zero(); graph.num_edges() * 2].into_boxed_slice(),
@@ -140,18 +142,37 @@ where
         }
     }
 
+    // with a flow limit, the source is an ordinary node holding the limit as its excess,
+    // so the search passes through it and the source gets its exact distance
+    pub(crate) fn update_distances_to_sink_through_source(&mut self, sink: NodeId) {
+        self.update_distances_to_sink(sink, sink);
+    }
+
     #[inline]
     pub(crate) fn is_admissible_arc(&self, from: NodeId, arc_id: ArcId) -> bool {
         self.residual_capacities[arc_id.index()] > F::zero()
             && self.distances_to_sink[from.index()] == self.distances_to_sink[self.to[arc_id.index()].index()] + 1
     }
 
-    pub(crate) fn reachable_from_source(&self, source: NodeId) -> Vec<bool> {
+    // the source and the nodes with positive excess are on the source side of every minimum cut of a maximum preflow
+    pub(crate) fn source_side_roots(&self, source: NodeId, sink: NodeId) -> Vec<NodeId> {
+        let mut roots = vec![source];
+        roots.extend(
+            (0..self.num_nodes)
+                .map(NodeId)
+                .filter(|&u| u != source && u != sink && self.excesses[u.index()] > F::zero()),
+        );
+        roots
+    }
+
+    pub(crate) fn reachable_from(&self, roots: &[NodeId]) -> Vec<bool> {
         let mut seen = vec![false; self.num_nodes];
         let mut que = VecDeque::new();
 
-        seen[source.index()] = true;
-        que.push_back(source);
+        for &root in roots {
+            seen[root.index()] = true;
+            que.push_back(root);
+        }
 
         while let Some(u) = que.pop_front() {
             for arc_id in self.neighbors(u) {
@@ -167,4 +188,143 @@ where
         }
         seen
     }
+
+    // O(n + m)
+    // strongly connected components of the residual network (kosaraju)
+    // component ids are numbered in topological order of the condensation
+    pub(crate) fn strongly_connected_components(&self) -> (usize, Vec<usize>) {
+        let mut order = Vec::with_capacity(self.num_nodes);
+        let mut visited = vec![false; self.num_nodes];
+        let mut current_arc = self.start[..self.num_nodes].to_vec();
+        let mut stack = Vec::new();
+
+        for r in (0..self.num_nodes).map(NodeId) {
+            if visited[r.index()] {
+                continue;
+            }
+            visited[r.index()] = true;
+            stack.push(r);
+            while let Some(&u) = stack.last() {
+                if current_arc[u.index()] == self.start[u.index() + 1] {
+                    order.push(u);
+                    stack.pop();
+                    continue;
+                }
+                let arc_id = ArcId(current_arc[u.index()]);
+                current_arc[u.index()] += 1;
+
+                let v = self.to[arc_id.index()];
+                if self.residual_capacities[arc_id.index()] > F::zero() && !visited[v.index()] {
+                    visited[v.index()] = true;
+                    stack.push(v);
+                }
+            }
+        }
+
+        let mut num_components = 0;
+        let mut component = vec![usize::MAX; self.num_nodes];
+        for &r in order.iter().rev() {
+            if component[r.index()] != usize::MAX {
+                continue;
+            }
+            component[r.index()] = num_components;
+            stack.push(r);
+            while let Some(v) = stack.pop() {
+                // u -> v in the residual network
+                for arc_id in self.neighbors(v) {
+                    let u = self.to[arc_id.index()];
+                    let rev_arc_id = self.rev[arc_id.index()];
+                    if self.residual_capacities[rev_arc_id.index()] > F::zero() && component[u.index()] == usize::MAX {
+                        component[u.index()] = num_components;
+                        stack.push(u);
+                    }
+                }
+            }
+            num_components += 1;
+        }
+
+        (num_components, component)
+    }
+
+    // returns the excesses to the source along the arcs carrying flow into them:
+    // flow cycles are cancelled by a dfs, then the excesses are pushed back in topological order
+    pub(crate) fn push_flow_excess_back_to_source(&mut self, source: NodeId, sink: NodeId) {
+        const UNVISITED: u8 = 0;
+        const ON_STACK: u8 = 1;
+        const FINISHED: u8 = 2;
+
+        let num_nodes = self.num_nodes;
+        let mut state = vec![UNVISITED; num_nodes];
+        let mut order = Vec::new();
+        let mut stack = Vec::new();
+        let mut current_arc = self.start[..num_nodes].to_vec();
+
+        for r in (0..num_nodes).map(NodeId) {
+            if r == source || r == sink || self.excesses[r.index()] == F::zero() || state[r.index()] != UNVISITED {
+                continue;
+            }
+            state[r.index()] = ON_STACK;
+            stack.push(r);
+
+            'dfs: while let Some(&u) = stack.last() {
+                while current_arc[u.index()] < self.start[u.index() + 1] {
+                    let arc_id = ArcId(current_arc[u.index()]);
+                    let v = self.to[arc_id.index()];
+                    if v == source || v == sink || state[v.index()] == FINISHED || self.returnable(arc_id) == F::zero()
+                    {
+                        current_arc[u.index()] += 1;
+                        continue;
+                    }
+                    if state[v.index()] == UNVISITED {
+                        state[v.index()] = ON_STACK;
+                        stack.push(v);
+                        continue 'dfs;
+                    }
+
+                    // cancel the cycle v -> ... -> u -> v and restart from v
+                    let pos = stack.iter().rposition(|&w| w == v).unwrap();
+                    let delta = stack[pos..]
+                        .iter()
+                        .map(|&w| self.returnable(ArcId(current_arc[w.index()])))
+                        .min()
+                        .unwrap();
+                    for &w in stack[pos..].iter() {
+                        self.push_flow_without_excess(w, ArcId(current_arc[w.index()]), delta);
+                    }
+                    for &w in stack[pos + 1..].iter() {
+                        state[w.index()] = UNVISITED;
+                    }
+                    stack.truncate(pos + 1);
+                    continue 'dfs;
+                }
+
+                state[u.index()] = FINISHED;
+                order.push(u);
+                stack.pop();
+            }
+        }
+
+        for &u in order.iter().rev() {
+            for arc_id in self.neighbors(u) {
+                if self.excesses[u.index()] == F::zero() {
+                    break;
+                }
+                if self.to[arc_id.index()] != sink {
+                    let delta = self.excesses[u.index()].min(self.returnable(arc_id));
+                    self.push_flow(u, arc_id, delta);
+                }
+            }
+        }
+    }
+
+    // flow on the reverse arc of arc_id that can be cancelled
+    #[inline]
+    fn returnable(&self, arc_id: ArcId) -> F {
+        let (residual_capacity, upper) = (self.residual_capacity(arc_id), self.upper[arc_id.index()]);
+        if residual_capacity > upper {
+            residual_capacity - upper
+        } else {
+            F::zero()
+        }
+    }
 }
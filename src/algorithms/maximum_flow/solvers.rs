@@ -1,8 +1,15 @@
+pub mod boykov_kolmogorov;
 pub mod capacity_scaling;
 pub mod dinic;
+pub mod dinic_dynamic_tree;
 pub mod edmonds_karp;
+pub mod excess_scaling;
 pub mod ford_fulkerson;
 pub(crate) mod macros;
+pub mod mpm;
+#[cfg(feature = "parallel")]
+pub mod parallel_push_relabel;
+pub mod pseudoflow;
 pub mod push_relabel_fifo;
 pub mod push_relabel_highest_label;
 pub mod shortest_augmenting_path;
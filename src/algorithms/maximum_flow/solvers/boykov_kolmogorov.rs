@@ -0,0 +1,478 @@
+use crate::graph::edge::Edge;
+use crate::{
+    algorithms::maximum_flow::{
+        edge::MaximumFlowEdge, error::MaximumFlowError, minimum_cut_dag::MinimumCutDag,
+        residual_network::ResidualNetwork, solvers::solver::MaximumFlowSolver, status::Status,
+        validate::validate_input,
+    },
+    core::numeric::FlowNum,
+    graph::{
+        direction::Direction,
+        graph::Graph,
+        ids::{ArcId, EdgeId, INVALID_ARC_ID, NodeId},
+    },
+};
+use std::collections::VecDeque;
+
+const ORPHAN: ArcId = INVALID_ARC_ID;
+const TERMINAL: ArcId = ArcId(usize::MAX - 1);
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Tree {
+    Free,
+    Source,
+    Sink,
+}
+
+pub struct BoykovKolmogorov<F> {
+    status: Status,
+    source: Option<NodeId>,
+    sink: Option<NodeId>,
+
+    rn: ResidualNetwork<F>,
+
+    // implicit arcs source -> u and u -> sink
+    source_capacities: Box<[F]>,
+    sink_capacities: Box<[F]>,
+    source_residuals: Box<[F]>,
+    sink_residuals: Box<[F]>,
+    // flow which may still be sent before the limit is reached
+    residual: Option<F>,
+
+    tree: Box<[Tree]>,
+    // arc from u to its parent
+    parent: Box<[ArcId]>,
+    timestamps: Box<[usize]>,
+    distances: Box<[usize]>,
+    time: usize,
+
+    active: VecDeque<NodeId>,
+    in_active: Box<[bool]>,
+    current_arc: Box<[usize]>,
+    orphans: VecDeque<NodeId>,
+}
+
+impl<F> BoykovKolmogorov<F>
+where
+    F: FlowNum,
+{
+    fn new<D: Direction, N>(graph: &Graph<D, N, MaximumFlowEdge<F>>) -> Self {
+        let rn = ResidualNetwork::from(graph, |e| e.data.upper);
+        Self::new_with_residual_network(rn)
+    }
+
+    pub fn new_with<D: Direction, N, E, UF>(graph: &Graph<D, N, E>, upper_fn: UF) -> Self
+    where
+        UF: Fn(&Edge<E>) -> F,
+    {
+        let rn = ResidualNetwork::from(graph, upper_fn);
+        Self::new_with_residual_network(rn)
+    }
+
+    fn new_with_residual_network(rn: ResidualNetwork<F>) -> Self {
+        let num_nodes = rn.num_nodes;
+        Self {
+            status: Status::NotSolved,
+            source: None,
+            sink: None,
+            rn,
+            source_capacities: vec![F::zero(); num_nodes].into_boxed_slice(),
+            sink_capacities: vec![F::zero(); num_nodes].into_boxed_slice(),
+            source_residuals: vec![F::zero(); num_nodes].into_boxed_slice(),
+            sink_residuals: vec![F::zero(); num_nodes].into_boxed_slice(),
+            residual: None,
+            tree: vec![Tree::Free; num_nodes].into_boxed_slice(),
+            parent: vec![ORPHAN; num_nodes].into_boxed_slice(),
+            timestamps: vec![0; num_nodes].into_boxed_slice(),
+            distances: vec![0; num_nodes].into_boxed_slice(),
+            time: 0,
+            active: VecDeque::new(),
+            in_active: vec![false; num_nodes].into_boxed_slice(),
+            current_arc: vec![0; num_nodes].into_boxed_slice(),
+            orphans: VecDeque::new(),
+        }
+    }
+
+    // adds the capacities of the implicit arcs source -> u and u -> sink (unary terms), which must be non-negative
+    pub fn add_terminal_capacities(
+        &mut self,
+        u: NodeId,
+        source_capacity: F,
+        sink_capacity: F,
+    ) -> Result<(), MaximumFlowError> {
+        if u.index() >= self.rn.num_nodes {
+            return Err(MaximumFlowError::InvalidNodeId { node_id: u });
+        }
+        if source_capacity < F::zero() || sink_capacity < F::zero() {
+            return Err(MaximumFlowError::InvalidParameter);
+        }
+        self.source_capacities[u.index()] += source_capacity;
+        self.sink_capacities[u.index()] += sink_capacity;
+        self.status = Status::NotSolved;
+        Ok(())
+    }
+
+    fn run(&mut self, source: NodeId, sink: NodeId, limit: Option<F>) -> Result<F, MaximumFlowError> {
+        validate_input(&self.rn, source, sink)?;
+
+        self.source = Some(source);
+        self.sink = Some(sink);
+        self.rn.residual_capacities.copy_from_slice(&self.rn.upper);
+        self.source_residuals.copy_from_slice(&self.source_capacities);
+        self.sink_residuals.copy_from_slice(&self.sink_capacities);
+        self.residual = limit;
+
+        let mut flow = self.pre_process(source, sink);
+        while self.residual != Some(F::zero()) {
+            let Some((u, arc_id)) = self.grow() else {
+                break;
+            };
+            flow += self.augment(u, arc_id);
+            self.adopt();
+        }
+
+        self.status = Status::Optimal;
+        Ok(flow)
+    }
+
+    // routes flow of the implicit paths source -> u -> sink directly and builds the initial trees
+    fn pre_process(&mut self, source: NodeId, sink: NodeId) -> F {
+        let mut flow = self.take(self.sink_residuals[source.index()]);
+        flow += self.take(self.source_residuals[sink.index()]);
+        self.source_residuals[source.index()] = F::zero();
+        self.sink_residuals[source.index()] = F::zero();
+        self.source_residuals[sink.index()] = F::zero();
+        self.sink_residuals[sink.index()] = F::zero();
+        for u in 0..self.rn.num_nodes {
+            let delta = self.take(self.source_residuals[u].min(self.sink_residuals[u]));
+            self.source_residuals[u] -= delta;
+            self.sink_residuals[u] -= delta;
+            flow += delta;
+        }
+
+        self.tree.fill(Tree::Free);
+        self.parent.fill(ORPHAN);
+        self.timestamps.fill(0);
+        self.distances.fill(0);
+        self.time = 0;
+        self.active.clear();
+        self.in_active.fill(false);
+        self.orphans.clear();
+
+        for u in (0..self.rn.num_nodes).map(NodeId) {
+            if u == source || self.source_residuals[u.index()] > F::zero() {
+                self.make_root(u, Tree::Source);
+            } else if u == sink || self.sink_residuals[u.index()] > F::zero() {
+                self.make_root(u, Tree::Sink);
+            }
+        }
+        flow
+    }
+
+    // caps delta by the flow which may still be sent and consumes it
+    fn take(&mut self, delta: F) -> F {
+        match &mut self.residual {
+            Some(residual) => {
+                let delta = delta.min(*residual);
+                *residual -= delta;
+                delta
+            }
+            None => delta,
+        }
+    }
+
+    fn make_root(&mut self, u: NodeId, tree: Tree) {
+        self.tree[u.index()] = tree;
+        self.parent[u.index()] = TERMINAL;
+        self.timestamps[u.index()] = self.time;
+        self.distances[u.index()] = 1;
+        self.current_arc[u.index()] = self.rn.start[u.index()];
+        self.activate(u);
+    }
+
+    fn activate(&mut self, u: NodeId) {
+        if !self.in_active[u.index()] {
+            self.in_active[u.index()] = true;
+            self.active.push_back(u);
+        }
+    }
+
+    // residual capacity of the arc in the direction away from the root of the tree of u
+    #[inline]
+    fn tree_capacity(&self, u: NodeId, arc_id: ArcId) -> F {
+        match self.tree[u.index()] {
+            Tree::Sink => self.rn.residual_capacity(self.rn.rev[arc_id.index()]),
+            _ => self.rn.residual_capacity(arc_id),
+        }
+    }
+
+    // grows both trees until they touch, returns (u, arc) where u is the endpoint of the arc in the source tree
+    fn grow(&mut self) -> Option<(NodeId, ArcId)> {
+        while let Some(&u) = self.active.front() {
+            if self.tree[u.index()] == Tree::Free {
+                self.active.pop_front();
+                self.in_active[u.index()] = false;
+                continue;
+            }
+
+            for arc_id in (self.current_arc[u.index()]..self.rn.start[u.index() + 1]).map(ArcId) {
+                self.current_arc[u.index()] = arc_id.index();
+                if self.tree_capacity(u, arc_id) == F::zero() {
+                    continue;
+                }
+                let v = self.rn.to[arc_id.index()];
+                match self.tree[v.index()] {
+                    Tree::Free => {
+                        self.tree[v.index()] = self.tree[u.index()];
+                        self.parent[v.index()] = self.rn.rev[arc_id.index()];
+                        self.timestamps[v.index()] = self.timestamps[u.index()];
+                        self.distances[v.index()] = self.distances[u.index()] + 1;
+                        self.current_arc[v.index()] = self.rn.start[v.index()];
+                        self.activate(v);
+                    }
+                    tree if tree != self.tree[u.index()] => {
+                        return match self.tree[u.index()] {
+                            Tree::Source => Some((u, arc_id)),
+                            _ => Some((v, self.rn.rev[arc_id.index()])),
+                        };
+                    }
+                    _ => {
+                        // prefer parents closer to the terminal
+                        if self.timestamps[v.index()] <= self.timestamps[u.index()]
+                            && self.distances[v.index()] > self.distances[u.index()]
+                        {
+                            self.parent[v.index()] = self.rn.rev[arc_id.index()];
+                            self.timestamps[v.index()] = self.timestamps[u.index()];
+                            self.distances[v.index()] = self.distances[u.index()] + 1;
+                        }
+                    }
+                }
+            }
+
+            self.current_arc[u.index()] = self.rn.start[u.index() + 1];
+            self.active.pop_front();
+            self.in_active[u.index()] = false;
+        }
+        None
+    }
+
+    fn augment(&mut self, u: NodeId, arc_id: ArcId) -> F {
+        let v = self.rn.to[arc_id.index()];
+
+        // bottleneck
+        let mut delta = self.rn.residual_capacity(arc_id);
+        let mut x = u;
+        while self.parent[x.index()] != TERMINAL {
+            let parent_arc_id = self.parent[x.index()];
+            delta = delta.min(self.rn.residual_capacity(self.rn.rev[parent_arc_id.index()]));
+            x = self.rn.to[parent_arc_id.index()];
+        }
+        if Some(x) != self.source {
+            delta = delta.min(self.source_residuals[x.index()]);
+        }
+        let mut x = v;
+        while self.parent[x.index()] != TERMINAL {
+            let parent_arc_id = self.parent[x.index()];
+            delta = delta.min(self.rn.residual_capacity(parent_arc_id));
+            x = self.rn.to[parent_arc_id.index()];
+        }
+        if Some(x) != self.sink {
+            delta = delta.min(self.sink_residuals[x.index()]);
+        }
+        let delta = self.take(delta);
+
+        // update flow
+        self.time += 1;
+        self.rn.push_flow_without_excess(u, arc_id, delta);
+
+        let mut x = u;
+        while self.parent[x.index()] != TERMINAL {
+            let parent_arc_id = self.parent[x.index()];
+            let rev = self.rn.rev[parent_arc_id.index()];
+            let y = self.rn.to[parent_arc_id.index()];
+            self.rn.push_flow_without_excess(y, rev, delta);
+            if self.rn.residual_capacity(rev) == F::zero() {
+                self.make_orphan(x);
+            }
+            x = y;
+        }
+        if Some(x) != self.source {
+            self.source_residuals[x.index()] -= delta;
+            if self.source_residuals[x.index()] == F::zero() {
+                self.make_orphan(x);
+            }
+        }
+
+        let mut x = v;
+        while self.parent[x.index()] != TERMINAL {
+            let parent_arc_id = self.parent[x.index()];
+            let y = self.rn.to[parent_arc_id.index()];
+            self.rn.push_flow_without_excess(x, parent_arc_id, delta);
+            if self.rn.residual_capacity(parent_arc_id) == F::zero() {
+                self.make_orphan(x);
+            }
+            x = y;
+        }
+        if Some(x) != self.sink {
+            self.sink_residuals[x.index()] -= delta;
+            if self.sink_residuals[x.index()] == F::zero() {
+                self.make_orphan(x);
+            }
+        }
+
+        delta
+    }
+
+    fn make_orphan(&mut self, u: NodeId) {
+        self.parent[u.index()] = ORPHAN;
+        self.orphans.push_back(u);
+    }
+
+    fn adopt(&mut self) {
+        while let Some(u) = self.orphans.pop_front() {
+            if let Some(arc_id) = self.find_new_parent(u) {
+                self.parent[u.index()] = arc_id;
+                continue;
+            }
+
+            // u becomes free
+            for arc_id in self.rn.neighbors(u) {
+                let v = self.rn.to[arc_id.index()];
+                if self.tree[v.index()] != self.tree[u.index()] {
+                    continue;
+                }
+                // v has to grow into u again
+                let rev = self.rn.rev[arc_id.index()];
+                if self.tree_capacity(v, rev) > F::zero() {
+                    self.current_arc[v.index()] = self.current_arc[v.index()].min(rev.index());
+                    self.activate(v);
+                }
+                let parent_arc_id = self.parent[v.index()];
+                if parent_arc_id != TERMINAL && parent_arc_id != ORPHAN && self.rn.to[parent_arc_id.index()] == u {
+                    self.make_orphan(v);
+                }
+            }
+            self.tree[u.index()] = Tree::Free;
+        }
+    }
+
+    fn find_new_parent(&mut self, u: NodeId) -> Option<ArcId> {
+        let mut best: Option<(ArcId, usize)> = None;
+        for arc_id in self.rn.neighbors(u) {
+            let v = self.rn.to[arc_id.index()];
+            if self.tree[v.index()] != self.tree[u.index()]
+                || self.tree_capacity(v, self.rn.rev[arc_id.index()]) == F::zero()
+            {
+                continue;
+            }
+            if let Some(d) = self.distance_to_terminal(v)
+                && best.is_none_or(|(_, best_d)| d < best_d)
+            {
+                best = Some((arc_id, d));
+            }
+        }
+
+        let (arc_id, d) = best?;
+        self.timestamps[u.index()] = self.time;
+        self.distances[u.index()] = d + 1;
+        Some(arc_id)
+    }
+
+    // distance from v to the terminal along the tree, or None if v is not connected to the terminal
+    fn distance_to_terminal(&mut self, v: NodeId) -> Option<usize> {
+        let mut d = 0;
+        let mut x = v;
+        loop {
+            if self.timestamps[x.index()] == self.time {
+                d += self.distances[x.index()];
+                break;
+            }
+            let parent_arc_id = self.parent[x.index()];
+            d += 1;
+            if parent_arc_id == TERMINAL {
+                self.timestamps[x.index()] = self.time;
+                self.distances[x.index()] = 1;
+                break;
+            }
+            if parent_arc_id == ORPHAN {
+                return None;
+            }
+            x = self.rn.to[parent_arc_id.index()];
+        }
+
+        // cache the distances along the path
+        let (mut x, mut dist) = (v, d);
+        while self.timestamps[x.index()] != self.time {
+            self.timestamps[x.index()] = self.time;
+            self.distances[x.index()] = dist;
+            dist -= 1;
+            x = self.rn.to[self.parent[x.index()].index()];
+        }
+        Some(d)
+    }
+}
+
+impl<F> MaximumFlowSolver<F> for BoykovKolmogorov<F>
+where
+    F: FlowNum,
+{
+    fn new<D: Direction, N>(graph: &Graph<D, N, MaximumFlowEdge<F>>) -> Self
+    where
+        Self: Sized,
+    {
+        Self::new(graph)
+    }
+
+    fn solve(&mut self, source: NodeId, sink: NodeId) -> Result<F, MaximumFlowError> {
+        self.run(source, sink, None)
+    }
+
+    fn solve_with_limit(&mut self, source: NodeId, sink: NodeId, limit: F) -> Result<F, MaximumFlowError> {
+        self.run(source, sink, Some(limit))
+    }
+
+    fn flow(&self, edge_id: EdgeId) -> Result<F, MaximumFlowError> {
+        if edge_id.index() >= self.rn.num_edges {
+            return Err(MaximumFlowError::InvalidEdgeId { edge_id });
+        }
+        if self.status == Status::NotSolved {
+            return Err(MaximumFlowError::NotSolved);
+        }
+
+        let arc_id = self.rn.edge_id_to_arc_id[edge_id.index()];
+        Ok(self.rn.upper[arc_id.index()] - self.rn.residual_capacities[arc_id.index()])
+    }
+
+    fn flows(&self) -> Result<Vec<F>, MaximumFlowError> {
+        if self.status == Status::NotSolved {
+            return Err(MaximumFlowError::NotSolved);
+        }
+        Ok((0..self.rn.num_edges)
+            .map(|edge_id| self.flow(EdgeId(edge_id)).unwrap())
+            .collect())
+    }
+
+    // the source tree is exactly the set of nodes reachable from the source terminal
+    fn minimum_cut(&mut self) -> Result<Vec<bool>, MaximumFlowError> {
+        if self.status == Status::NotSolved {
+            return Err(MaximumFlowError::NotSolved);
+        }
+        Ok(self.tree.iter().map(|&tree| tree == Tree::Source).collect())
+    }
+
+    fn minimum_cut_dag(&self) -> Result<MinimumCutDag, MaximumFlowError> {
+        if self.status == Status::NotSolved {
+            return Err(MaximumFlowError::NotSolved);
+        }
+        let (source, sink) = (self.source.unwrap(), self.sink.unwrap());
+        let sources: Vec<_> = (0..self.rn.num_nodes)
+            .map(NodeId)
+            .filter(|&u| u == source || self.source_residuals[u.index()] > F::zero())
+            .collect();
+        let sinks: Vec<_> = (0..self.rn.num_nodes)
+            .map(NodeId)
+            .filter(|&u| u == sink || self.sink_residuals[u.index()] > F::zero())
+            .collect();
+        Ok(MinimumCutDag::from_residual_network(&self.rn, &sources, &sinks))
+    }
+}
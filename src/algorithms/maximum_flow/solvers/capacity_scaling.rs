@@ -3,6 +3,7 @@ use crate::{
     algorithms::maximum_flow::{
         edge::MaximumFlowEdge,
         error::MaximumFlowError,
+        minimum_cut_dag::MinimumCutDag,
         residual_network::ResidualNetwork,
         solvers::{macros::impl_maximum_flow_solver, solver::MaximumFlowSolver},
         status::Status,
@@ -10,7 +11,7 @@ use crate::{
     },
     core::numeric::FlowNum,
     graph::{
-        direction::Directed,
+        direction::Direction,
         graph::Graph,
         ids::{ArcId, EdgeId, NodeId},
     },
@@ -21,6 +22,7 @@ use std::collections::VecDeque;
 pub struct CapacityScaling<F> {
     status: Status,
     source: Option<NodeId>,
+    sink: Option<NodeId>,
 
     rn: ResidualNetwork<F>,
     current_edge: Box<[usize]>,
@@ -32,12 +34,12 @@ impl<F> CapacityScaling<F>
 where
     F: FlowNum + One,
 {
-    fn new<N>(graph: &Graph<Directed, N, MaximumFlowEdge<F>>) -> Self {
+    fn new<D: Direction, N>(graph: &Graph<D, N, MaximumFlowEdge<F>>) -> Self {
         let rn = ResidualNetwork::from(graph, |e| e.data.upper);
         Self::new_with_residual_network(rn)
     }
 
-    pub fn new_with<N, E, UF>(graph: &Graph<Directed, N, E>, upper_fn: UF) -> Self
+    pub fn new_with<D: Direction, N, E, UF>(graph: &Graph<D, N, E>, upper_fn: UF) -> Self
     where
         UF: Fn(&Edge<E>) -> F,
     {
@@ -50,6 +52,7 @@ where
         Self {
             status: Status::NotSolved,
             source: None,
+            sink: None,
             rn,
             current_edge: vec![0_usize; num_nodes].into_boxed_slice(),
             que: VecDeque::new(),
@@ -57,10 +60,12 @@ where
         }
     }
 
-    fn run(&mut self, source: NodeId, sink: NodeId) -> Result<F, MaximumFlowError> {
+    fn run(&mut self, source: NodeId, sink: NodeId, limit: Option<F>) -> Result<F, MaximumFlowError> {
         validate_input(&self.rn, source, sink)?;
 
         self.source = Some(source);
+        self.sink = Some(sink);
+        self.rn.residual_capacities.copy_from_slice(&self.rn.upper);
         let max_capacity = *self.rn.upper.iter().max().unwrap_or(&F::zero());
         let mut deltas: Vec<F> = Vec::new();
         let mut d = F::one();
@@ -69,7 +74,7 @@ where
             d = d + d;
         }
 
-        let mut residual = self.cutoff.unwrap_or_else(|| {
+        let mut residual = limit.into_iter().chain(self.cutoff).min().unwrap_or_else(|| {
             self.rn
                 .neighbors(source)
                 .fold(F::zero(), |sum, arc_id| sum + self.rn.upper[arc_id.index()])
@@ -77,7 +82,7 @@ where
         let mut flow = F::zero();
         for delta in deltas.into_iter().rev() {
             // solve maximum flow in delta-residual network
-            loop {
+            while residual > F::zero() {
                 self.bfs(source, sink, delta);
 
                 // no s-t path
@@ -3,6 +3,7 @@ use crate::{
     algorithms::maximum_flow::{
         edge::MaximumFlowEdge,
         error::MaximumFlowError,
+        minimum_cut_dag::MinimumCutDag,
         residual_network::ResidualNetwork,
         solvers::{macros::impl_maximum_flow_solver, solver::MaximumFlowSolver},
         status::Status,
@@ -10,7 +11,7 @@ use crate::{
     },
     core::numeric::FlowNum,
     graph::{
-        direction::Directed,
+        direction::Direction,
         graph::Graph,
         ids::{ArcId, EdgeId, NodeId},
     },
@@ -20,11 +21,13 @@ use std::collections::VecDeque;
 pub struct Dinic<F> {
     status: Status,
     source: Option<NodeId>,
+    sink: Option<NodeId>,
 
     pub(crate) rn: ResidualNetwork<F>,
     current_edge: Box<[usize]>,
     distances_to_sink: Box<[usize]>,
     que: VecDeque<NodeId>,
+    path: Vec<ArcId>,
     cutoff: Option<F>,
 }
 
@@ -32,12 +35,12 @@ impl<F> Dinic<F>
 where
     F: FlowNum,
 {
-    fn new<N>(graph: &Graph<Directed, N, MaximumFlowEdge<F>>) -> Self {
+    fn new<D: Direction, N>(graph: &Graph<D, N, MaximumFlowEdge<F>>) -> Self {
         let rn = ResidualNetwork::from(graph, |e| e.data.upper);
         Self::new_with_residual_network(rn)
     }
 
-    pub fn new_with<N, E, UF>(graph: &Graph<Directed, N, E>, upper_fn: UF) -> Self
+    pub fn new_with<D: Direction, N, E, UF>(graph: &Graph<D, N, E>, upper_fn: UF) -> Self
     where
         UF: Fn(&Edge<E>) -> F,
     {
@@ -50,22 +53,25 @@ where
         Self {
             status: Status::NotSolved,
             source: None,
+            sink: None,
             rn,
             current_edge: vec![0_usize; num_nodes].into_boxed_slice(),
             distances_to_sink: vec![0; num_nodes].into_boxed_slice(),
             que: VecDeque::new(),
+            path: Vec::new(),
             cutoff: None,
         }
     }
 
-    fn run(&mut self, source: NodeId, sink: NodeId) -> Result<F, MaximumFlowError> {
+    fn run(&mut self, source: NodeId, sink: NodeId, limit: Option<F>) -> Result<F, MaximumFlowError> {
         validate_input(&self.rn, source, sink)?;
 
         // initialize
         self.source = Some(source);
+        self.sink = Some(sink);
         self.rn.residual_capacities.copy_from_slice(&self.rn.upper);
 
-        let mut residual = self.cutoff.unwrap_or_else(|| {
+        let mut residual = limit.into_iter().chain(self.cutoff).min().unwrap_or_else(|| {
             self.rn
                 .neighbors(source)
                 .fold(F::zero(), |sum, arc_id| sum + self.rn.upper[arc_id.index()])
@@ -96,37 +102,66 @@ where
         Ok(objective_value)
     }
 
-    fn dfs(&mut self, u: NodeId, sink: NodeId, upper: F) -> Option<F> {
-        if u == sink {
-            return Some(upper);
-        }
-
+    // blocking flow of at most upper with an explicit path stack
+    // after each augmentation, the search resumes from the tail of the saturated arc closest to the source
+    fn dfs(&mut self, source: NodeId, sink: NodeId, upper: F) -> Option<F> {
+        self.path.clear();
         let mut res = F::zero();
-        for arc_id in self.current_edge[u.index()]..self.rn.start[u.index() + 1] {
-            let arc_id = ArcId(arc_id);
-            self.current_edge[u.index()] = arc_id.index();
+        let mut u = source;
+        while res < upper {
+            if u == sink {
+                let delta = self
+                    .path
+                    .iter()
+                    .map(|&arc_id| self.rn.residual_capacities[arc_id.index()])
+                    .fold(upper - res, |delta, residual_capacity| delta.min(residual_capacity));
+                for &arc_id in self.path.iter() {
+                    self.rn
+                        .push_flow_without_excess(self.rn.to[self.rn.rev[arc_id.index()].index()], arc_id, delta);
+                }
+                res += delta;
 
-            let v = self.rn.to[arc_id.index()];
-            let residual_capacity = self.rn.residual_capacities[arc_id.index()];
+                if let Some(pos) = self
+                    .path
+                    .iter()
+                    .position(|&arc_id| self.rn.residual_capacities[arc_id.index()] == F::zero())
+                {
+                    self.path.truncate(pos);
+                }
+                u = self.path.last().map_or(source, |&arc_id| self.rn.to[arc_id.index()]);
+                continue;
+            }
 
-            if !self.is_admissible_edge(u, arc_id) {
+            // advance
+            if let Some(arc_id) = self.find_admissible_edge(u) {
+                self.path.push(arc_id);
+                u = self.rn.to[arc_id.index()];
                 continue;
             }
 
-            if let Some(d) = self.dfs(v, sink, residual_capacity.min(upper - res)) {
-                self.rn.push_flow_without_excess(u, arc_id, d);
-                res += d;
-                if res == upper {
-                    return Some(res);
-                }
+            // retreat
+            self.distances_to_sink[u.index()] = self.rn.num_nodes;
+            if u == source {
+                break;
             }
+            self.path.pop();
+            u = self.path.last().map_or(source, |&arc_id| self.rn.to[arc_id.index()]);
         }
-        self.current_edge[u.index()] = self.rn.start[u.index() + 1];
-        self.distances_to_sink[u.index()] = self.rn.num_nodes;
 
         Some(res)
     }
 
+    fn find_admissible_edge(&mut self, u: NodeId) -> Option<ArcId> {
+        for arc_id in (self.current_edge[u.index()]..self.rn.start[u.index() + 1]).map(ArcId) {
+            self.current_edge[u.index()] = arc_id.index();
+            if self.is_admissible_edge(u, arc_id) {
+                return Some(arc_id);
+            }
+        }
+        self.current_edge[u.index()] = self.rn.start[u.index() + 1];
+        None
+    }
+
     // O(n + m)
     // calculate the distance from u to sink in the residual network
     // if such a path does not exist, distance[u] becomes self.num_nodes
@@ -0,0 +1,166 @@
+use crate::graph::edge::Edge;
+use crate::{
+    algorithms::maximum_flow::{
+        edge::MaximumFlowEdge,
+        error::MaximumFlowError,
+        minimum_cut_dag::MinimumCutDag,
+        residual_network::ResidualNetwork,
+        solvers::{macros::impl_maximum_flow_solver, solver::MaximumFlowSolver},
+        status::Status,
+        validate::validate_input,
+    },
+    core::numeric::FlowNum,
+    data_structures::LinkCutTree,
+    graph::{
+        direction::Direction,
+        graph::Graph,
+        ids::{ArcId, EdgeId, INVALID_ARC_ID, NodeId},
+    },
+};
+
+// Dinic's algorithm with blocking flows found by dynamic trees (Sleator and Tarjan), O(nm log n)
+// the tree arc of each node is its current admissible arc, and the residual capacity of a tree arc is kept in the
+// link-cut tree until the arc is cut
+pub struct DinicDynamicTree<F> {
+    status: Status,
+    source: Option<NodeId>,
+    sink: Option<NodeId>,
+
+    rn: ResidualNetwork<F>,
+    current_edge: Box<[usize]>,
+    tree_arc: Box<[ArcId]>,
+    tree: LinkCutTree<F>,
+}
+
+impl<F> DinicDynamicTree<F>
+where
+    F: FlowNum,
+{
+    fn new<D: Direction, N>(graph: &Graph<D, N, MaximumFlowEdge<F>>) -> Self {
+        let rn = ResidualNetwork::from(graph, |e| e.data.upper);
+        Self::new_with_residual_network(rn)
+    }
+
+    pub fn new_with<D: Direction, N, E, UF>(graph: &Graph<D, N, E>, upper_fn: UF) -> Self
+    where
+        UF: Fn(&Edge<E>) -> F,
+    {
+        let rn = ResidualNetwork::from(graph, upper_fn);
+        Self::new_with_residual_network(rn)
+    }
+
+    fn new_with_residual_network(rn: ResidualNetwork<F>) -> Self {
+        let num_nodes = rn.num_nodes;
+        Self {
+            status: Status::NotSolved,
+            source: None,
+            sink: None,
+            rn,
+            current_edge: vec![0_usize; num_nodes].into_boxed_slice(),
+            tree_arc: vec![INVALID_ARC_ID; num_nodes].into_boxed_slice(),
+            tree: LinkCutTree::new(num_nodes),
+        }
+    }
+
+    fn run(&mut self, source: NodeId, sink: NodeId, limit: Option<F>) -> Result<F, MaximumFlowError> {
+        validate_input(&self.rn, source, sink)?;
+
+        self.source = Some(source);
+        self.sink = Some(sink);
+        self.rn.residual_capacities.copy_from_slice(&self.rn.upper);
+
+        let mut residual = limit.unwrap_or_else(|| {
+            self.rn
+                .neighbors(source)
+                .fold(F::zero(), |sum, arc_id| sum + self.rn.upper[arc_id.index()])
+        });
+        let mut objective_value = F::zero();
+        while residual > F::zero() {
+            self.rn.update_distances_to_sink(source, sink);
+
+            // no s-t path
+            if self.rn.distances_to_sink[source.index()] >= self.rn.num_nodes {
+                break;
+            }
+            let delta = self.blocking_flow(source, sink, residual);
+            objective_value += delta;
+            residual -= delta;
+        }
+
+        self.status = Status::Optimal;
+        Ok(objective_value)
+    }
+
+    fn blocking_flow(&mut self, source: NodeId, sink: NodeId, upper: F) -> F {
+        self.tree.reset();
+        self.tree_arc.fill(INVALID_ARC_ID);
+        self.current_edge.copy_from_slice(&self.rn.start[..self.rn.num_nodes]);
+
+        let mut flow = F::zero();
+        while flow < upper {
+            let u = NodeId(self.tree.find_root(source.index()));
+            if u == sink {
+                // augment along the tree path and cut the saturated arcs
+                let delta = self.tree.path_min(source.index()).unwrap().0.min(upper - flow);
+                self.tree.path_sub(source.index(), delta);
+                flow += delta;
+                while let Some((residual_capacity, v)) = self.tree.path_min(source.index()) {
+                    if residual_capacity > F::zero() {
+                        break;
+                    }
+                    self.cut(NodeId(v));
+                }
+                continue;
+            }
+
+            // advance
+            if let Some(arc_id) = self.find_admissible_arc(u) {
+                let to = self.rn.to[arc_id.index()];
+                self.tree.link(u.index(), to.index(), self.rn.residual_capacity(arc_id));
+                self.tree_arc[u.index()] = arc_id;
+                continue;
+            }
+
+            // retreat: u cannot reach the sink in the level graph anymore
+            if u == source {
+                break;
+            }
+            self.rn.distances_to_sink[u.index()] = self.rn.num_nodes;
+            for arc_id in self.rn.neighbors(u) {
+                let child = self.rn.to[arc_id.index()];
+                if self.tree_arc[child.index()] == self.rn.rev[arc_id.index()] {
+                    self.cut(child);
+                }
+            }
+        }
+
+        // write back the residual capacities of the remaining tree arcs
+        for u in (0..self.rn.num_nodes).map(NodeId) {
+            if self.tree_arc[u.index()] != INVALID_ARC_ID {
+                self.cut(u);
+            }
+        }
+        flow
+    }
+
+    fn find_admissible_arc(&mut self, u: NodeId) -> Option<ArcId> {
+        for arc_id in (self.current_edge[u.index()]..self.rn.start[u.index() + 1]).map(ArcId) {
+            self.current_edge[u.index()] = arc_id.index();
+            if self.rn.is_admissible_arc(u, arc_id) {
+                return Some(arc_id);
+            }
+        }
+        self.current_edge[u.index()] = self.rn.start[u.index() + 1];
+        None
+    }
+
+    fn cut(&mut self, u: NodeId) {
+        let arc_id = self.tree_arc[u.index()];
+        let residual_capacity = self.tree.cut(u.index());
+        let flow = self.rn.residual_capacity(arc_id) - residual_capacity;
+        self.rn.push_flow_without_excess(u, arc_id, flow);
+        self.tree_arc[u.index()] = INVALID_ARC_ID;
+    }
+}
+
+impl_maximum_flow_solver!(DinicDynamicTree, run);
@@ -3,6 +3,7 @@ use crate::{
     algorithms::maximum_flow::{
         edge::MaximumFlowEdge,
         error::MaximumFlowError,
+        minimum_cut_dag::MinimumCutDag,
         residual_network::ResidualNetwork,
         solvers::{macros::impl_maximum_flow_solver, solver::MaximumFlowSolver},
         status::Status,
@@ -10,7 +11,7 @@ use crate::{
     },
     core::numeric::FlowNum,
     graph::{
-        direction::Directed,
+        direction::Direction,
         graph::Graph,
         ids::{EdgeId, INVALID_ARC_ID, INVALID_NODE_ID, NodeId},
     },
@@ -20,6 +21,7 @@ use std::collections::VecDeque;
 pub struct EdmondsKarp<F> {
     status: Status,
     source: Option<NodeId>,
+    sink: Option<NodeId>,
 
     rn: ResidualNetwork<F>,
     cutoff: Option<F>,
@@ -29,12 +31,12 @@ impl<F> EdmondsKarp<F>
 where
     F: FlowNum,
 {
-    fn new<N>(graph: &Graph<Directed, N, MaximumFlowEdge<F>>) -> Self {
+    fn new<D: Direction, N>(graph: &Graph<D, N, MaximumFlowEdge<F>>) -> Self {
         let rn = ResidualNetwork::from(graph, |e| e.data.upper);
         Self::new_with_residual_network(rn)
     }
 
-    pub fn new_with<N, E, UF>(graph: &Graph<Directed, N, E>, upper_fn: UF) -> Self
+    pub fn new_with<D: Direction, N, E, UF>(graph: &Graph<D, N, E>, upper_fn: UF) -> Self
     where
         UF: Fn(&Edge<E>) -> F,
     {
@@ -43,16 +45,18 @@ where
     }
 
     fn new_with_residual_network(rn: ResidualNetwork<F>) -> Self {
-        Self { status: Status::NotSolved, source: None, rn, cutoff: None }
+        Self { status: Status::NotSolved, source: None, sink: None, rn, cutoff: None }
     }
 
-    fn run(&mut self, source: NodeId, sink: NodeId) -> Result<F, MaximumFlowError> {
+    fn run(&mut self, source: NodeId, sink: NodeId, limit: Option<F>) -> Result<F, MaximumFlowError> {
         validate_input(&self.rn, source, sink)?;
 
         self.source = Some(source);
+        self.sink = Some(sink);
+        self.rn.residual_capacities.copy_from_slice(&self.rn.upper);
         let mut prev = vec![(INVALID_NODE_ID, INVALID_ARC_ID); self.rn.num_nodes];
         let mut visited = vec![false; self.rn.num_nodes];
-        let mut residual = self.cutoff.unwrap_or_else(|| {
+        let mut residual = limit.into_iter().chain(self.cutoff).min().unwrap_or_else(|| {
             self.rn
                 .neighbors(source)
                 .fold(F::zero(), |acc, arc_id| acc + self.rn.upper[arc_id.index()])
@@ -0,0 +1,248 @@
+use crate::graph::edge::Edge;
+use crate::{
+    algorithms::maximum_flow::{
+        edge::MaximumFlowEdge,
+        error::MaximumFlowError,
+        minimum_cut_dag::MinimumCutDag,
+        residual_network::ResidualNetwork,
+        solvers::{macros::impl_maximum_flow_solver, solver::MaximumFlowSolver},
+        status::Status,
+        validate::validate_input,
+    },
+    core::numeric::FlowNum,
+    graph::{
+        direction::Direction,
+        graph::Graph,
+        ids::{ArcId, EdgeId, NodeId},
+    },
+};
+use num_traits::One;
+
+// excess scaling algorithm (Ahuja and Orlin), O(nm + n^2 log U)
+// in the phase with scaling factor delta, every excess is at most delta, and among the nodes with excess at least
+// delta / 2 the one with the smallest distance label is pushed or relabeled
+pub struct ExcessScaling<F> {
+    status: Status,
+    source: Option<NodeId>,
+    sink: Option<NodeId>,
+
+    rn: ResidualNetwork<F>,
+    current_arc: Box<[usize]>,
+
+    global_relabel_freq: f64,
+    value_only: bool,
+    limited: bool, // the source holds the flow limit as its excess and is pushed like the other nodes
+    threshold: usize,
+    work: usize,
+
+    buckets: Box<[Vec<NodeId>]>, // buckets[i] = nodes with large excess and distance i
+    in_bucket: Box<[bool]>,
+    bucket_idx: usize,
+}
+
+impl<F> ExcessScaling<F>
+where
+    F: FlowNum + One,
+{
+    fn new<D: Direction, N>(graph: &Graph<D, N, MaximumFlowEdge<F>>) -> Self {
+        let rn = ResidualNetwork::from(graph, |e| e.data.upper);
+        Self::new_with_residual_network(rn)
+    }
+
+    pub fn new_with<D: Direction, N, E, UF>(graph: &Graph<D, N, E>, upper_fn: UF) -> Self
+    where
+        UF: Fn(&Edge<E>) -> F,
+    {
+        let rn = ResidualNetwork::from(graph, upper_fn);
+        Self::new_with_residual_network(rn)
+    }
+
+    fn new_with_residual_network(rn: ResidualNetwork<F>) -> Self {
+        let num_nodes = rn.num_nodes;
+        Self {
+            status: Status::NotSolved,
+            source: None,
+            sink: None,
+            rn,
+            current_arc: vec![0; num_nodes].into_boxed_slice(),
+
+            global_relabel_freq: 1.0,
+            value_only: false,
+            limited: false,
+            threshold: 0,
+            work: 0,
+
+            buckets: vec![Vec::new(); num_nodes].into_boxed_slice(),
+            in_bucket: vec![false; num_nodes].into_boxed_slice(),
+            bucket_idx: 0,
+        }
+    }
+
+    // stops after phase 1: the objective value and the minimum cut are available, but flows are a preflow
+    pub fn set_value_only(mut self, value_only: bool) -> Self {
+        self.value_only = value_only;
+        self
+    }
+
+    pub fn set_global_relabel_freq(mut self, global_relabel_freq: f64) -> Self {
+        self.global_relabel_freq = global_relabel_freq;
+        self
+    }
+
+    fn run(&mut self, source: NodeId, sink: NodeId, limit: Option<F>) -> Result<F, MaximumFlowError> {
+        validate_input(&self.rn, source, sink)?;
+
+        self.source = Some(source);
+        self.sink = Some(sink);
+        self.limited = limit.is_some();
+        self.pre_process(source, sink, limit);
+
+        let max_excess = (0..self.rn.num_nodes)
+            .filter(|&u| (u != source.index() || self.limited) && u != sink.index())
+            .map(|u| self.rn.excesses[u])
+            .max()
+            .unwrap_or(F::zero());
+        let mut deltas = vec![F::one()];
+        while *deltas.last().unwrap() < max_excess {
+            let d = *deltas.last().unwrap();
+            deltas.push(d + d);
+        }
+
+        // in the last phase, every positive excess is large
+        for k in (0..deltas.len()).rev() {
+            let large = if k == 0 { None } else { Some(deltas[k - 1]) };
+            self.scaling_phase(source, sink, deltas[k], large);
+        }
+
+        if !self.value_only {
+            self.rn.push_flow_excess_back_to_source(source, sink);
+        }
+
+        self.status = Status::Optimal;
+        Ok(self.rn.excesses[sink.index()])
+    }
+
+    fn pre_process(&mut self, source: NodeId, sink: NodeId, limit: Option<F>) {
+        self.rn.residual_capacities.copy_from_slice(&self.rn.upper);
+        self.rn.excesses.fill(F::zero());
+        self.work = 0;
+
+        match limit {
+            Some(limit) => self.rn.excesses[source.index()] = limit,
+            None => {
+                for arc_id in self.rn.neighbors(source) {
+                    let delta = self.rn.residual_capacity(arc_id);
+                    self.rn.push_flow_without_excess(source, arc_id, delta);
+                    self.rn.excesses[self.rn.to[arc_id.index()].index()] += delta;
+                }
+            }
+        }
+        self.global_relabel(source, sink);
+
+        self.threshold = if self.global_relabel_freq <= 0.0 {
+            usize::MAX
+        } else {
+            (((self.rn.num_nodes + self.rn.num_edges) as f64) / self.global_relabel_freq).ceil() as usize
+        };
+    }
+
+    fn global_relabel(&mut self, source: NodeId, sink: NodeId) {
+        if self.limited {
+            self.rn.update_distances_to_sink_through_source(sink);
+        } else {
+            self.rn.update_distances_to_sink(source, sink);
+            self.rn.distances_to_sink[source.index()] = self.rn.num_nodes;
+        }
+        self.current_arc.copy_from_slice(&self.rn.start[..self.rn.num_nodes]);
+    }
+
+    fn scaling_phase(&mut self, source: NodeId, sink: NodeId, delta: F, large: Option<F>) {
+        self.fill_buckets(source, sink, large);
+        while let Some(u) = self.lowest_large_excess_node() {
+            self.push_relabel(u, sink, delta, large);
+            self.enqueue(u, large);
+
+            if self.work > self.threshold {
+                self.work = 0;
+                self.global_relabel(source, sink);
+                self.fill_buckets(source, sink, large);
+            }
+        }
+    }
+
+    fn fill_buckets(&mut self, source: NodeId, sink: NodeId, large: Option<F>) {
+        self.buckets.iter_mut().for_each(|bucket| bucket.clear());
+        self.in_bucket.fill(false);
+        self.bucket_idx = 0;
+        for u in (0..self.rn.num_nodes).map(NodeId) {
+            if (u != source || self.limited) && u != sink {
+                self.enqueue(u, large);
+            }
+        }
+    }
+
+    // nodes which cannot reach the sink are left for phase 2
+    fn enqueue(&mut self, u: NodeId, large: Option<F>) {
+        let excess = self.rn.excesses[u.index()];
+        let is_large = match large {
+            Some(large) => excess >= large,
+            None => excess > F::zero(),
+        };
+        if self.in_bucket[u.index()] || !is_large || self.rn.distances_to_sink[u.index()] >= self.rn.num_nodes {
+            return;
+        }
+
+        let d = self.rn.distances_to_sink[u.index()];
+        self.in_bucket[u.index()] = true;
+        self.buckets[d].push(u);
+        self.bucket_idx = self.bucket_idx.min(d);
+    }
+
+    fn lowest_large_excess_node(&mut self) -> Option<NodeId> {
+        while self.bucket_idx < self.buckets.len() {
+            if let Some(u) = self.buckets[self.bucket_idx].pop() {
+                self.in_bucket[u.index()] = false;
+                return Some(u);
+            }
+            self.bucket_idx += 1;
+        }
+        None
+    }
+
+    // pushes from u along its current admissible arc without raising the excess of the head above delta,
+    // or relabels u
+    fn push_relabel(&mut self, u: NodeId, sink: NodeId, delta: F, large: Option<F>) {
+        for arc_id in (self.current_arc[u.index()]..self.rn.start[u.index() + 1]).map(ArcId) {
+            self.current_arc[u.index()] = arc_id.index();
+            if !self.rn.is_admissible_arc(u, arc_id) {
+                continue;
+            }
+
+            // the head has a smaller distance label than every other large excess node, so its excess is small
+            let to = self.rn.to[arc_id.index()];
+            let mut flow = self.rn.excesses[u.index()].min(self.rn.residual_capacity(arc_id));
+            if to != sink {
+                flow = flow.min(delta - self.rn.excesses[to.index()]);
+            }
+            self.rn.push_flow(u, arc_id, flow);
+            if to != sink {
+                self.enqueue(to, large);
+            }
+            return;
+        }
+
+        // relabel
+        self.work += self.rn.start[u.index() + 1] - self.rn.start[u.index()];
+        self.rn.distances_to_sink[u.index()] = self
+            .rn
+            .neighbors(u)
+            .filter(|&arc_id| self.rn.residual_capacity(arc_id) > F::zero())
+            .map(|arc_id| self.rn.distances_to_sink[self.rn.to[arc_id.index()].index()] + 1)
+            .min()
+            .unwrap_or(self.rn.num_nodes)
+            .min(self.rn.num_nodes);
+        self.current_arc[u.index()] = self.rn.start[u.index()];
+    }
+}
+
+impl_maximum_flow_solver!(ExcessScaling, run, One);
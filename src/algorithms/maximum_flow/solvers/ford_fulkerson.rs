@@ -3,6 +3,7 @@ use crate::{
     algorithms::maximum_flow::{
         edge::MaximumFlowEdge,
         error::MaximumFlowError,
+        minimum_cut_dag::MinimumCutDag,
         residual_network::ResidualNetwork,
         solvers::{macros::impl_maximum_flow_solver, solver::MaximumFlowSolver},
         status::Status,
@@ -10,18 +11,20 @@ use crate::{
     },
     core::numeric::FlowNum,
     graph::{
-        direction::Directed,
+        direction::Direction,
         graph::Graph,
-        ids::{EdgeId, NodeId},
+        ids::{ArcId, EdgeId, NodeId},
     },
 };
 
 pub struct FordFulkerson<F> {
     status: Status,
     source: Option<NodeId>,
+    sink: Option<NodeId>,
 
     rn: ResidualNetwork<F>,
     visited: Box<[bool]>,
+    stack: Vec<(NodeId, ArcId)>, // nodes on the current path and the arc to try next
     cutoff: Option<F>,
 }
 
@@ -29,12 +32,12 @@ impl<F> FordFulkerson<F>
 where
     F: FlowNum,
 {
-    fn new<N>(graph: &Graph<Directed, N, MaximumFlowEdge<F>>) -> Self {
+    fn new<D: Direction, N>(graph: &Graph<D, N, MaximumFlowEdge<F>>) -> Self {
         let rn = ResidualNetwork::from(graph, |e| e.data.upper);
         Self::new_with_residual_network(rn)
     }
 
-    pub fn new_with<N, E, UF>(graph: &Graph<Directed, N, E>, upper_fn: UF) -> Self
+    pub fn new_with<D: Direction, N, E, UF>(graph: &Graph<D, N, E>, upper_fn: UF) -> Self
     where
         UF: Fn(&Edge<E>) -> F,
     {
@@ -47,20 +50,23 @@ where
         Self {
             status: Status::NotSolved,
             source: None,
+            sink: None,
             rn,
             visited: vec![false; num_nodes].into_boxed_slice(),
+            stack: Vec::new(),
             cutoff: None,
         }
     }
 
-    pub(crate) fn run(&mut self, source: NodeId, sink: NodeId) -> Result<F, MaximumFlowError> {
+    pub(crate) fn run(&mut self, source: NodeId, sink: NodeId, limit: Option<F>) -> Result<F, MaximumFlowError> {
         validate_input(&self.rn, source, sink)?;
 
         // initialize
         self.source = Some(source);
+        self.sink = Some(sink);
         self.rn.residual_capacities.copy_from_slice(&self.rn.upper);
 
-        let mut residual = self.cutoff.unwrap_or_else(|| {
+        let mut residual = limit.into_iter().chain(self.cutoff).min().unwrap_or_else(|| {
             self.rn
                 .neighbors(source)
                 .fold(F::zero(), |acc, arc_id| acc + self.rn.residual_capacities[arc_id.index()])
@@ -82,23 +88,41 @@ where
         Ok(objective_value)
     }
 
-    fn dfs(&mut self, u: NodeId, sink: NodeId, flow: F) -> Option<F> {
-        if u == sink {
-            return Some(flow);
-        }
-        self.visited[u.index()] = true;
+    // finds a single augmenting path with an explicit stack and augments at most flow along it
+    fn dfs(&mut self, source: NodeId, sink: NodeId, flow: F) -> Option<F> {
+        self.stack.clear();
+        self.stack.push((source, ArcId(self.rn.start[source.index()])));
+        self.visited[source.index()] = true;
+
+        while let Some(&mut (u, ref mut arc_id)) = self.stack.last_mut() {
+            if u == sink {
+                self.stack.pop();
+                let delta = self
+                    .stack
+                    .iter()
+                    .map(|&(_, arc_id)| self.rn.residual_capacities[arc_id.index()])
+                    .fold(flow, |delta, residual_capacity| delta.min(residual_capacity));
+                for &(v, arc_id) in self.stack.iter() {
+                    self.rn.push_flow_without_excess(v, arc_id, delta);
+                }
+                return Some(delta);
+            }
 
-        for arc_id in self.rn.neighbors(u) {
-            let to = self.rn.to[arc_id.index()];
-            let residual_capacity = self.rn.residual_capacities[arc_id.index()];
-            if self.visited[to.index()] || residual_capacity == F::zero() {
+            if arc_id.index() == self.rn.start[u.index() + 1] {
+                self.stack.pop();
+                if let Some((_, arc_id)) = self.stack.last_mut() {
+                    *arc_id = ArcId(arc_id.index() + 1);
+                }
                 continue;
             }
 
-            if let Some(d) = self.dfs(to, sink, flow.min(residual_capacity)) {
-                self.rn.push_flow_without_excess(u, arc_id, d);
-                return Some(d);
+            let to = self.rn.to[arc_id.index()];
+            if self.visited[to.index()] || self.rn.residual_capacities[arc_id.index()] == F::zero() {
+                *arc_id = ArcId(arc_id.index() + 1);
+                continue;
             }
+            self.visited[to.index()] = true;
+            self.stack.push((to, ArcId(self.rn.start[to.index()])));
         }
         None
     }
@@ -4,7 +4,7 @@ macro_rules! impl_maximum_flow_solver {
         where
             F: FlowNum $( + $bound )*,
         {
-            fn new<N>(graph: &Graph<Directed, N, MaximumFlowEdge<F>>) -> Self
+            fn new<D: Direction, N>(graph: &Graph<D, N, MaximumFlowEdge<F>>) -> Self
             where
                 Self: Sized,
             {
@@ -12,7 +12,12 @@ macro_rules! impl_maximum_flow_solver {
             }
 
             fn solve(&mut self, source: NodeId, sink: NodeId) -> Result<F, MaximumFlowError> {
-                let objective_value = self.$run(source, sink)?;
+                let objective_value = self.$run(source, sink, None)?;
+                Ok(objective_value)
+            }
+
+            fn solve_with_limit(&mut self, source: NodeId, sink: NodeId, limit: F) -> Result<F, MaximumFlowError> {
+                let objective_value = self.$run(source, sink, Some(limit))?;
                 Ok(objective_value)
             }
 
@@ -39,11 +44,20 @@ macro_rules! impl_maximum_flow_solver {
                 if self.status == Status::NotSolved {
                     return Err(MaximumFlowError::NotSolved);
                 }
-                Ok(self.rn.reachable_from_source(self.source.unwrap()))
+                let roots = self.rn.source_side_roots(self.source.unwrap(), self.sink.unwrap());
+                Ok(self.rn.reachable_from(&roots))
+            }
+
+            fn minimum_cut_dag(&self) -> Result<MinimumCutDag, MaximumFlowError> {
+                if self.status == Status::NotSolved {
+                    return Err(MaximumFlowError::NotSolved);
+                }
+                let roots = self.rn.source_side_roots(self.source.unwrap(), self.sink.unwrap());
+                Ok(MinimumCutDag::from_residual_network(&self.rn, &roots, &[self.sink.unwrap()]))
             }
         }
     };
 }
 
-pub(crate) use impl_maximum_flow_solver;
 use crate::prelude::maximum_flow::MaximumFlowError;
+pub(crate) use impl_maximum_flow_solver;
@@ -0,0 +1,272 @@
+use crate::graph::edge::Edge;
+use crate::{
+    algorithms::maximum_flow::{
+        edge::MaximumFlowEdge,
+        error::MaximumFlowError,
+        minimum_cut_dag::MinimumCutDag,
+        residual_network::ResidualNetwork,
+        solvers::{macros::impl_maximum_flow_solver, solver::MaximumFlowSolver},
+        status::Status,
+        validate::validate_input,
+    },
+    core::numeric::FlowNum,
+    graph::{
+        direction::Direction,
+        graph::Graph,
+        ids::{ArcId, EdgeId, NodeId},
+    },
+};
+use std::collections::VecDeque;
+
+// Malhotra, Pramodh Kumar and Maheshwari's algorithm, O(n^3)
+// each blocking flow repeatedly sends the minimum throughput of the level graph from the node attaining it,
+// forwards to the sink and backwards to the source, and deletes saturated arcs and nodes without throughput
+pub struct Mpm<F> {
+    status: Status,
+    source: Option<NodeId>,
+    sink: Option<NodeId>,
+
+    rn: ResidualNetwork<F>,
+    alive: Box<[bool]>,
+    in_capacity: Box<[F]>,
+    out_capacity: Box<[F]>,
+    current_out_edge: Box<[usize]>,
+    current_in_edge: Box<[usize]>,
+    pending: Box<[F]>,
+    que: VecDeque<NodeId>,
+    deleted: Vec<NodeId>,
+}
+
+impl<F> Mpm<F>
+where
+    F: FlowNum,
+{
+    fn new<D: Direction, N>(graph: &Graph<D, N, MaximumFlowEdge<F>>) -> Self {
+        let rn = ResidualNetwork::from(graph, |e| e.data.upper);
+        Self::new_with_residual_network(rn)
+    }
+
+    pub fn new_with<D: Direction, N, E, UF>(graph: &Graph<D, N, E>, upper_fn: UF) -> Self
+    where
+        UF: Fn(&Edge<E>) -> F,
+    {
+        let rn = ResidualNetwork::from(graph, upper_fn);
+        Self::new_with_residual_network(rn)
+    }
+
+    fn new_with_residual_network(rn: ResidualNetwork<F>) -> Self {
+        let num_nodes = rn.num_nodes;
+        Self {
+            status: Status::NotSolved,
+            source: None,
+            sink: None,
+            rn,
+            alive: vec![false; num_nodes].into_boxed_slice(),
+            in_capacity: vec![F::zero(); num_nodes].into_boxed_slice(),
+            out_capacity: vec![F::zero(); num_nodes].into_boxed_slice(),
+            current_out_edge: vec![0; num_nodes].into_boxed_slice(),
+            current_in_edge: vec![0; num_nodes].into_boxed_slice(),
+            pending: vec![F::zero(); num_nodes].into_boxed_slice(),
+            que: VecDeque::new(),
+            deleted: Vec::new(),
+        }
+    }
+
+    fn run(&mut self, source: NodeId, sink: NodeId, limit: Option<F>) -> Result<F, MaximumFlowError> {
+        validate_input(&self.rn, source, sink)?;
+
+        self.source = Some(source);
+        self.sink = Some(sink);
+        self.rn.residual_capacities.copy_from_slice(&self.rn.upper);
+
+        let mut residual = limit.unwrap_or_else(|| {
+            self.rn
+                .neighbors(source)
+                .fold(F::zero(), |sum, arc_id| sum + self.rn.upper[arc_id.index()])
+        });
+        let mut objective_value = F::zero();
+        while residual > F::zero() {
+            self.rn.update_distances_to_sink(source, sink);
+
+            // no s-t path
+            if self.rn.distances_to_sink[source.index()] >= self.rn.num_nodes {
+                break;
+            }
+            let delta = self.blocking_flow(source, sink, residual);
+            objective_value += delta;
+            residual -= delta;
+        }
+
+        self.status = Status::Optimal;
+        Ok(objective_value)
+    }
+
+    // arc of the level graph between two alive nodes
+    #[inline]
+    fn is_level_arc(&self, from: NodeId, arc_id: ArcId) -> bool {
+        self.alive[from.index()]
+            && self.alive[self.rn.to[arc_id.index()].index()]
+            && self.rn.is_admissible_arc(from, arc_id)
+    }
+
+    fn throughput(&self, u: NodeId, source: NodeId, sink: NodeId) -> F {
+        if u == source {
+            self.out_capacity[u.index()]
+        } else if u == sink {
+            self.in_capacity[u.index()]
+        } else {
+            self.in_capacity[u.index()].min(self.out_capacity[u.index()])
+        }
+    }
+
+    fn blocking_flow(&mut self, source: NodeId, sink: NodeId, upper: F) -> F {
+        let num_nodes = self.rn.num_nodes;
+        for u in 0..num_nodes {
+            self.alive[u] = self.rn.distances_to_sink[u] < num_nodes;
+            self.current_out_edge[u] = self.rn.start[u];
+            self.current_in_edge[u] = self.rn.start[u];
+        }
+        self.in_capacity.fill(F::zero());
+        self.out_capacity.fill(F::zero());
+        for u in (0..num_nodes).map(NodeId) {
+            for arc_id in self.rn.neighbors(u) {
+                if self.is_level_arc(u, arc_id) {
+                    let residual_capacity = self.rn.residual_capacity(arc_id);
+                    self.out_capacity[u.index()] += residual_capacity;
+                    self.in_capacity[self.rn.to[arc_id.index()].index()] += residual_capacity;
+                }
+            }
+        }
+
+        self.deleted = (0..num_nodes)
+            .map(NodeId)
+            .filter(|&u| self.alive[u.index()] && self.throughput(u, source, sink) == F::zero())
+            .collect();
+        self.delete_nodes(source, sink);
+
+        let mut flow = F::zero();
+        while flow < upper && self.alive[source.index()] && self.alive[sink.index()] {
+            let u = (0..num_nodes)
+                .map(NodeId)
+                .filter(|&u| self.alive[u.index()])
+                .min_by_key(|&u| self.throughput(u, source, sink))
+                .unwrap();
+            let delta = self.throughput(u, source, sink).min(upper - flow);
+
+            self.push_forward(u, sink, delta);
+            self.pull_backward(u, source, delta);
+            flow += delta;
+            self.delete_nodes(source, sink);
+        }
+        flow
+    }
+
+    // sends delta from u to the sink level by level
+    fn push_forward(&mut self, u: NodeId, sink: NodeId, delta: F) {
+        self.pending[u.index()] = delta;
+        self.que.push_back(u);
+        while let Some(v) = self.que.pop_front() {
+            if v == sink {
+                self.pending[v.index()] = F::zero();
+                continue;
+            }
+
+            // the throughput of v covers its pending flow, so the arcs of v never run out before it is sent
+            let end = self.rn.start[v.index() + 1];
+            while self.pending[v.index()] > F::zero() && self.current_out_edge[v.index()] < end {
+                let arc_id = ArcId(self.current_out_edge[v.index()]);
+                if !self.is_level_arc(v, arc_id) || self.rn.residual_capacity(arc_id) == F::zero() {
+                    self.current_out_edge[v.index()] += 1;
+                    continue;
+                }
+
+                let to = self.rn.to[arc_id.index()];
+                let flow = self.pending[v.index()].min(self.rn.residual_capacity(arc_id));
+                self.send(v, arc_id, flow);
+                if self.pending[to.index()] == F::zero() {
+                    self.que.push_back(to);
+                }
+                self.pending[v.index()] -= flow;
+                self.pending[to.index()] += flow;
+            }
+            debug_assert!(self.pending[v.index()] == F::zero());
+        }
+    }
+
+    // draws delta into u from the source level by level
+    fn pull_backward(&mut self, u: NodeId, source: NodeId, delta: F) {
+        self.pending[u.index()] = delta;
+        self.que.push_back(u);
+        while let Some(v) = self.que.pop_front() {
+            if v == source {
+                self.pending[v.index()] = F::zero();
+                continue;
+            }
+
+            let end = self.rn.start[v.index() + 1];
+            while self.pending[v.index()] > F::zero() && self.current_in_edge[v.index()] < end {
+                // arc_id is from v to the tail of a level arc entering v
+                let arc_id = ArcId(self.current_in_edge[v.index()]);
+                let (from, rev) = (self.rn.to[arc_id.index()], self.rn.rev[arc_id.index()]);
+                if !self.is_level_arc(from, rev) || self.rn.residual_capacity(rev) == F::zero() {
+                    self.current_in_edge[v.index()] += 1;
+                    continue;
+                }
+
+                let flow = self.pending[v.index()].min(self.rn.residual_capacity(rev));
+                self.send(from, rev, flow);
+                if self.pending[from.index()] == F::zero() {
+                    self.que.push_back(from);
+                }
+                self.pending[v.index()] -= flow;
+                self.pending[from.index()] += flow;
+            }
+            debug_assert!(self.pending[v.index()] == F::zero());
+        }
+    }
+
+    fn send(&mut self, u: NodeId, arc_id: ArcId, flow: F) {
+        let to = self.rn.to[arc_id.index()];
+        self.rn.push_flow_without_excess(u, arc_id, flow);
+        self.out_capacity[u.index()] -= flow;
+        self.in_capacity[to.index()] -= flow;
+        for w in [u, to] {
+            if self.throughput(w, self.source.unwrap(), self.sink.unwrap()) == F::zero() {
+                self.deleted.push(w);
+            }
+        }
+    }
+
+    // removes nodes without throughput together with their level arcs
+    fn delete_nodes(&mut self, source: NodeId, sink: NodeId) {
+        while let Some(u) = self.deleted.pop() {
+            if !self.alive[u.index()] {
+                continue;
+            }
+            self.alive[u.index()] = false;
+            if u == source || u == sink {
+                continue;
+            }
+
+            for arc_id in self.rn.neighbors(u) {
+                let to = self.rn.to[arc_id.index()];
+                let rev = self.rn.rev[arc_id.index()];
+                if !self.alive[to.index()] {
+                    continue;
+                }
+                if self.rn.is_admissible_arc(u, arc_id) {
+                    self.in_capacity[to.index()] -= self.rn.residual_capacity(arc_id);
+                } else if self.rn.is_admissible_arc(to, rev) {
+                    self.out_capacity[to.index()] -= self.rn.residual_capacity(rev);
+                } else {
+                    continue;
+                }
+                if self.throughput(to, source, sink) == F::zero() {
+                    self.deleted.push(to);
+                }
+            }
+        }
+    }
+}
+
+impl_maximum_flow_solver!(Mpm, run);
@@ -0,0 +1,448 @@
+use crate::{
+    algorithms::maximum_flow::{
+        edge::MaximumFlowEdge,
+        error::MaximumFlowError,
+        minimum_cut_dag::MinimumCutDag,
+        residual_network::ResidualNetwork,
+        solvers::{macros::impl_maximum_flow_solver, solver::MaximumFlowSolver},
+        status::Status,
+        validate::validate_input,
+    },
+    core::numeric::FlowNum,
+    graph::{
+        direction::Direction,
+        edge::Edge,
+        graph::Graph,
+        ids::{ArcId, EdgeId, NodeId},
+    },
+};
+use std::{
+    sync::{
+        Barrier, Mutex, RwLock,
+        atomic::{AtomicBool, Ordering},
+    },
+    thread,
+};
+
+// minimum number of nodes per thread
+const MIN_CHUNK_SIZE: usize = 256;
+
+// synchronous parallel push-relabel (Baumstark, Blelloch and Shun)
+// the threads are started once per solve, each owning a range of nodes and their arcs, and the phases of a round
+// are separated by a barrier; each round discharges all active nodes with the labels of the previous round:
+// 1. pushes are computed in parallel without modifying the residual capacities
+// 2. every thread applies the pushes into and out of its own nodes
+// 3. nodes with remaining excess are relabeled in parallel
+// 4. every thread stores its new labels and collects its active nodes
+pub struct ParallelPushRelabel<F> {
+    status: Status,
+    source: Option<NodeId>,
+    sink: Option<NodeId>,
+
+    rn: ResidualNetwork<F>,
+    num_threads: usize,
+    global_relabel_freq: f64,
+    value_only: bool,
+    limited: bool, // the source holds the flow limit as its excess and is discharged like the other nodes
+    threshold: usize,
+    work: usize,
+
+    active_nodes: Vec<NodeId>,
+}
+
+struct Update<F> {
+    node: NodeId,
+    arc_id: ArcId,
+    delta: F,
+    is_push: bool, // push: flow leaves node along arc_id, otherwise flow enters node and arc_id is the reverse arc
+}
+
+impl<F> ParallelPushRelabel<F>
+where
+    F: FlowNum + Send + Sync,
+{
+    fn new<D: Direction, N>(graph: &Graph<D, N, MaximumFlowEdge<F>>) -> Self {
+        let rn = ResidualNetwork::from(graph, |e| e.data.upper);
+        Self::new_with_residual_network(rn)
+    }
+
+    pub fn new_with<D: Direction, N, E, UF>(graph: &Graph<D, N, E>, upper_fn: UF) -> Self
+    where
+        UF: Fn(&Edge<E>) -> F,
+    {
+        let rn = ResidualNetwork::from(graph, upper_fn);
+        Self::new_with_residual_network(rn)
+    }
+
+    fn new_with_residual_network(rn: ResidualNetwork<F>) -> Self {
+        Self {
+            status: Status::NotSolved,
+            source: None,
+            sink: None,
+            rn,
+            num_threads: thread::available_parallelism().map_or(1, |n| n.get()),
+            global_relabel_freq: 1.0,
+            value_only: false,
+            limited: false,
+            threshold: 0,
+            work: 0,
+
+            active_nodes: Vec::new(),
+        }
+    }
+
+    pub fn set_num_threads(mut self, num_threads: usize) -> Self {
+        self.num_threads = num_threads.max(1);
+        self
+    }
+
+    // stops after phase 1: the objective value and the minimum cut are available, but flows are a preflow
+    pub fn set_value_only(mut self, value_only: bool) -> Self {
+        self.value_only = value_only;
+        self
+    }
+
+    pub fn set_global_relabel_freq(mut self, global_relabel_freq: f64) -> Self {
+        self.global_relabel_freq = global_relabel_freq;
+        self
+    }
+
+    fn run(&mut self, source: NodeId, sink: NodeId, limit: Option<F>) -> Result<F, MaximumFlowError> {
+        validate_input(&self.rn, source, sink)?;
+
+        self.source = Some(source);
+        self.sink = Some(sink);
+        self.limited = limit.is_some();
+        self.pre_process(source, sink, limit);
+
+        let num_threads = self.num_threads.min(self.rn.num_nodes.div_ceil(MIN_CHUNK_SIZE)).max(1);
+        let pool = Pool::new(&mut self.rn, num_threads, &self.active_nodes, source, sink, self.limited);
+        thread::scope(|s| {
+            for t in 1..num_threads {
+                let pool = &pool;
+                s.spawn(move || while pool.round(t) {});
+            }
+
+            // the calling thread is thread 0 and decides between the rounds whether to go on
+            loop {
+                pool.done.store(pool.num_active_nodes() == 0, Ordering::Relaxed);
+                if !pool.round(0) {
+                    break;
+                }
+                self.work += pool.take_work();
+                if self.work > self.threshold {
+                    self.work = 0;
+                    pool.global_relabel();
+                }
+            }
+        });
+        pool.finish();
+        self.active_nodes.clear();
+
+        if !self.value_only {
+            self.rn.push_flow_excess_back_to_source(source, sink);
+        }
+
+        self.status = Status::Optimal;
+        Ok(self.rn.excesses[sink.index()])
+    }
+
+    fn pre_process(&mut self, source: NodeId, sink: NodeId, limit: Option<F>) {
+        self.rn.residual_capacities.copy_from_slice(&self.rn.upper);
+        self.rn.excesses.fill(F::zero());
+        self.work = 0;
+
+        match limit {
+            Some(limit) => self.rn.excesses[source.index()] = limit,
+            None => {
+                for arc_id in self.rn.neighbors(source) {
+                    let delta = self.rn.residual_capacity(arc_id);
+                    self.rn.push_flow_without_excess(source, arc_id, delta);
+                    self.rn.excesses[self.rn.to[arc_id.index()].index()] += delta;
+                }
+            }
+        }
+
+        self.active_nodes = (0..self.rn.num_nodes)
+            .map(NodeId)
+            .filter(|&u| (u != source || self.limited) && u != sink && self.rn.excesses[u.index()] > F::zero())
+            .collect();
+        update_distances(&mut self.rn, source, sink, self.limited);
+        let (num_nodes, distances) = (self.rn.num_nodes, &self.rn.distances_to_sink);
+        self.active_nodes.retain(|&u| distances[u.index()] < num_nodes);
+
+        self.threshold = if self.global_relabel_freq <= 0.0 {
+            usize::MAX
+        } else {
+            (((self.rn.num_nodes + self.rn.num_edges) as f64) / self.global_relabel_freq).ceil() as usize
+        };
+    }
+}
+
+// the threads of one solve; thread t owns the nodes bounds[t]..bounds[t + 1] together with their excesses
+// and the residual capacities of their arcs, which the residual network only gets back for global relabels
+// and at the end of the solve
+struct Pool<'a, F> {
+    rn: RwLock<&'a mut ResidualNetwork<F>>,
+    segments: Vec<Mutex<Segment<F>>>,
+    // outboxes[t][o] holds the updates computed by thread t for the nodes of thread o
+    outboxes: Vec<Vec<Mutex<Vec<Update<F>>>>>,
+    barrier: Barrier,
+    done: AtomicBool,
+    bounds: Vec<usize>,
+    arc_bounds: Vec<usize>,
+    source: NodeId,
+    sink: NodeId,
+    limited: bool,
+}
+
+struct Segment<F> {
+    residual_capacities: Vec<F>,
+    excesses: Vec<F>,
+    marked: Vec<bool>,
+    active_nodes: Vec<NodeId>,
+    // nodes that received flow in the current round
+    activated: Vec<NodeId>,
+    // nodes left with excess after their pushes, and their new labels
+    relabels: Vec<NodeId>,
+    labels: Vec<usize>,
+    work: usize,
+}
+
+impl<'a, F> Pool<'a, F>
+where
+    F: FlowNum + Send + Sync,
+{
+    fn new(
+        rn: &'a mut ResidualNetwork<F>,
+        num_threads: usize,
+        active_nodes: &[NodeId],
+        source: NodeId,
+        sink: NodeId,
+        limited: bool,
+    ) -> Self {
+        let bounds: Vec<usize> = (0..=num_threads).map(|i| i * rn.num_nodes / num_threads).collect();
+        let arc_bounds: Vec<usize> = bounds.iter().map(|&b| rn.start[b]).collect();
+        let mut segments: Vec<_> = (0..num_threads)
+            .map(|t| Segment {
+                residual_capacities: rn.residual_capacities[arc_bounds[t]..arc_bounds[t + 1]].to_vec(),
+                excesses: rn.excesses[bounds[t]..bounds[t + 1]].to_vec(),
+                marked: vec![false; bounds[t + 1] - bounds[t]],
+                active_nodes: Vec::new(),
+                activated: Vec::new(),
+                relabels: Vec::new(),
+                labels: Vec::new(),
+                work: 0,
+            })
+            .collect();
+        let owner = |u: NodeId| bounds.partition_point(|&b| b <= u.index()) - 1;
+        for &u in active_nodes {
+            segments[owner(u)].active_nodes.push(u);
+        }
+
+        Self {
+            rn: RwLock::new(rn),
+            segments: segments.into_iter().map(Mutex::new).collect(),
+            outboxes: (0..num_threads)
+                .map(|_| (0..num_threads).map(|_| Mutex::new(Vec::new())).collect())
+                .collect(),
+            barrier: Barrier::new(num_threads),
+            done: AtomicBool::new(false),
+            bounds,
+            arc_bounds,
+            source,
+            sink,
+            limited,
+        }
+    }
+
+    fn owner(&self, u: NodeId) -> usize {
+        self.bounds.partition_point(|&b| b <= u.index()) - 1
+    }
+
+    // runs the part of thread t in one round, false once thread 0 has found no active node
+    fn round(&self, t: usize) -> bool {
+        self.barrier.wait();
+        if self.done.load(Ordering::Relaxed) {
+            return false;
+        }
+        self.discharge(t);
+        self.barrier.wait();
+        self.apply(t);
+        self.barrier.wait();
+        self.relabel(t);
+        self.barrier.wait();
+        self.activate(t);
+        self.barrier.wait();
+        true
+    }
+
+    // 1. pushes the excess of each active node along admissible arcs, sending the updates to the owners
+    fn discharge(&self, t: usize) {
+        let rn = self.rn.read().unwrap();
+        let mut segment = self.segments[t].lock().unwrap();
+        let segment = &mut *segment;
+        let mut outboxes: Vec<_> = self.outboxes[t].iter().map(|outbox| outbox.lock().unwrap()).collect();
+        let (node_offset, arc_offset) = (self.bounds[t], self.arc_bounds[t]);
+
+        segment.relabels.clear();
+        for &u in segment.active_nodes.iter() {
+            let mut excess = segment.excesses[u.index() - node_offset];
+            for arc_id in rn.neighbors(u) {
+                if excess == F::zero() {
+                    break;
+                }
+                let (to, residual_capacity) =
+                    (rn.to[arc_id.index()], segment.residual_capacities[arc_id.index() - arc_offset]);
+                if residual_capacity == F::zero()
+                    || rn.distances_to_sink[u.index()] != rn.distances_to_sink[to.index()] + 1
+                {
+                    continue;
+                }
+
+                let delta = excess.min(residual_capacity);
+                excess -= delta;
+                outboxes[t].push(Update { node: u, arc_id, delta, is_push: true });
+                outboxes[self.owner(to)].push(Update {
+                    node: to,
+                    arc_id: rn.rev[arc_id.index()],
+                    delta,
+                    is_push: false,
+                });
+            }
+            if excess > F::zero() {
+                segment.relabels.push(u);
+            }
+        }
+    }
+
+    // 2. applies the updates of every thread to the nodes of thread t
+    fn apply(&self, t: usize) {
+        let mut segment = self.segments[t].lock().unwrap();
+        let segment = &mut *segment;
+        let (node_offset, arc_offset) = (self.bounds[t], self.arc_bounds[t]);
+        for outboxes in self.outboxes.iter() {
+            for update in outboxes[t].lock().unwrap().drain(..) {
+                let (u, a) = (update.node.index() - node_offset, update.arc_id.index() - arc_offset);
+                if update.is_push {
+                    segment.residual_capacities[a] -= update.delta;
+                    segment.excesses[u] -= update.delta;
+                } else {
+                    segment.residual_capacities[a] += update.delta;
+                    segment.excesses[u] += update.delta;
+                    if !segment.marked[u] {
+                        segment.marked[u] = true;
+                        segment.activated.push(update.node);
+                    }
+                }
+            }
+        }
+    }
+
+    // 3. computes the new labels of the nodes with remaining excess from the labels of the previous round
+    fn relabel(&self, t: usize) {
+        let rn = self.rn.read().unwrap();
+        let mut segment = self.segments[t].lock().unwrap();
+        let segment = &mut *segment;
+        let arc_offset = self.arc_bounds[t];
+        segment.labels = segment
+            .relabels
+            .iter()
+            .map(|&u| {
+                rn.neighbors(u)
+                    .filter(|&arc_id| segment.residual_capacities[arc_id.index() - arc_offset] > F::zero())
+                    .map(|arc_id| rn.distances_to_sink[rn.to[arc_id.index()].index()] + 1)
+                    .min()
+                    .unwrap_or(rn.num_nodes)
+                    .min(rn.num_nodes)
+            })
+            .collect();
+    }
+
+    // 4. stores the new labels and collects the active nodes of the next round
+    fn activate(&self, t: usize) {
+        let mut segment = self.segments[t].lock().unwrap();
+        let segment = &mut *segment;
+        let node_offset = self.bounds[t];
+        {
+            let mut rn = self.rn.write().unwrap();
+            for (&u, &label) in segment.relabels.iter().zip(segment.labels.iter()) {
+                segment.work += rn.start[u.index() + 1] - rn.start[u.index()];
+                rn.distances_to_sink[u.index()] = label;
+            }
+        }
+
+        // nodes that received flow are marked in step 2
+        for &u in segment.relabels.iter() {
+            if !segment.marked[u.index() - node_offset] {
+                segment.marked[u.index() - node_offset] = true;
+                segment.activated.push(u);
+            }
+        }
+
+        let rn = self.rn.read().unwrap();
+        segment.active_nodes.clear();
+        for u in std::mem::take(&mut segment.activated) {
+            segment.marked[u.index() - node_offset] = false;
+            if (u != self.source || self.limited)
+                && u != self.sink
+                && segment.excesses[u.index() - node_offset] > F::zero()
+                && rn.distances_to_sink[u.index()] < rn.num_nodes
+            {
+                segment.active_nodes.push(u);
+            }
+        }
+    }
+
+    fn num_active_nodes(&self) -> usize {
+        self.segments
+            .iter()
+            .map(|segment| segment.lock().unwrap().active_nodes.len())
+            .sum()
+    }
+
+    fn take_work(&self) -> usize {
+        self.segments
+            .iter()
+            .map(|segment| std::mem::take(&mut segment.lock().unwrap().work))
+            .sum()
+    }
+
+    fn global_relabel(&self) {
+        let mut rn = self.rn.write().unwrap();
+        self.gather(&mut rn);
+        update_distances(&mut rn, self.source, self.sink, self.limited);
+        for segment in self.segments.iter() {
+            segment
+                .lock()
+                .unwrap()
+                .active_nodes
+                .retain(|&u| rn.distances_to_sink[u.index()] < rn.num_nodes);
+        }
+    }
+
+    // hands the residual capacities and excesses back to the residual network
+    fn finish(self) {
+        self.gather(&mut self.rn.write().unwrap());
+    }
+
+    fn gather(&self, rn: &mut ResidualNetwork<F>) {
+        for (t, segment) in self.segments.iter().enumerate() {
+            let segment = segment.lock().unwrap();
+            rn.residual_capacities[self.arc_bounds[t]..self.arc_bounds[t + 1]]
+                .copy_from_slice(&segment.residual_capacities);
+            rn.excesses[self.bounds[t]..self.bounds[t + 1]].copy_from_slice(&segment.excesses);
+        }
+    }
+}
+
+fn update_distances<F: FlowNum>(rn: &mut ResidualNetwork<F>, source: NodeId, sink: NodeId, limited: bool) {
+    if limited {
+        rn.update_distances_to_sink_through_source(sink);
+    } else {
+        rn.update_distances_to_sink(source, sink);
+        rn.distances_to_sink[source.index()] = rn.num_nodes;
+    }
+}
+
+impl_maximum_flow_solver!(ParallelPushRelabel, run, Send, Sync);
@@ -0,0 +1,334 @@
+use crate::{
+    algorithms::maximum_flow::{
+        edge::MaximumFlowEdge,
+        error::MaximumFlowError,
+        minimum_cut_dag::MinimumCutDag,
+        residual_network::ResidualNetwork,
+        solvers::{macros::impl_maximum_flow_solver, solver::MaximumFlowSolver},
+        status::Status,
+        validate::validate_input,
+    },
+    core::numeric::FlowNum,
+    graph::{
+        direction::Direction,
+        edge::Edge,
+        graph::Graph,
+        ids::{ArcId, EdgeId, INVALID_ARC_ID, INVALID_NODE_ID, NodeId},
+    },
+};
+use std::collections::VecDeque;
+
+// Hochbaum's pseudoflow algorithm (highest label variant)
+// phase 1 computes a minimum cut with a normalized tree, phase 2 recovers a maximum flow from the pseudoflow
+pub struct Pseudoflow<F> {
+    status: Status,
+    source: Option<NodeId>,
+    sink: Option<NodeId>,
+
+    rn: ResidualNetwork<F>,
+    value_only: bool,
+
+    labels: Box<[usize]>,
+    label_count: Box<[usize]>,
+    current_arc: Box<[usize]>,
+
+    // normalized tree: only roots have positive excess
+    parent: Box<[NodeId]>,
+    parent_arc: Box<[ArcId]>, // arc from the node to its parent
+    first_child: Box<[NodeId]>,
+    next_sibling: Box<[NodeId]>,
+    prev_sibling: Box<[NodeId]>,
+    next_scan: Box<[NodeId]>,
+
+    strong_roots: Box<[VecDeque<NodeId>]>, // strong_roots[i] = roots with positive excess and label i
+    highest_strong_label: usize,
+}
+
+impl<F> Pseudoflow<F>
+where
+    F: FlowNum,
+{
+    fn new<D: Direction, N>(graph: &Graph<D, N, MaximumFlowEdge<F>>) -> Self {
+        let rn = ResidualNetwork::from(graph, |e| e.data.upper);
+        Self::new_with_residual_network(rn)
+    }
+
+    pub fn new_with<D: Direction, N, E, UF>(graph: &Graph<D, N, E>, upper_fn: UF) -> Self
+    where
+        UF: Fn(&Edge<E>) -> F,
+    {
+        let rn = ResidualNetwork::from(graph, upper_fn);
+        Self::new_with_residual_network(rn)
+    }
+
+    fn new_with_residual_network(rn: ResidualNetwork<F>) -> Self {
+        let num_nodes = rn.num_nodes;
+        Self {
+            status: Status::NotSolved,
+            source: None,
+            sink: None,
+            rn,
+            value_only: false,
+
+            labels: vec![0; num_nodes].into_boxed_slice(),
+            label_count: vec![0; num_nodes + 1].into_boxed_slice(),
+            current_arc: vec![0; num_nodes].into_boxed_slice(),
+
+            parent: vec![INVALID_NODE_ID; num_nodes].into_boxed_slice(),
+            parent_arc: vec![INVALID_ARC_ID; num_nodes].into_boxed_slice(),
+            first_child: vec![INVALID_NODE_ID; num_nodes].into_boxed_slice(),
+            next_sibling: vec![INVALID_NODE_ID; num_nodes].into_boxed_slice(),
+            prev_sibling: vec![INVALID_NODE_ID; num_nodes].into_boxed_slice(),
+            next_scan: vec![INVALID_NODE_ID; num_nodes].into_boxed_slice(),
+
+            strong_roots: vec![VecDeque::new(); num_nodes + 1].into_boxed_slice(),
+            highest_strong_label: 0,
+        }
+    }
+
+    // stops after phase 1: the objective value and the minimum cut are available, but flows are a preflow
+    pub fn set_value_only(mut self, value_only: bool) -> Self {
+        self.value_only = value_only;
+        self
+    }
+
+    fn run(&mut self, source: NodeId, sink: NodeId, limit: Option<F>) -> Result<F, MaximumFlowError> {
+        validate_input(&self.rn, source, sink)?;
+
+        self.source = Some(source);
+        self.sink = Some(sink);
+        self.pre_process(source, sink, limit);
+
+        // phase 1
+        while let Some(root) = self.highest_strong_root() {
+            self.process_root(root, sink);
+        }
+
+        // phase 2
+        if !self.value_only {
+            self.rn.push_flow_excess_back_to_source(source, sink);
+        }
+
+        self.status = Status::Optimal;
+        Ok(self.rn.excesses[sink.index()])
+    }
+
+    fn pre_process(&mut self, source: NodeId, sink: NodeId, limit: Option<F>) {
+        let num_nodes = self.rn.num_nodes;
+        self.rn.residual_capacities.copy_from_slice(&self.rn.upper);
+        self.rn.excesses.fill(F::zero());
+        self.labels.fill(0);
+        self.label_count.fill(0);
+        self.parent.fill(INVALID_NODE_ID);
+        self.parent_arc.fill(INVALID_ARC_ID);
+        self.first_child.fill(INVALID_NODE_ID);
+        self.next_sibling.fill(INVALID_NODE_ID);
+        self.prev_sibling.fill(INVALID_NODE_ID);
+        self.strong_roots.iter_mut().for_each(|roots| roots.clear());
+        self.current_arc.copy_from_slice(&self.rn.start[..num_nodes]);
+
+        // saturate all source arcs; every other node starts as a singleton tree
+        // with a flow limit, the source instead starts as a strong root holding the limit as its excess
+        match limit {
+            Some(limit) => self.rn.excesses[source.index()] = limit,
+            None => {
+                for arc_id in self.rn.neighbors(source) {
+                    let delta = self.rn.residual_capacity(arc_id);
+                    self.rn.push_flow_without_excess(source, arc_id, delta);
+                    self.rn.excesses[self.rn.to[arc_id.index()].index()] += delta;
+                }
+            }
+        }
+
+        for u in (0..num_nodes).map(NodeId) {
+            if u == source && limit.is_none() {
+                self.labels[u.index()] = num_nodes;
+                continue;
+            }
+            if u != sink && self.rn.excesses[u.index()] > F::zero() {
+                self.labels[u.index()] = 1;
+                self.strong_roots[1].push_back(u);
+            }
+            self.label_count[self.labels[u.index()]] += 1;
+        }
+        self.highest_strong_label = 1;
+    }
+
+    fn highest_strong_root(&mut self) -> Option<NodeId> {
+        let num_nodes = self.rn.num_nodes;
+        for label in (1..=self.highest_strong_label).rev() {
+            if self.strong_roots[label].is_empty() {
+                continue;
+            }
+            self.highest_strong_label = label;
+            if label < num_nodes && self.label_count[label - 1] > 0 {
+                let root = self.strong_roots[label].pop_front().unwrap();
+                self.current_arc[root.index()] = self.rn.start[root.index()];
+                return Some(root);
+            }
+
+            // gap: the strong trees with this label can never reach the sink
+            while let Some(root) = self.strong_roots[label].pop_front() {
+                self.lift_all(root);
+            }
+        }
+
+        if self.strong_roots[0].is_empty() {
+            return None;
+        }
+        while let Some(root) = self.strong_roots[0].pop_front() {
+            self.label_count[0] -= 1;
+            self.labels[root.index()] = 1;
+            self.label_count[1] += 1;
+            self.strong_roots[1].push_back(root);
+        }
+        self.highest_strong_label = 1;
+
+        let root = self.strong_roots[1].pop_front().unwrap();
+        self.current_arc[root.index()] = self.rn.start[root.index()];
+        Some(root)
+    }
+
+    // searches the subtree of nodes with the same label as the root for a merger arc
+    fn process_root(&mut self, root: NodeId, sink: NodeId) {
+        let mut u = root;
+        self.next_scan[root.index()] = self.first_child[root.index()];
+        if let Some(arc_id) = self.find_weak_node(root) {
+            self.merge(root, arc_id);
+            self.push_excess(root, sink);
+            return;
+        }
+        self.check_children(root);
+
+        loop {
+            while self.next_scan[u.index()] != INVALID_NODE_ID {
+                let child = self.next_scan[u.index()];
+                self.next_scan[u.index()] = self.next_sibling[child.index()];
+                u = child;
+                self.next_scan[u.index()] = self.first_child[u.index()];
+
+                if let Some(arc_id) = self.find_weak_node(u) {
+                    self.merge(u, arc_id);
+                    self.push_excess(root, sink);
+                    return;
+                }
+                self.check_children(u);
+            }
+            if u == root {
+                break;
+            }
+            u = self.parent[u.index()];
+            self.check_children(u);
+        }
+
+        self.strong_roots[self.labels[root.index()]].push_back(root);
+        self.highest_strong_label += 1;
+    }
+
+    fn find_weak_node(&mut self, u: NodeId) -> Option<ArcId> {
+        let label = self.highest_strong_label - 1;
+        for arc_id in (self.current_arc[u.index()]..self.rn.start[u.index() + 1]).map(ArcId) {
+            self.current_arc[u.index()] = arc_id.index();
+            if self.rn.residual_capacity(arc_id) > F::zero() && self.labels[self.rn.to[arc_id.index()].index()] == label
+            {
+                return Some(arc_id);
+            }
+        }
+        self.current_arc[u.index()] = self.rn.start[u.index() + 1];
+        None
+    }
+
+    // relabels u if none of its unscanned children has the same label
+    fn check_children(&mut self, u: NodeId) {
+        while self.next_scan[u.index()] != INVALID_NODE_ID {
+            if self.labels[self.next_scan[u.index()].index()] == self.labels[u.index()] {
+                return;
+            }
+            self.next_scan[u.index()] = self.next_sibling[self.next_scan[u.index()].index()];
+        }
+
+        self.label_count[self.labels[u.index()]] -= 1;
+        self.labels[u.index()] += 1;
+        self.label_count[self.labels[u.index()]] += 1;
+        self.current_arc[u.index()] = self.rn.start[u.index()];
+    }
+
+    // makes u the root of its strong tree and hangs it below the weak node at the head of arc_id
+    fn merge(&mut self, u: NodeId, arc_id: ArcId) {
+        let (mut current, mut new_parent, mut new_arc) = (u, self.rn.to[arc_id.index()], arc_id);
+        while self.parent[current.index()] != INVALID_NODE_ID {
+            let (old_parent, old_arc) = (self.parent[current.index()], self.parent_arc[current.index()]);
+            self.cut(current);
+            self.link(current, new_parent, new_arc);
+            (current, new_parent, new_arc) = (old_parent, current, self.rn.rev[old_arc.index()]);
+        }
+        self.link(current, new_parent, new_arc);
+    }
+
+    // pushes the excess of u towards the root, splitting off the subtrees below saturated arcs
+    fn push_excess(&mut self, u: NodeId, sink: NodeId) {
+        let mut current = u;
+        let mut previous_excess = F::zero();
+        while self.parent[current.index()] != INVALID_NODE_ID && self.rn.excesses[current.index()] > F::zero() {
+            let (parent, arc_id) = (self.parent[current.index()], self.parent_arc[current.index()]);
+            previous_excess = self.rn.excesses[parent.index()];
+
+            let delta = self.rn.excesses[current.index()].min(self.rn.residual_capacity(arc_id));
+            self.rn.push_flow(current, arc_id, delta);
+            if self.rn.excesses[current.index()] > F::zero() {
+                self.cut(current);
+                self.strong_roots[self.labels[current.index()]].push_back(current);
+            }
+            current = parent;
+        }
+
+        if self.parent[current.index()] == INVALID_NODE_ID
+            && current != sink
+            && self.rn.excesses[current.index()] > F::zero()
+            && previous_excess == F::zero()
+        {
+            self.strong_roots[self.labels[current.index()]].push_back(current);
+        }
+    }
+
+    fn lift_all(&mut self, root: NodeId) {
+        let mut stack = vec![root];
+        while let Some(u) = stack.pop() {
+            self.label_count[self.labels[u.index()]] -= 1;
+            self.labels[u.index()] = self.rn.num_nodes;
+
+            let mut child = self.first_child[u.index()];
+            while child != INVALID_NODE_ID {
+                stack.push(child);
+                child = self.next_sibling[child.index()];
+            }
+        }
+    }
+
+    fn link(&mut self, u: NodeId, parent: NodeId, arc_id: ArcId) {
+        self.parent[u.index()] = parent;
+        self.parent_arc[u.index()] = arc_id;
+        self.prev_sibling[u.index()] = INVALID_NODE_ID;
+        self.next_sibling[u.index()] = self.first_child[parent.index()];
+        if self.first_child[parent.index()] != INVALID_NODE_ID {
+            self.prev_sibling[self.first_child[parent.index()].index()] = u;
+        }
+        self.first_child[parent.index()] = u;
+    }
+
+    fn cut(&mut self, u: NodeId) {
+        let (prev, next) = (self.prev_sibling[u.index()], self.next_sibling[u.index()]);
+        if prev == INVALID_NODE_ID {
+            self.first_child[self.parent[u.index()].index()] = next;
+        } else {
+            self.next_sibling[prev.index()] = next;
+        }
+        if next != INVALID_NODE_ID {
+            self.prev_sibling[next.index()] = prev;
+        }
+        self.parent[u.index()] = INVALID_NODE_ID;
+        self.parent_arc[u.index()] = INVALID_ARC_ID;
+    }
+}
+
+impl_maximum_flow_solver!(Pseudoflow, run);
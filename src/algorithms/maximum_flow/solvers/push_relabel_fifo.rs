@@ -3,6 +3,7 @@ use crate::{
     algorithms::maximum_flow::{
         edge::MaximumFlowEdge,
         error::MaximumFlowError,
+        minimum_cut_dag::MinimumCutDag,
         residual_network::ResidualNetwork,
         solvers::{macros::impl_maximum_flow_solver, solver::MaximumFlowSolver},
         status::Status,
@@ -10,7 +11,7 @@ use crate::{
     },
     core::numeric::FlowNum,
     graph::{
-        direction::Directed,
+        direction::Direction,
         graph::Graph,
         ids::{ArcId, EdgeId, NodeId},
     },
@@ -20,9 +21,11 @@ use std::collections::VecDeque;
 pub struct PushRelabelFifo<F> {
     status: Status,
     source: Option<NodeId>,
+    sink: Option<NodeId>,
 
     rn: ResidualNetwork<F>,
     global_relabel_freq: f64,
+    gap_relabeling: bool,
     value_only: bool,
     threshold: usize,
     work: usize,
@@ -35,12 +38,12 @@ impl<F> PushRelabelFifo<F>
 where
     F: FlowNum,
 {
-    fn new<N>(graph: &Graph<Directed, N, MaximumFlowEdge<F>>) -> Self {
+    fn new<D: Direction, N>(graph: &Graph<D, N, MaximumFlowEdge<F>>) -> Self {
         let rn = ResidualNetwork::from(graph, |e| e.data.upper);
         Self::new_with_residual_network(rn)
     }
 
-    pub fn new_with<N, E, UF>(graph: &Graph<Directed, N, E>, upper_fn: UF) -> Self
+    pub fn new_with<D: Direction, N, E, UF>(graph: &Graph<D, N, E>, upper_fn: UF) -> Self
     where
         UF: Fn(&Edge<E>) -> F,
     {
@@ -54,8 +57,10 @@ where
         Self {
             status: Status::NotSolved,
             source: None,
+            sink: None,
             rn,
             global_relabel_freq: 1.0,
+            gap_relabeling: true,
             value_only: false,
             threshold: 0,
             work: 0,
@@ -65,35 +70,46 @@ where
         }
     }
 
-    fn run(&mut self, source: NodeId, sink: NodeId) -> Result<F, MaximumFlowError> {
+    fn run(&mut self, source: NodeId, sink: NodeId, limit: Option<F>) -> Result<F, MaximumFlowError> {
         validate_input(&self.rn, source, sink)?;
 
         // initialize
         self.source = Some(source);
+        self.sink = Some(sink);
         self.rn.residual_capacities.copy_from_slice(&self.rn.upper);
         self.rn.excesses.fill(F::zero());
 
-        let residual = self
-            .rn
-            .neighbors(source)
-            .fold(F::zero(), |sum, arc_id| sum + self.rn.upper[arc_id.index()]);
+        let residual = limit.unwrap_or_else(|| {
+            self.rn
+                .neighbors(source)
+                .fold(F::zero(), |sum, arc_id| sum + self.rn.upper[arc_id.index()])
+        });
 
         self.rn.excesses[source.index()] = residual;
 
-        self.pre_process(source, sink);
+        self.pre_process(source, sink, limit.is_some());
         while let Some(u) = self.active_nodes.pop_front() {
             // no path to sink
-            if u == source || u == sink || self.rn.distances_to_sink[u.index()] >= self.rn.num_nodes {
+            if (u == source && limit.is_none())
+                || u == sink
+                || self.rn.distances_to_sink[u.index()] >= self.rn.num_nodes
+            {
                 continue;
             }
             self.discharge(u);
 
             if self.work > self.threshold {
                 self.work = 0;
-                self.rn.update_distances_to_sink(source, sink);
+                if limit.is_some() {
+                    self.rn.update_distances_to_sink_through_source(sink);
+                } else {
+                    self.rn.update_distances_to_sink(source, sink);
+                }
+                // the new distances can make arcs before the current edge admissible again
                 self.distance_count.fill(0);
                 for u in 0..self.rn.num_nodes {
                     self.distance_count[self.rn.distances_to_sink[u]] += 1;
+                    self.current_edge[u] = self.rn.start[u];
                 }
             }
         }
@@ -106,6 +122,7 @@ where
         Ok(self.rn.excesses[sink.index()])
     }
 
+    // stops after phase 1: the objective value and the minimum cut are available, but flows are a preflow
     pub fn set_value_only(mut self, value_only: bool) -> Self {
         self.value_only = value_only;
         self
@@ -116,27 +133,39 @@ where
         self
     }
 
-    fn pre_process(&mut self, source: NodeId, sink: NodeId) {
+    pub fn set_gap_relabeling(mut self, gap_relabeling: bool) -> Self {
+        self.gap_relabeling = gap_relabeling;
+        self
+    }
+
+    // with a flow limit, the source keeps the limit as its excess instead of saturating its arcs
+    fn pre_process(&mut self, source: NodeId, sink: NodeId, limited: bool) {
         self.current_edge.fill(0);
         self.distance_count.fill(0);
 
-        self.rn.update_distances_to_sink(source, sink);
-        self.rn.distances_to_sink[source.index()] = self.rn.num_nodes;
+        if limited {
+            self.rn.update_distances_to_sink_through_source(sink);
+        } else {
+            self.rn.update_distances_to_sink(source, sink);
+            self.rn.distances_to_sink[source.index()] = self.rn.num_nodes;
+        }
 
         for u in 0..self.rn.num_nodes {
             self.distance_count[self.rn.distances_to_sink[u]] += 1;
             self.current_edge[u] = self.rn.start[u];
         }
 
-        for arc_id in self.rn.neighbors(source) {
-            let delta = self.rn.residual_capacities[arc_id.index()];
-            self.rn.push_flow_without_excess(source, arc_id, delta);
-            self.rn.excesses[self.rn.to[arc_id.index()].index()] += delta;
+        if !limited {
+            for arc_id in self.rn.neighbors(source) {
+                let delta = self.rn.residual_capacities[arc_id.index()];
+                self.rn.push_flow_without_excess(source, arc_id, delta);
+                self.rn.excesses[self.rn.to[arc_id.index()].index()] += delta;
+            }
         }
 
         for u in 0..self.rn.num_nodes {
             let u = NodeId(u);
-            if u != source && u != sink && self.rn.excesses[u.index()] > F::zero() {
+            if (u != source || limited) && u != sink && self.rn.excesses[u.index()] > F::zero() {
                 self.active_nodes.push_back(u);
             }
         }
@@ -165,7 +194,7 @@ where
         self.current_edge[u.index()] = self.rn.start[u.index()];
 
         // relabel
-        if self.distance_count[self.rn.distances_to_sink[u.index()]] == 1 {
+        if self.gap_relabeling && self.distance_count[self.rn.distances_to_sink[u.index()]] == 1 {
             self.gap_relabeling(self.rn.distances_to_sink[u.index()]);
         } else {
             self.relabel(u);
@@ -198,7 +227,7 @@ where
             .filter(|&arc_id| self.rn.residual_capacities[arc_id.index()] > F::zero())
             .map(|arc_id| self.rn.distances_to_sink[self.rn.to[arc_id.index()].index()] + 1)
             .min()
-            .expect("relabel: no outgoing residual arc found")
+            .unwrap_or(self.rn.num_nodes)
             .min(self.rn.num_nodes);
 
         self.rn.distances_to_sink[u.index()] = new_distance;
@@ -255,7 +284,7 @@ where
 
             let delta = self.dfs(to, source, flow.min(residual_capacity), visited);
             if delta > F::zero() {
-                self.rn.push_flow(u, i, delta);
+                self.rn.push_flow_without_excess(u, i, delta);
                 return delta;
             }
         }
@@ -3,6 +3,7 @@ use crate::{
     algorithms::maximum_flow::{
         edge::MaximumFlowEdge,
         error::MaximumFlowError,
+        minimum_cut_dag::MinimumCutDag,
         residual_network::ResidualNetwork,
         solvers::{macros::impl_maximum_flow_solver, solver::MaximumFlowSolver},
         status::Status,
@@ -10,21 +11,25 @@ use crate::{
     },
     core::numeric::FlowNum,
     graph::{
-        direction::Directed,
+        direction::Direction,
         graph::Graph,
         ids::{ArcId, EdgeId, NodeId},
     },
 };
 
+#[derive(Clone)]
 pub struct PushRelabelHighestLabel<F> {
     status: Status,
     source: Option<NodeId>,
+    sink: Option<NodeId>,
 
     rn: ResidualNetwork<F>,
     current_arc: Vec<usize>,
 
     global_relabel_freq: f64,
+    gap_relabeling: bool,
     value_only: bool,
+    limited: bool, // the source holds the flow limit as its excess and is discharged like the other nodes
     threshold: usize,
     work: usize,
 
@@ -39,12 +44,12 @@ impl<F> PushRelabelHighestLabel<F>
 where
     F: FlowNum,
 {
-    fn new<N>(graph: &Graph<Directed, N, MaximumFlowEdge<F>>) -> Self {
+    fn new<D: Direction, N>(graph: &Graph<D, N, MaximumFlowEdge<F>>) -> Self {
         let rn = ResidualNetwork::from(graph, |e| e.data.upper);
         Self::new_with_residual_network(rn)
     }
 
-    pub fn new_with<N, E, UF>(graph: &Graph<Directed, N, E>, upper_fn: UF) -> Self
+    pub fn new_with<D: Direction, N, E, UF>(graph: &Graph<D, N, E>, upper_fn: UF) -> Self
     where
         UF: Fn(&Edge<E>) -> F,
     {
@@ -57,11 +62,14 @@ where
         Self {
             status: Status::NotSolved,
             source: None,
+            sink: None,
             rn,
             current_arc: Vec::new(),
 
             global_relabel_freq: 1.0,
+            gap_relabeling: true,
             value_only: false,
+            limited: false,
             threshold: 0,
             work: 0,
 
@@ -73,6 +81,7 @@ where
         }
     }
 
+    // stops after phase 1: the objective value and the minimum cut are available, but flows are a preflow
     pub fn set_value_only(mut self, value_only: bool) -> Self {
         self.value_only = value_only;
         self
@@ -83,11 +92,45 @@ where
         self
     }
 
-    fn run(&mut self, source: NodeId, sink: NodeId) -> Result<F, MaximumFlowError> {
+    pub fn set_gap_relabeling(mut self, gap_relabeling: bool) -> Self {
+        self.gap_relabeling = gap_relabeling;
+        self
+    }
+
+    fn run(&mut self, source: NodeId, sink: NodeId, limit: Option<F>) -> Result<F, MaximumFlowError> {
         validate_input(&self.rn, source, sink)?;
 
         self.source = Some(source);
-        self.pre_process(source, sink);
+        self.sink = Some(sink);
+        self.limited = limit.is_some();
+        self.pre_process(source, sink, limit);
+        self.discharge_active_nodes(source, sink);
+
+        if !self.value_only {
+            self.push_flow_excess_back_to_source(source, sink);
+        }
+
+        self.status = Status::Optimal;
+        Ok(self.rn.excesses[sink.index()])
+    }
+
+    // continues phase 1 from the current preflow and distance labels after capacities of arcs leaving the source
+    // increased or capacities of arcs entering the sink decreased (Gallo–Grigoriadis–Tarjan)
+    pub(crate) fn resume(&mut self, source: NodeId, sink: NodeId) -> F {
+        for u in (0..self.rn.num_nodes).map(NodeId) {
+            if u != source && u != sink {
+                self.enqueue(u);
+            }
+        }
+        self.discharge_active_nodes(source, sink);
+        self.rn.excesses[sink.index()]
+    }
+
+    pub(crate) fn residual_network_mut(&mut self) -> &mut ResidualNetwork<F> {
+        &mut self.rn
+    }
+
+    fn discharge_active_nodes(&mut self, source: NodeId, sink: NodeId) {
         loop {
             if self.buckets[self.bucket_idx].is_empty() {
                 if self.bucket_idx == 0 {
@@ -103,45 +146,42 @@ where
 
             if self.work > self.threshold {
                 self.work = 0;
-                self.rn.update_distances_to_sink(source, sink);
+                self.global_relabel(source, sink);
                 self.distance_count.fill(0);
                 for u in 0..self.rn.num_nodes {
                     self.distance_count[self.rn.distances_to_sink[u]] += 1;
                 }
             }
         }
-
-        if !self.value_only {
-            self.push_flow_excess_back_to_source(source, sink);
-        }
-
-        self.status = Status::Optimal;
-        Ok(self.rn.excesses[sink.index()])
     }
 
-    fn pre_process(&mut self, source: NodeId, sink: NodeId) {
+    fn pre_process(&mut self, source: NodeId, sink: NodeId, limit: Option<F>) {
+        self.rn.residual_capacities.copy_from_slice(&self.rn.upper);
         self.rn.excesses.fill(F::zero());
         self.current_arc = vec![0; self.rn.num_nodes];
         self.buckets.fill(Vec::new());
         self.in_bucket.fill(false);
         self.distance_count = vec![0; self.rn.num_nodes + 1];
-
-        self.rn.update_distances_to_sink(source, sink);
-        self.rn.distances_to_sink[source.index()] = self.rn.num_nodes;
+        self.global_relabel(source, sink);
 
         for u in 0..self.rn.num_nodes {
             self.distance_count[self.rn.distances_to_sink[u]] += 1;
             self.current_arc[u] = self.rn.start[u];
         }
 
-        for arc_id in self.rn.neighbors(source) {
-            let delta = self.rn.residual_capacity(arc_id);
-            self.rn.push_flow_without_excess(source, arc_id, delta);
-            self.rn.excesses[self.rn.to[arc_id.index()].index()] += delta;
+        match limit {
+            Some(limit) => self.rn.excesses[source.index()] = limit,
+            None => {
+                for arc_id in self.rn.neighbors(source) {
+                    let delta = self.rn.residual_capacity(arc_id);
+                    self.rn.push_flow_without_excess(source, arc_id, delta);
+                    self.rn.excesses[self.rn.to[arc_id.index()].index()] += delta;
+                }
+            }
         }
 
         for u in (0..self.rn.num_nodes).map(NodeId) {
-            if u != source && u != sink && self.rn.excesses[u.index()] > F::zero() {
+            if (u != source || self.limited) && u != sink && self.rn.excesses[u.index()] > F::zero() {
                 self.enqueue(u);
             }
         }
@@ -155,6 +195,15 @@ where
         };
     }
 
+    fn global_relabel(&mut self, source: NodeId, sink: NodeId) {
+        if self.limited {
+            self.rn.update_distances_to_sink_through_source(sink);
+        } else {
+            self.rn.update_distances_to_sink(source, sink);
+            self.rn.distances_to_sink[source.index()] = self.rn.num_nodes;
+        }
+    }
+
     fn enqueue(&mut self, u: NodeId) {
         if self.in_bucket[u.index()]
             || self.rn.excesses[u.index()] <= F::zero()
@@ -183,7 +232,7 @@ where
         }
 
         // relabel
-        if self.distance_count[self.rn.distances_to_sink[u.index()]] == 1 {
+        if self.gap_relabeling && self.distance_count[self.rn.distances_to_sink[u.index()]] == 1 {
             self.gap_relabeling(self.rn.distances_to_sink[u.index()]);
         } else {
             self.relabel(u);
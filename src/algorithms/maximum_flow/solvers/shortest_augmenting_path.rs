@@ -3,6 +3,7 @@ use crate::{
     algorithms::maximum_flow::{
         edge::MaximumFlowEdge,
         error::MaximumFlowError,
+        minimum_cut_dag::MinimumCutDag,
         residual_network::ResidualNetwork,
         solvers::{macros::impl_maximum_flow_solver, solver::MaximumFlowSolver},
         status::Status,
@@ -10,7 +11,7 @@ use crate::{
     },
     core::numeric::FlowNum,
     graph::{
-        direction::Directed,
+        direction::Direction,
         graph::Graph,
         ids::{ArcId, EdgeId, NodeId},
     },
@@ -19,6 +20,7 @@ use crate::{
 pub struct ShortestAugmentingPath<F> {
     status: Status,
     source: Option<NodeId>,
+    sink: Option<NodeId>,
 
     rn: ResidualNetwork<F>,
     current_edge: Box<[usize]>,
@@ -29,12 +31,12 @@ impl<F> ShortestAugmentingPath<F>
 where
     F: FlowNum,
 {
-    fn new<N>(graph: &Graph<Directed, N, MaximumFlowEdge<F>>) -> Self {
+    fn new<D: Direction, N>(graph: &Graph<D, N, MaximumFlowEdge<F>>) -> Self {
         let rn = ResidualNetwork::from(graph, |e| e.data.upper);
         Self::new_with_residual_network(rn)
     }
 
-    pub fn new_with<N, E, UF>(graph: &Graph<Directed, N, E>, upper_fn: UF) -> Self
+    pub fn new_with<D: Direction, N, E, UF>(graph: &Graph<D, N, E>, upper_fn: UF) -> Self
     where
         UF: Fn(&Edge<E>) -> F,
     {
@@ -48,25 +50,28 @@ where
         Self {
             status: Status::NotSolved,
             source: None,
+            sink: None,
             rn,
             current_edge: vec![0_usize; num_nodes].into_boxed_slice(),
             cutoff: None,
         }
     }
 
-    fn run(&mut self, source: NodeId, sink: NodeId) -> Result<F, MaximumFlowError> {
+    fn run(&mut self, source: NodeId, sink: NodeId, limit: Option<F>) -> Result<F, MaximumFlowError> {
         validate_input(&self.rn, source, sink)?;
 
         self.source = Some(source);
+        self.sink = Some(sink);
+        self.rn.residual_capacities.copy_from_slice(&self.rn.upper);
         self.rn.update_distances_to_sink(source, sink);
 
         let mut flow = F::zero();
-        let mut residual = self.cutoff.unwrap_or_else(|| {
+        let mut residual = limit.into_iter().chain(self.cutoff).min().unwrap_or_else(|| {
             self.rn
                 .neighbors(source)
                 .fold(F::zero(), |sum, arc_id| sum + self.rn.upper[arc_id.index()])
         });
-        while self.rn.distances_to_sink[source.index()] < self.rn.num_nodes {
+        while residual > F::zero() && self.rn.distances_to_sink[source.index()] < self.rn.num_nodes {
             self.current_edge
                 .iter_mut()
                 .enumerate()
@@ -1,19 +1,23 @@
 use crate::{
-    algorithms::maximum_flow::{edge::MaximumFlowEdge, error::MaximumFlowError},
+    algorithms::maximum_flow::{edge::MaximumFlowEdge, error::MaximumFlowError, minimum_cut_dag::MinimumCutDag},
     core::numeric::FlowNum,
     graph::{
-        direction::Directed,
+        direction::Direction,
         graph::Graph,
         ids::{EdgeId, NodeId},
     },
 };
 
 pub trait MaximumFlowSolver<F: FlowNum> {
-    fn new<N>(graph: &Graph<Directed, N, MaximumFlowEdge<F>>) -> Self
+    fn new<D: Direction, N>(graph: &Graph<D, N, MaximumFlowEdge<F>>) -> Self
     where
         Self: Sized;
     fn solve(&mut self, source: NodeId, sink: NodeId) -> Result<F, MaximumFlowError>;
+    // stops once limit units reach the sink, so the objective value is min(maximum flow, limit)
+    // the minimum cut is only meaningful if the returned value is below limit
+    fn solve_with_limit(&mut self, source: NodeId, sink: NodeId, limit: F) -> Result<F, MaximumFlowError>;
     fn flow(&self, u: EdgeId) -> Result<F, MaximumFlowError>;
     fn flows(&self) -> Result<Vec<F>, MaximumFlowError>;
     fn minimum_cut(&mut self) -> Result<Vec<bool>, MaximumFlowError>;
+    fn minimum_cut_dag(&self) -> Result<MinimumCutDag, MaximumFlowError>;
 }
@@ -1,7 +1,7 @@
 use crate::{
+    algorithms::maximum_flow::{error::MaximumFlowError, residual_network::ResidualNetwork},
     core::numeric::FlowNum,
     graph::ids::NodeId,
-    algorithms::maximum_flow::{residual_network::ResidualNetwork, error::MaximumFlowError},
 };
 
 pub fn validate_input<F: FlowNum>(
@@ -10,7 +10,11 @@ pub fn validate_input<F: FlowNum>(
     sink: NodeId,
 ) -> Result<(), MaximumFlowError> {
     if source.index() >= rn.num_nodes || sink.index() >= rn.num_nodes || source == sink {
-        return Err(MaximumFlowError::InvalidTerminal {source, sink, num_nodes: rn.num_nodes});
+        return Err(MaximumFlowError::InvalidTerminal { source, sink, num_nodes: rn.num_nodes });
+    }
+    // flow against the orientation of an undirected edge is negative, which an unsigned F cannot hold
+    if !rn.is_directed && F::min_value() >= F::zero() {
+        return Err(MaximumFlowError::UnsignedFlowOnUndirectedGraph);
     }
 
     Ok(())
@@ -0,0 +1,141 @@
+use crate::{
+    core::numeric::FlowNum,
+    graph::{
+        direction::Direction,
+        edge::Edge,
+        graph::Graph,
+        ids::{EdgeId, NodeId},
+    },
+};
+use std::{error::Error as StdError, fmt};
+
+// the first constraint of a maximum flow certificate which does not hold, in the order they are checked
+#[non_exhaustive]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Violation<F> {
+    InvalidTerminal {
+        source: NodeId,
+        sink: NodeId,
+        num_nodes: usize,
+    },
+    InvalidFlowsLength {
+        expected: usize,
+        actual: usize,
+    },
+    InvalidCutLength {
+        expected: usize,
+        actual: usize,
+    },
+    // the cut must contain the source but not the sink
+    NotSeparating,
+    // 0 <= flow <= upper, or -upper <= flow <= upper for undirected edges
+    CapacityBound {
+        edge_id: EdgeId,
+        flow: F,
+        upper: F,
+    },
+    Conservation {
+        node: NodeId,
+        inflow: F,
+        outflow: F,
+    },
+    // the net inflow of the sink, inflow - outflow, differs from the capacity of the cut
+    ValueMismatch {
+        inflow: F,
+        outflow: F,
+        cut_capacity: F,
+    },
+}
+
+impl<F: fmt::Debug> fmt::Display for Violation<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidTerminal { source, sink, num_nodes } => {
+                write!(f, "invalid source/sink (source={source:?}, sink={sink:?}, num_nodes={num_nodes})")
+            }
+            Self::InvalidFlowsLength { expected, actual } => {
+                write!(f, "flows must have one entry per edge (expected={expected}, actual={actual})")
+            }
+            Self::InvalidCutLength { expected, actual } => {
+                write!(f, "cut must have one entry per node (expected={expected}, actual={actual})")
+            }
+            Self::NotSeparating => write!(f, "cut does not separate the source from the sink"),
+            Self::CapacityBound { edge_id, flow, upper } => {
+                write!(f, "capacity bound violated (edge id={edge_id:?}, flow={flow:?}, upper={upper:?})")
+            }
+            Self::Conservation { node, inflow, outflow } => {
+                write!(f, "flow conservation violated (node={node:?}, inflow={inflow:?}, outflow={outflow:?})")
+            }
+            Self::ValueMismatch { inflow, outflow, cut_capacity } => write!(
+                f,
+                "flow value differs from cut capacity (sink inflow={inflow:?}, sink outflow={outflow:?}, cut capacity={cut_capacity:?})"
+            ),
+        }
+    }
+}
+
+impl<F: fmt::Debug> StdError for Violation<F> {}
+
+// O(n + m)
+// checks that flows is a feasible source-sink flow and cut a source side whose capacity equals the flow value,
+// which certifies that both are optimal; returns the maximum flow value
+pub fn verify<D, N, E, F, UF>(
+    graph: &Graph<D, N, E>,
+    upper_fn: UF,
+    source: NodeId,
+    sink: NodeId,
+    flows: &[F],
+    cut: &[bool],
+) -> Result<F, Violation<F>>
+where
+    D: Direction,
+    F: FlowNum,
+    UF: Fn(&Edge<E>) -> F,
+{
+    let (num_nodes, num_edges) = (graph.num_nodes(), graph.num_edges());
+    if source.index() >= num_nodes || sink.index() >= num_nodes || source == sink {
+        return Err(Violation::InvalidTerminal { source, sink, num_nodes });
+    }
+    if flows.len() != num_edges {
+        return Err(Violation::InvalidFlowsLength { expected: num_edges, actual: flows.len() });
+    }
+    if cut.len() != num_nodes {
+        return Err(Violation::InvalidCutLength { expected: num_nodes, actual: cut.len() });
+    }
+    if !cut[source.index()] || cut[sink.index()] {
+        return Err(Violation::NotSeparating);
+    }
+
+    let mut inflow = vec![F::zero(); num_nodes];
+    let mut outflow = vec![F::zero(); num_nodes];
+    let mut cut_capacity = F::zero();
+    for (edge_id, (e, &flow)) in graph.edges().zip(flows.iter()).enumerate() {
+        let upper = upper_fn(e);
+        let within = if flow >= F::zero() {
+            flow <= upper
+        } else {
+            !D::IS_DIRECTED && F::zero() - flow <= upper
+        };
+        if !within {
+            return Err(Violation::CapacityBound { edge_id: EdgeId(edge_id), flow, upper });
+        }
+
+        outflow[e.u.index()] += flow;
+        inflow[e.v.index()] += flow;
+        if cut[e.u.index()] != cut[e.v.index()] && (cut[e.u.index()] || !D::IS_DIRECTED) {
+            cut_capacity += upper;
+        }
+    }
+
+    for u in (0..num_nodes).map(NodeId) {
+        if u != source && u != sink && inflow[u.index()] != outflow[u.index()] {
+            return Err(Violation::Conservation { node: u, inflow: inflow[u.index()], outflow: outflow[u.index()] });
+        }
+    }
+
+    let (inflow, outflow) = (inflow[sink.index()], outflow[sink.index()]);
+    if outflow + cut_capacity != inflow {
+        return Err(Violation::ValueMismatch { inflow, outflow, cut_capacity });
+    }
+    Ok(cut_capacity)
+}
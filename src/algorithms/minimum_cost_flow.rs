@@ -3,6 +3,7 @@ pub mod error;
 mod extend_network;
 pub mod graph;
 pub mod node;
+pub mod node_capacitated;
 mod normalized_network;
 pub mod prelude;
 pub mod residual_network;
@@ -1,3 +1,4 @@
+use crate::ids::{EdgeId, NodeId};
 use std::{error::Error as StdError, fmt};
 
 #[non_exhaustive]
@@ -6,6 +7,18 @@ pub enum MinimumCostFlowError {
     NotSolved,
     Unbalanced,
     Infeasible,
+    InvalidEdgeId {
+        edge_id: EdgeId,
+    },
+    InvalidNodeId {
+        node_id: NodeId,
+    },
+    InvalidTerminal {
+        source: NodeId,
+        sink: NodeId,
+        num_nodes: usize,
+    },
+    InvalidParameter,
 }
 
 impl fmt::Display for MinimumCostFlowError {
@@ -14,6 +27,14 @@ impl fmt::Display for MinimumCostFlowError {
             Self::NotSolved => write!(f, "solver has not been run yet"),
             Self::Unbalanced => write!(f, "unbalanced"),
             Self::Infeasible => write!(f, "infeasible"),
+            Self::InvalidEdgeId { edge_id } => write!(f, "invalid edge id (edge id={edge_id:?})"),
+            Self::InvalidNodeId { node_id } => write!(f, "invalid node id (node id={node_id:?})"),
+            Self::InvalidTerminal { source, sink, num_nodes } => {
+                write!(f, "invalid source/sink (source={source:?}, sink={sink:?}, num_nodes={num_nodes})")
+            }
+            Self::InvalidParameter => {
+                write!(f, "invalid parameter (capacities and transit times must be non-negative)")
+            }
         }
     }
 }
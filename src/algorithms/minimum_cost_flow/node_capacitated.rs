@@ -0,0 +1,154 @@
+use crate::{
+    algorithms::minimum_cost_flow::{
+        error::MinimumCostFlowError,
+        graph::MinimumCostFlowGraph,
+        solvers::{primal_network_simplex::PrimalNetworkSimplex, solver::MinimumCostFlowSolver},
+    },
+    core::numeric::CostNum,
+    graph::ids::{EdgeId, NodeId},
+};
+
+// minimum cost flow with node capacities and costs
+// every node u with a capacity or a cost is split into u, receiving the arcs entering u and its supply,
+// and a new node sending the arcs leaving u and its demand, joined by an arc with the capacity and cost of u
+// S is the minimum cost flow solver run on the split graph
+pub struct NodeCapacitated<F, S = PrimalNetworkSimplex<F>> {
+    supplies: Vec<F>,
+    // (u, v, lower, upper, cost)
+    edges: Vec<(NodeId, NodeId, F, F, F)>,
+    capacities: Vec<Option<F>>,
+    costs: Vec<Option<F>>,
+
+    // built by solve
+    solver: Option<S>,
+    // the split arc of every split node
+    node_arcs: Vec<Option<EdgeId>>,
+}
+
+impl<F, S> NodeCapacitated<F, S>
+where
+    F: CostNum + Default,
+    S: MinimumCostFlowSolver<F>,
+{
+    pub fn new(graph: &MinimumCostFlowGraph<F>) -> Self {
+        let n = graph.num_nodes();
+        Self {
+            supplies: graph.nodes().map(|node| node.data.b).collect(),
+            edges: graph
+                .edges()
+                .map(|e| (e.u, e.v, e.data.lower, e.data.upper, e.data.cost))
+                .collect(),
+            capacities: vec![None; n],
+            costs: vec![None; n],
+            solver: None,
+            node_arcs: Vec::new(),
+        }
+    }
+
+    // bounds the total flow passing through u, including its own supply or demand
+    pub fn set_node_capacity(&mut self, u: NodeId, capacity: F) -> Option<()> {
+        *self.capacities.get_mut(u.index())? = Some(capacity);
+        self.solver = None;
+        Some(())
+    }
+
+    // cost per unit of flow passing through u, including its own supply or demand
+    pub fn set_node_cost(&mut self, u: NodeId, cost: F) -> Option<()> {
+        *self.costs.get_mut(u.index())? = Some(cost);
+        self.solver = None;
+        Some(())
+    }
+
+    pub fn node_capacity(&self, u: NodeId) -> Option<F> {
+        self.capacities.get(u.index()).copied().flatten()
+    }
+
+    pub fn node_cost(&self, u: NodeId) -> Option<F> {
+        self.costs.get(u.index()).copied().flatten()
+    }
+
+    // the cost includes the node costs
+    pub fn solve(&mut self) -> Result<F, MinimumCostFlowError> {
+        self.build().solve()
+    }
+
+    pub fn flow(&self, edge_id: EdgeId) -> Result<F, MinimumCostFlowError> {
+        if edge_id.index() >= self.edges.len() {
+            return Err(MinimumCostFlowError::InvalidEdgeId { edge_id });
+        }
+        self.solver()?.flow(edge_id)
+    }
+
+    pub fn flows(&self) -> Result<Vec<F>, MinimumCostFlowError> {
+        let mut flows = self.solver()?.flows()?;
+        flows.truncate(self.edges.len());
+        Ok(flows)
+    }
+
+    // the flow through u, including its supply or demand, None if u has neither a capacity nor a cost
+    pub fn node_flow(&self, u: NodeId) -> Result<Option<F>, MinimumCostFlowError> {
+        self.validate_node(u)?;
+        let solver = self.solver()?;
+        match self.node_arcs[u.index()] {
+            Some(arc) => solver.flow(arc).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    // the potential of a split node is the one of the node receiving its entering arcs
+    pub fn potential(&self, u: NodeId) -> Result<F, MinimumCostFlowError> {
+        self.validate_node(u)?;
+        self.solver()?.potential(u)
+    }
+
+    pub fn potentials(&self) -> Result<Vec<F>, MinimumCostFlowError> {
+        let mut potentials = self.solver()?.potentials()?;
+        potentials.truncate(self.supplies.len());
+        Ok(potentials)
+    }
+
+    fn validate_node(&self, u: NodeId) -> Result<(), MinimumCostFlowError> {
+        if u.index() >= self.supplies.len() {
+            return Err(MinimumCostFlowError::InvalidNodeId { node_id: u });
+        }
+        Ok(())
+    }
+
+    fn solver(&self) -> Result<&S, MinimumCostFlowError> {
+        self.solver.as_ref().ok_or(MinimumCostFlowError::NotSolved)
+    }
+
+    fn build(&mut self) -> &mut S {
+        if self.solver.is_none() {
+            let n = self.supplies.len();
+            let mut split = MinimumCostFlowGraph::default();
+            let mut out_node = split.add_nodes(n);
+            let is_split: Vec<_> = (0..n)
+                .map(|u| self.capacities[u].is_some() || self.costs[u].is_some())
+                .collect();
+            for u in (0..n).filter(|&u| is_split[u]) {
+                out_node[u] = split.add_node();
+            }
+
+            // the edges keep their ids, so flows can be read off directly
+            let mut inflow_bound: Vec<_> = self.supplies.iter().map(|&b| b.max(F::zero())).collect();
+            for &(u, v, lower, upper, cost) in self.edges.iter() {
+                split.add_edge(out_node[u.index()], v, lower, upper, cost);
+                inflow_bound[v.index()] += upper;
+            }
+
+            self.node_arcs = vec![None; n];
+            for (u, &b) in self.supplies.iter().enumerate() {
+                split.set_excess(if b >= F::zero() { NodeId(u) } else { out_node[u] }, b);
+                if is_split[u] {
+                    // without a capacity, nothing beyond the flow which can reach u passes through it
+                    let upper = self.capacities[u].unwrap_or(inflow_bound[u]);
+                    let cost = self.costs[u].unwrap_or(F::zero());
+                    self.node_arcs[u] = split.add_edge(NodeId(u), out_node[u], F::zero(), upper, cost);
+                }
+            }
+            self.solver = Some(S::new(&split));
+        }
+        self.solver.as_mut().unwrap()
+    }
+}
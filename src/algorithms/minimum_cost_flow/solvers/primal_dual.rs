@@ -29,6 +29,7 @@ pub struct PrimalDual<F> {
     que: VecDeque<NodeId>,
     distances: Box<[usize]>,
     current_edge: Box<[usize]>,
+    path: Vec<ArcId>,
 
     // working
     dist: Box<[Option<F>]>,
@@ -82,6 +83,7 @@ where
             que: VecDeque::new(),
             distances: vec![0; num_nodes].into_boxed_slice(),
             current_edge: vec![0; num_nodes].into_boxed_slice(),
+            path: Vec::new(),
             dist: vec![None; num_nodes].into_boxed_slice(),
             visited: vec![false; num_nodes].into_boxed_slice(),
             source,
@@ -212,40 +214,69 @@ where
         }
     }
 
-    fn dfs(&mut self, u: NodeId, sink: NodeId, upper: F) -> Option<F> {
-        if u == sink {
-            return Some(upper);
-        }
-
+    // blocking flow of at most upper on the admissible arcs with zero reduced cost, with an explicit path stack
+    // after each augmentation, the search resumes from the tail of the saturated arc closest to the source
+    fn dfs(&mut self, source: NodeId, sink: NodeId, upper: F) -> Option<F> {
+        self.path.clear();
         let mut res = F::zero();
-        for arc_id in (self.current_edge[u.index()]..self.rn.start[u.index() + 1]).map(ArcId) {
-            self.current_edge[u.index()] = arc_id.index();
+        let mut u = source;
+        while res < upper {
+            if u == sink {
+                let delta = self
+                    .path
+                    .iter()
+                    .map(|&arc_id| self.rn.residual_capacity(arc_id))
+                    .fold(upper - res, |delta, residual_capacity| delta.min(residual_capacity));
+
+                // update flow
+                for &arc_id in self.path.iter() {
+                    let rev = self.rn.rev[arc_id.index()];
+                    self.rn.residual_capacity[arc_id.index()] -= delta;
+                    self.rn.residual_capacity[rev.index()] += delta;
+                }
+                res += delta;
 
-            if !self.is_admissible_edge(u, arc_id) || self.rn.reduced_cost(u, arc_id) != F::zero() {
+                if let Some(pos) = self
+                    .path
+                    .iter()
+                    .position(|&arc_id| self.rn.residual_capacity(arc_id) == F::zero())
+                {
+                    self.path.truncate(pos);
+                }
+                u = self.path.last().map_or(source, |&arc_id| self.rn.to[arc_id.index()]);
                 continue;
             }
 
-            let v = self.rn.to[arc_id.index()];
-            let residual_capacity = self.rn.residual_capacity(arc_id);
-            if let Some(d) = self.dfs(v, sink, residual_capacity.min(upper - res)) {
-                let rev = self.rn.rev[arc_id.index()];
-
-                // update flow
-                self.rn.residual_capacity[arc_id.index()] -= d;
-                self.rn.residual_capacity[rev.index()] += d;
+            // advance
+            if let Some(arc_id) = self.find_admissible_edge(u) {
+                self.path.push(arc_id);
+                u = self.rn.to[arc_id.index()];
+                continue;
+            }
 
-                res += d;
-                if res == upper {
-                    return Some(res);
-                }
+            // retreat
+            self.distances[u.index()] = self.rn.num_nodes;
+            if u == source {
+                break;
             }
+            self.path.pop();
+            u = self.path.last().map_or(source, |&arc_id| self.rn.to[arc_id.index()]);
         }
-        self.current_edge[u.index()] = self.rn.start[u.index() + 1];
-        self.distances[u.index()] = self.rn.num_nodes;
 
         Some(res)
     }
 
+    fn find_admissible_edge(&mut self, u: NodeId) -> Option<ArcId> {
+        for arc_id in (self.current_edge[u.index()]..self.rn.start[u.index() + 1]).map(ArcId) {
+            self.current_edge[u.index()] = arc_id.index();
+            if self.is_admissible_edge(u, arc_id) && self.rn.reduced_cost(u, arc_id) == F::zero() {
+                return Some(arc_id);
+            }
+        }
+        self.current_edge[u.index()] = self.rn.start[u.index() + 1];
+        None
+    }
+
     #[inline]
     fn is_admissible_edge(&self, from: NodeId, arc_id: ArcId) -> bool {
         self.rn.residual_capacity(arc_id) > F::zero()
@@ -0,0 +1,4 @@
+pub mod hao_orlin;
+pub mod karger_stein;
+pub mod prelude;
+pub mod stoer_wagner;
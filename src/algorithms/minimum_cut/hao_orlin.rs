@@ -0,0 +1,205 @@
+use crate::{
+    algorithms::maximum_flow::{edge::MaximumFlowEdge, residual_network::ResidualNetwork},
+    core::numeric::FlowNum,
+    graph::{direction::Directed, edge::Edge, graph::Graph, ids::NodeId},
+};
+
+// Hao and Orlin's algorithm, O(n^2 m) with generic push-relabel
+// a single push-relabel run fixes node 0 on the source side and moves the sink into the source set one node at a time,
+// so the n - 1 cuts it finds contain the minimum cut with node 0 on the source side;
+// a second run on the reversed network covers the cuts with node 0 on the sink side
+pub struct HaoOrlin<F> {
+    rn: ResidualNetwork<F>,
+}
+
+struct Phase<F> {
+    in_source: Vec<bool>,
+    in_awake: Vec<bool>,
+    // dormant node sets, the last one is woken up first
+    dormant: Vec<Vec<NodeId>>,
+    // number of awake nodes with each label
+    count: Vec<usize>,
+    active: Vec<NodeId>,
+    best: Option<(F, Vec<bool>, NodeId)>,
+}
+
+impl<F> HaoOrlin<F>
+where
+    F: FlowNum,
+{
+    pub fn new<N>(graph: &Graph<Directed, N, MaximumFlowEdge<F>>) -> Self {
+        Self::new_with(graph, |e| e.data.upper)
+    }
+
+    // capacities must be non-negative
+    pub fn new_with<N, E, CF>(graph: &Graph<Directed, N, E>, capacity_fn: CF) -> Self
+    where
+        CF: Fn(&Edge<E>) -> F,
+    {
+        Self { rn: ResidualNetwork::from(graph, capacity_fn) }
+    }
+
+    // returns the minimum capacity of the arcs leaving a node set, the set (true side) and a node pair (s, t)
+    // with s inside and t outside of it, or None if the graph has fewer than two nodes
+    pub fn solve(&mut self) -> Option<(F, Vec<bool>, (NodeId, NodeId))> {
+        if self.rn.num_nodes < 2 {
+            return None;
+        }
+        let source = NodeId(0);
+
+        let (value, side, sink) = self.run(source);
+        let mut best = (value, side, (source, sink));
+
+        self.reverse();
+        let (value, side, sink) = self.run(source);
+        self.reverse();
+        if value < best.0 {
+            // the sink side of a cut of the reversed network is the source side of the same cut of the original one
+            best = (value, side.iter().map(|&s| !s).collect(), (sink, source));
+        }
+        Some(best)
+    }
+
+    fn reverse(&mut self) {
+        for &arc_id in self.rn.edge_id_to_arc_id.iter() {
+            let rev_arc_id = self.rn.rev[arc_id.index()];
+            self.rn.upper.swap(arc_id.index(), rev_arc_id.index());
+        }
+    }
+
+    // returns the minimum cut with source on the true side, together with the sink it separates
+    fn run(&mut self, source: NodeId) -> (F, Vec<bool>, NodeId) {
+        let n = self.rn.num_nodes;
+        self.rn.residual_capacities.copy_from_slice(&self.rn.upper);
+        self.rn.excesses.fill(F::zero());
+        self.rn.distances_to_sink.fill(0);
+
+        let mut phase = Phase {
+            in_source: vec![false; n],
+            in_awake: vec![true; n],
+            dormant: Vec::new(),
+            count: vec![0; n + 1],
+            active: Vec::new(),
+            best: None,
+        };
+        phase.count[0] = n;
+        self.move_to_source(&mut phase, source);
+
+        let mut sink = (0..n).map(NodeId).find(|&u| u != source).unwrap();
+        for _ in 1..n {
+            while let Some(u) = phase.active.pop() {
+                if phase.in_awake[u.index()] && u != sink && self.rn.excesses[u.index()] > F::zero() {
+                    self.discharge(&mut phase, u, sink);
+                }
+            }
+
+            // no residual arc enters the awake nodes, and only the sink among them has excess
+            let value = self.rn.excesses[sink.index()];
+            if phase.best.as_ref().is_none_or(|(best, _, _)| value < *best) {
+                phase.best = Some((value, phase.in_awake.iter().map(|&awake| !awake).collect(), sink));
+            }
+
+            self.move_to_source(&mut phase, sink);
+            if phase.in_awake.iter().all(|&awake| !awake) {
+                let Some(set) = phase.dormant.pop() else {
+                    break;
+                };
+                for u in set {
+                    self.wake_up(&mut phase, u);
+                }
+            }
+            sink = (0..n)
+                .map(NodeId)
+                .filter(|&u| phase.in_awake[u.index()])
+                .min_by_key(|&u| self.rn.distances_to_sink[u.index()])
+                .unwrap_or(sink);
+        }
+
+        phase.best.unwrap()
+    }
+
+    // the node leaves the awake set and saturates every arc leaving the source set
+    fn move_to_source(&mut self, phase: &mut Phase<F>, u: NodeId) {
+        phase.in_awake[u.index()] = false;
+        phase.count[self.rn.distances_to_sink[u.index()]] -= 1;
+        phase.in_source[u.index()] = true;
+
+        for arc_id in self.rn.neighbors(u) {
+            let to = self.rn.to[arc_id.index()];
+            let delta = self.rn.residual_capacity(arc_id);
+            if phase.in_source[to.index()] || delta == F::zero() {
+                continue;
+            }
+            self.rn.push_flow_without_excess(u, arc_id, delta);
+            self.rn.excesses[to.index()] += delta;
+            phase.active.push(to);
+        }
+    }
+
+    fn wake_up(&mut self, phase: &mut Phase<F>, u: NodeId) {
+        phase.in_awake[u.index()] = true;
+        phase.count[self.rn.distances_to_sink[u.index()]] += 1;
+        if self.rn.excesses[u.index()] > F::zero() {
+            phase.active.push(u);
+        }
+    }
+
+    fn sleep(&mut self, phase: &mut Phase<F>, set: Vec<NodeId>) {
+        for &u in set.iter() {
+            phase.in_awake[u.index()] = false;
+            phase.count[self.rn.distances_to_sink[u.index()]] -= 1;
+        }
+        phase.dormant.push(set);
+    }
+
+    // pushes and relabels within the awake nodes until u has no excess or falls asleep
+    fn discharge(&mut self, phase: &mut Phase<F>, u: NodeId, sink: NodeId) {
+        while self.rn.excesses[u.index()] > F::zero() {
+            for arc_id in self.rn.neighbors(u) {
+                let to = self.rn.to[arc_id.index()];
+                if !phase.in_awake[to.index()] || !self.rn.is_admissible_arc(u, arc_id) {
+                    continue;
+                }
+                let delta = self.rn.excesses[u.index()].min(self.rn.residual_capacity(arc_id));
+                if to != sink && self.rn.excesses[to.index()] == F::zero() {
+                    phase.active.push(to);
+                }
+                self.rn.push_flow(u, arc_id, delta);
+                if self.rn.excesses[u.index()] == F::zero() {
+                    return;
+                }
+            }
+
+            // u is the only awake node with its label, so nothing at or above it can reach the sink
+            let label = self.rn.distances_to_sink[u.index()];
+            if phase.count[label] == 1 {
+                let set = (0..self.rn.num_nodes)
+                    .map(NodeId)
+                    .filter(|&v| phase.in_awake[v.index()] && self.rn.distances_to_sink[v.index()] >= label)
+                    .collect();
+                self.sleep(phase, set);
+                return;
+            }
+
+            let new_label = self
+                .rn
+                .neighbors(u)
+                .filter(|&arc_id| self.rn.residual_capacity(arc_id) > F::zero())
+                .map(|arc_id| self.rn.to[arc_id.index()])
+                .filter(|&to| to != u && phase.in_awake[to.index()])
+                .map(|to| self.rn.distances_to_sink[to.index()] + 1)
+                .min();
+            let Some(new_label) = new_label else {
+                self.sleep(phase, vec![u]);
+                return;
+            };
+
+            phase.count[label] -= 1;
+            if new_label >= phase.count.len() {
+                phase.count.resize(new_label + 1, 0);
+            }
+            phase.count[new_label] += 1;
+            self.rn.distances_to_sink[u.index()] = new_label;
+        }
+    }
+}
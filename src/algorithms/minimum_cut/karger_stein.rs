@@ -0,0 +1,202 @@
+use crate::{
+    algorithms::maximum_flow::edge::MaximumFlowEdge,
+    core::numeric::FlowNum,
+    data_structures::UnionFind,
+    graph::{direction::Undirected, edge::Edge, graph::Graph},
+};
+use num_traits::ToPrimitive;
+
+const BASE_SIZE: usize = 6;
+
+// Karger and Stein's recursive contraction algorithm, O(n^2 log^2 n) per trial with sorted contractions
+// each trial finds a minimum cut with probability Ω(1 / log n); by default ⌈log2 n⌉^2 trials are run
+// contracting edges in the order of exponential keys with rate equal to the capacity is the same as
+// repeatedly contracting an edge chosen with probability proportional to its capacity
+pub struct KargerStein<F> {
+    num_nodes: usize,
+    edges: Vec<(usize, usize, F)>,
+    seed: u64,
+    num_trials: Option<usize>,
+}
+
+impl<F> KargerStein<F>
+where
+    F: FlowNum + ToPrimitive,
+{
+    pub fn new<N>(graph: &Graph<Undirected, N, MaximumFlowEdge<F>>) -> Self {
+        Self::new_with(graph, |e| e.data.upper)
+    }
+
+    // capacities must be non-negative
+    pub fn new_with<N, E, CF>(graph: &Graph<Undirected, N, E>, capacity_fn: CF) -> Self
+    where
+        CF: Fn(&Edge<E>) -> F,
+    {
+        let edges = graph
+            .edges()
+            .map(|e| (e.u.index(), e.v.index(), capacity_fn(e)))
+            .collect();
+        Self { num_nodes: graph.num_nodes(), edges, seed: 0, num_trials: None }
+    }
+
+    pub fn set_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    pub fn set_num_trials(mut self, num_trials: usize) -> Self {
+        self.num_trials = Some(num_trials.max(1));
+        self
+    }
+
+    // returns the smallest cut capacity found and one side of that cut, or None if the graph has fewer than two nodes
+    pub fn solve(&self) -> Option<(F, Vec<bool>)> {
+        let n = self.num_nodes;
+        if n < 2 {
+            return None;
+        }
+
+        let mut rng = SplitMix64(self.seed);
+        let edges = merge_parallel_edges(self.edges.clone());
+        let num_trials = if n <= BASE_SIZE {
+            1
+        } else {
+            let log_n = usize::BITS - (n - 1).leading_zeros();
+            self.num_trials.unwrap_or((log_n * log_n) as usize)
+        };
+
+        (0..num_trials)
+            .map(|_| recursive_contraction(&mut rng, n, &edges))
+            .min_by(|a, b| a.0.cmp(&b.0))
+    }
+}
+
+fn recursive_contraction<F>(rng: &mut SplitMix64, n: usize, edges: &[(usize, usize, F)]) -> (F, Vec<bool>)
+where
+    F: FlowNum + ToPrimitive,
+{
+    if n <= BASE_SIZE {
+        return brute_force(n, edges);
+    }
+
+    let target = ((1.0 + n as f64 / std::f64::consts::SQRT_2).ceil() as usize).min(n - 1);
+    let mut best: Option<(F, Vec<bool>)> = None;
+    for _ in 0..2 {
+        let (label, contracted) = contract(rng, n, edges, target);
+        let (value, side) = recursive_contraction(rng, target, &contracted);
+        if best.as_ref().is_none_or(|(best_value, _)| value < *best_value) {
+            best = Some((value, label.iter().map(|&u| side[u]).collect()));
+        }
+    }
+    best.unwrap()
+}
+
+// contracts random edges until target nodes remain, returns the new label of every node and the contracted edges
+fn contract<F>(
+    rng: &mut SplitMix64,
+    n: usize,
+    edges: &[(usize, usize, F)],
+    target: usize,
+) -> (Vec<usize>, Vec<(usize, usize, F)>)
+where
+    F: FlowNum + ToPrimitive,
+{
+    let mut order: Vec<(f64, usize)> = edges
+        .iter()
+        .enumerate()
+        .filter(|(_, (_, _, capacity))| *capacity > F::zero())
+        .map(|(i, (_, _, capacity))| (-(1.0 - rng.next_f64()).ln() / capacity.to_f64().unwrap(), i))
+        .collect();
+    order.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+    let mut uf = UnionFind::new(n);
+    let mut num_components = n;
+    for &(_, i) in order.iter() {
+        if num_components == target {
+            break;
+        }
+        let (u, v, _) = edges[i];
+        if uf.union(u, v) {
+            num_components -= 1;
+        }
+    }
+
+    // the remaining components are disconnected, so merging them keeps a cut of capacity zero
+    for u in 1..n {
+        if num_components == target {
+            break;
+        }
+        if uf.union(0, u) {
+            num_components -= 1;
+        }
+    }
+
+    let mut label = vec![usize::MAX; n];
+    let mut num_labels = 0;
+    for u in 0..n {
+        let root = uf.find(u);
+        if label[root] == usize::MAX {
+            label[root] = num_labels;
+            num_labels += 1;
+        }
+        label[u] = label[root];
+    }
+
+    let contracted = edges
+        .iter()
+        .map(|&(u, v, capacity)| (label[u], label[v], capacity))
+        .collect();
+    (label, merge_parallel_edges(contracted))
+}
+
+// removes self-loops and merges parallel edges, O(m log m)
+fn merge_parallel_edges<F: FlowNum>(mut edges: Vec<(usize, usize, F)>) -> Vec<(usize, usize, F)> {
+    edges.retain(|&(u, v, _)| u != v);
+    for (u, v, _) in edges.iter_mut() {
+        if u > v {
+            std::mem::swap(u, v);
+        }
+    }
+    edges.sort_unstable_by_key(|&(u, v, _)| (u, v));
+
+    let mut merged: Vec<(usize, usize, F)> = Vec::with_capacity(edges.len());
+    for (u, v, capacity) in edges {
+        match merged.last_mut() {
+            Some(last) if (last.0, last.1) == (u, v) => last.2 += capacity,
+            _ => merged.push((u, v, capacity)),
+        }
+    }
+    merged
+}
+
+// the last node is always on the false side
+fn brute_force<F: FlowNum>(n: usize, edges: &[(usize, usize, F)]) -> (F, Vec<bool>) {
+    let mut best = (F::max_value(), 0);
+    for mask in 1..(1_usize << (n - 1)) {
+        let capacity = edges
+            .iter()
+            .filter(|&&(u, v, _)| (mask >> u) & 1 != (mask >> v) & 1)
+            .fold(F::zero(), |sum, &(_, _, capacity)| sum + capacity);
+        if capacity < best.0 {
+            best = (capacity, mask);
+        }
+    }
+    (best.0, (0..n).map(|u| (best.1 >> u) & 1 == 1).collect())
+}
+
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e3779b97f4a7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    }
+
+    // uniform in [0, 1)
+    fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1_u64 << 53) as f64
+    }
+}
@@ -0,0 +1,3 @@
+pub use super::hao_orlin::HaoOrlin;
+pub use super::karger_stein::KargerStein;
+pub use super::stoer_wagner::StoerWagner;
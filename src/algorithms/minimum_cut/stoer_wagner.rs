@@ -0,0 +1,97 @@
+use crate::{
+    algorithms::maximum_flow::edge::MaximumFlowEdge,
+    core::numeric::FlowNum,
+    data_structures::{FibonacciHeap, UnionFind},
+    graph::{direction::Undirected, edge::Edge, graph::Graph},
+};
+use std::cmp::Reverse;
+
+// Stoer and Wagner's algorithm, O(nm + n^2 log n) with a Fibonacci heap
+// each phase orders the nodes by maximum adjacency, takes the cut around the last node as a candidate
+// and merges the last two nodes
+pub struct StoerWagner<F> {
+    num_nodes: usize,
+    edges: Vec<(usize, usize, F)>,
+}
+
+impl<F> StoerWagner<F>
+where
+    F: FlowNum,
+{
+    pub fn new<N>(graph: &Graph<Undirected, N, MaximumFlowEdge<F>>) -> Self {
+        Self::new_with(graph, |e| e.data.upper)
+    }
+
+    // capacities must be non-negative
+    pub fn new_with<N, E, CF>(graph: &Graph<Undirected, N, E>, capacity_fn: CF) -> Self
+    where
+        CF: Fn(&Edge<E>) -> F,
+    {
+        let edges = graph
+            .edges()
+            .map(|e| (e.u.index(), e.v.index(), capacity_fn(e)))
+            .collect();
+        Self { num_nodes: graph.num_nodes(), edges }
+    }
+
+    // returns the minimum cut capacity and one side of the cut, or None if the graph has fewer than two nodes
+    pub fn solve(&self) -> Option<(F, Vec<bool>)> {
+        let n = self.num_nodes;
+        if n < 2 {
+            return None;
+        }
+
+        // adjacency of merged nodes, with endpoints resolved lazily by the union-find
+        let mut adjacency = vec![Vec::new(); n];
+        for &(u, v, capacity) in self.edges.iter().filter(|&&(u, v, _)| u != v) {
+            adjacency[u].push((v, capacity));
+            adjacency[v].push((u, capacity));
+        }
+        let mut uf = UnionFind::new(n);
+        let mut alive = vec![true; n];
+        let mut key = vec![F::zero(); n];
+        // a min-heap on the negated keys, nodes leave it once added to the ordering
+        let mut heap = FibonacciHeap::new(n);
+        let mut best: Option<(F, Vec<usize>)> = None;
+
+        for _ in 1..n {
+            // maximum adjacency ordering
+            heap.clear();
+            for u in (0..n).filter(|&u| alive[u]) {
+                key[u] = F::zero();
+                heap.push(u, Reverse(F::zero()));
+            }
+            let (mut s, mut t) = (usize::MAX, usize::MAX);
+            while let Some((_, u)) = heap.pop() {
+                (s, t) = (t, u);
+                for &(v, capacity) in adjacency[u].iter() {
+                    let v = uf.find(v);
+                    if heap.contains(v) {
+                        key[v] += capacity;
+                        heap.decrease_key(v, Reverse(key[v]));
+                    }
+                }
+            }
+
+            // cut of the phase
+            if best.as_ref().is_none_or(|(value, _)| key[t] < *value) {
+                best = Some((key[t], uf.group(t)));
+            }
+
+            // merge t into s
+            uf.union(s, t);
+            let (root, other) = if uf.find(s) == s { (s, t) } else { (t, s) };
+            let moved = std::mem::take(&mut adjacency[other]);
+            adjacency[root].extend(moved);
+            adjacency[root].retain(|&(v, _)| uf.find(v) != root);
+            alive[other] = false;
+        }
+
+        let (value, members) = best.unwrap();
+        let mut side = vec![false; n];
+        for u in members {
+            side[u] = true;
+        }
+        Some((value, side))
+    }
+}
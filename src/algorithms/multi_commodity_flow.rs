@@ -0,0 +1,2 @@
+pub mod garg_konemann;
+pub mod prelude;
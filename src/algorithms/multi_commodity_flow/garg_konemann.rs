@@ -0,0 +1,409 @@
+use crate::prelude::maximum_flow::MaximumFlowError;
+use crate::prelude::shortest_path::{Dijkstra, ShortestPathSolver};
+use crate::{
+    algorithms::maximum_flow::{
+        edge::MaximumFlowEdge, graph::MaximumFlowGraph, solvers::dinic::Dinic, solvers::solver::MaximumFlowSolver,
+    },
+    core::numeric::FlowNum,
+    graph::{
+        direction::Directed,
+        edge::Edge,
+        graph::Graph,
+        ids::{EdgeId, NodeId},
+    },
+};
+use num_traits::{Bounded, ToPrimitive, Zero};
+use std::{
+    cmp::Ordering,
+    ops::{Add, AddAssign, Sub, SubAssign},
+};
+
+// multi-commodity flow with Garg and Könemann's length functions in Fleischer's phases, Dijkstra being the oracle
+// every edge has a length growing exponentially with the flow on it and the commodities are routed along
+// shortest paths; at the end the flow is scaled down by its largest congestion, so it is always feasible
+// the lengths are a dual solution: under lengths l, no flow beats sum_e c_e l_e divided by the shortest path
+// length (maximum multicommodity flow) or by sum_j d_j dist_j (maximum concurrent flow), which gives the upper bound
+pub struct MultiCommodityFlow<F> {
+    // the data of an edge is its index
+    graph: Graph<Directed, (), usize>,
+    capacities: Vec<F>,
+    commodities: Vec<(NodeId, NodeId, F)>,
+    epsilon: f64,
+    solution: Option<Solution>,
+}
+
+struct Solution {
+    flows: Vec<Vec<f64>>,
+    values: Vec<f64>,
+    lengths: Vec<f64>,
+    upper_bound: f64,
+}
+
+impl<F> MultiCommodityFlow<F>
+where
+    F: FlowNum + ToPrimitive,
+{
+    pub fn new<N>(graph: &Graph<Directed, N, MaximumFlowEdge<F>>) -> Self {
+        Self::new_with(graph, |e| e.data.upper)
+    }
+
+    // capacities must be non-negative
+    pub fn new_with<N, E, CF>(graph: &Graph<Directed, N, E>, capacity_fn: CF) -> Self
+    where
+        CF: Fn(&Edge<E>) -> F,
+    {
+        let mut structure = Graph::default();
+        structure.add_nodes(graph.num_nodes());
+        for (i, e) in graph.edges().enumerate() {
+            structure.add_edge(e.u, e.v, i);
+        }
+        let capacities = graph.edges().map(capacity_fn).collect();
+        Self { graph: structure, capacities, commodities: Vec::new(), epsilon: 0.1, solution: None }
+    }
+
+    // ε in (0, 1), 0.1 by default; a smaller ε is more accurate and slower
+    pub fn set_epsilon(mut self, epsilon: f64) -> Self {
+        self.epsilon = epsilon;
+        self
+    }
+
+    // returns the index of the commodity, None if a terminal is out of range or source == sink
+    // the demand is only used by the maximum concurrent flow
+    pub fn add_commodity(&mut self, source: NodeId, sink: NodeId, demand: F) -> Option<usize> {
+        let n = self.graph.num_nodes();
+        if source.index() >= n || sink.index() >= n || source == sink {
+            return None;
+        }
+        self.commodities.push((source, sink, demand));
+        self.solution = None;
+        Some(self.commodities.len() - 1)
+    }
+
+    pub fn num_commodities(&self) -> usize {
+        self.commodities.len()
+    }
+
+    // maximizes the total flow of all commodities, returns a flow of at least (1 - ε)^2 times the optimum
+    pub fn maximum_multicommodity_flow(&mut self) -> Result<f64, MaximumFlowError> {
+        let (m, eps) = (self.num_positive_edges() as f64, self.epsilon);
+        let delta = self.validate((1.0 + eps) / ((1.0 + eps) * m).powf(1.0 / eps))?;
+        let mut lengths = self.initial_lengths(delta);
+        let mut dijkstra = self.oracle(&lengths);
+        let mut flows = vec![vec![0.0; self.capacities.len()]; self.commodities.len()];
+        let mut values = vec![0.0; self.commodities.len()];
+        let mut best = (f64::INFINITY, Vec::new());
+
+        // r is a lower bound on the length of every shortest path, it grows by 1 + ε per phase
+        let mut r = delta;
+        while r < 1.0 {
+            let threshold = ((1.0 + eps) * r).min(1.0);
+            let mut alpha = f64::INFINITY;
+            for (j, &(source, sink, _)) in self.commodities.iter().enumerate() {
+                while let Some((dist, path)) = self.shortest_path(&mut dijkstra, source, sink) {
+                    // lengths only grow, so the last distance stays a lower bound until the end of the phase
+                    if dist >= threshold {
+                        alpha = alpha.min(dist);
+                        break;
+                    }
+                    let amount = self.bottleneck(&path);
+                    self.augment(&mut dijkstra, &mut lengths, &mut flows[j], &path, amount);
+                    values[j] += amount;
+                }
+            }
+            self.update_bound(&mut best, &lengths, alpha);
+            r *= 1.0 + eps;
+        }
+
+        let value = self.finish(flows, values, best);
+        Ok(value.iter().sum())
+    }
+
+    // maximizes λ such that λ d_j of every commodity j can be routed at once, returns a λ of at least
+    // (1 - ε)^3 times the optimum; commodities with demand zero are ignored
+    pub fn maximum_concurrent_flow(&mut self) -> Result<f64, MaximumFlowError> {
+        let (m, eps) = (self.num_positive_edges() as f64, self.epsilon);
+        let delta = self.validate((m / (1.0 - eps)).powf(-1.0 / eps))?;
+        let demands: Vec<f64> = self.commodities.iter().map(|&(_, _, d)| d.to_f64().unwrap()).collect();
+        let active: Vec<usize> = (0..demands.len()).filter(|&j| demands[j] > 0.0).collect();
+        if active.is_empty() {
+            return Err(MaximumFlowError::InvalidParameter);
+        }
+        let mut flows = vec![vec![0.0; self.capacities.len()]; self.commodities.len()];
+        let mut values = vec![0.0; self.commodities.len()];
+
+        // the optimum lies between z / k and z, where z is the smallest maximum flow over demand ratio;
+        // routing z / k times the demands per phase bounds the number of phases
+        let z = self.single_commodity_ratio(&active, &demands);
+        if z == 0.0 {
+            let lengths = self.initial_lengths(0.0);
+            self.solution = Some(Solution { flows, values, lengths, upper_bound: 0.0 });
+            return Ok(0.0);
+        }
+        let scale = z / active.len() as f64;
+
+        let mut lengths = self.initial_lengths(delta);
+        let mut dijkstra = self.oracle(&lengths);
+        let mut volume = delta * m;
+        let mut best = (f64::INFINITY, Vec::new());
+        while volume < 1.0 {
+            let mut alpha = 0.0;
+            for &j in active.iter() {
+                let (source, sink, _) = self.commodities[j];
+                let mut remaining = demands[j] * scale;
+                let mut dist = 0.0;
+                while remaining > 0.0 && volume < 1.0 {
+                    // every commodity with a positive demand has a path, since z > 0
+                    let (d, path) = self.shortest_path(&mut dijkstra, source, sink).unwrap();
+                    let amount = self.bottleneck(&path).min(remaining);
+                    volume += self.augment(&mut dijkstra, &mut lengths, &mut flows[j], &path, amount);
+                    values[j] += amount;
+                    remaining -= amount;
+                    dist = d;
+                }
+                alpha += demands[j] * dist;
+            }
+            self.update_bound(&mut best, &lengths, alpha);
+        }
+
+        let values = self.finish(flows, values, best);
+        Ok(active
+            .iter()
+            .map(|&j| values[j] / demands[j])
+            .fold(f64::INFINITY, f64::min))
+    }
+
+    // the flow of a commodity on every edge
+    pub fn flows(&self, commodity: usize) -> Result<Vec<f64>, MaximumFlowError> {
+        let solution = self.solution(commodity)?;
+        Ok(solution.flows[commodity].clone())
+    }
+
+    pub fn flow(&self, commodity: usize, edge_id: EdgeId) -> Result<f64, MaximumFlowError> {
+        let solution = self.solution(commodity)?;
+        if edge_id.index() >= self.capacities.len() {
+            return Err(MaximumFlowError::InvalidEdgeId { edge_id });
+        }
+        Ok(solution.flows[commodity][edge_id.index()])
+    }
+
+    // the amount of a commodity sent from its source to its sink
+    pub fn value(&self, commodity: usize) -> Result<f64, MaximumFlowError> {
+        let solution = self.solution(commodity)?;
+        Ok(solution.values[commodity])
+    }
+
+    // the dual lengths, normalized so that sum_e c_e l_e over the edges with positive capacity is the upper bound;
+    // edges with capacity zero have an infinite length
+    pub fn lengths(&self) -> Result<Vec<f64>, MaximumFlowError> {
+        let solution = self.solution.as_ref().ok_or(MaximumFlowError::NotSolved)?;
+        Ok(solution.lengths.clone())
+    }
+
+    // an upper bound on the optimum certified by the lengths
+    pub fn upper_bound(&self) -> Result<f64, MaximumFlowError> {
+        let solution = self.solution.as_ref().ok_or(MaximumFlowError::NotSolved)?;
+        Ok(solution.upper_bound)
+    }
+
+    // the solution, if a commodity of this index exists
+    fn solution(&self, commodity: usize) -> Result<&Solution, MaximumFlowError> {
+        let solution = self.solution.as_ref().ok_or(MaximumFlowError::NotSolved)?;
+        if commodity >= self.commodities.len() {
+            return Err(MaximumFlowError::InvalidCommodity { commodity });
+        }
+        Ok(solution)
+    }
+
+    fn num_positive_edges(&self) -> usize {
+        self.capacities.iter().filter(|&&c| c > F::zero()).count()
+    }
+
+    // returns delta, which must not underflow
+    fn validate(&self, delta: f64) -> Result<f64, MaximumFlowError> {
+        if !(self.epsilon > 0.0 && self.epsilon < 1.0)
+            || delta.is_nan()
+            || delta < f64::MIN_POSITIVE
+            || self.capacities.iter().any(|&c| c < F::zero())
+            || self.commodities.iter().any(|&(_, _, d)| d < F::zero())
+        {
+            return Err(MaximumFlowError::InvalidParameter);
+        }
+        Ok(delta)
+    }
+
+    fn initial_lengths(&self, delta: f64) -> Vec<f64> {
+        self.capacities
+            .iter()
+            .map(|&c| {
+                if c > F::zero() {
+                    delta / c.to_f64().unwrap()
+                } else {
+                    f64::INFINITY
+                }
+            })
+            .collect()
+    }
+
+    // built once per solve, augment keeps its weights equal to the lengths
+    fn oracle(&self, lengths: &[f64]) -> Dijkstra<Length> {
+        Dijkstra::new_graph_with(&self.graph, |e| Length(lengths[e.data]))
+    }
+
+    fn shortest_path(
+        &self,
+        dijkstra: &mut Dijkstra<Length>,
+        source: NodeId,
+        sink: NodeId,
+    ) -> Option<(f64, Vec<EdgeId>)> {
+        dijkstra.solve(source).ok()?;
+        let dist = dijkstra.distance(sink)?.0;
+        if dist == f64::INFINITY {
+            return None;
+        }
+        Some((dist, dijkstra.path(sink)?))
+    }
+
+    fn bottleneck(&self, path: &[EdgeId]) -> f64 {
+        path.iter()
+            .map(|e| self.capacities[e.index()].to_f64().unwrap())
+            .fold(f64::INFINITY, f64::min)
+    }
+
+    // sends amount along the path and returns the growth of sum_e c_e l_e
+    fn augment(
+        &self,
+        dijkstra: &mut Dijkstra<Length>,
+        lengths: &mut [f64],
+        flow: &mut [f64],
+        path: &[EdgeId],
+        amount: f64,
+    ) -> f64 {
+        let mut growth = 0.0;
+        for &edge_id in path.iter() {
+            let e = edge_id.index();
+            flow[e] += amount;
+            growth += self.epsilon * amount * lengths[e];
+            lengths[e] *= 1.0 + self.epsilon * amount / self.capacities[e].to_f64().unwrap();
+            dijkstra.set_weight(edge_id, Length(lengths[e]));
+        }
+        growth
+    }
+
+    // alpha is a lower bound on the dual objective under the lengths; infinite if no commodity has a path
+    fn update_bound(&self, best: &mut (f64, Vec<f64>), lengths: &[f64], alpha: f64) {
+        let volume: f64 = lengths
+            .iter()
+            .zip(self.capacities.iter())
+            .filter(|(_, c)| **c > F::zero())
+            .map(|(l, c)| l * c.to_f64().unwrap())
+            .sum();
+        let bound = volume / alpha;
+        if alpha > 0.0 && bound < best.0 {
+            let normalized = lengths
+                .iter()
+                .map(|&l| if l == f64::INFINITY { l } else { l / alpha })
+                .collect();
+            *best = (bound, normalized);
+        }
+    }
+
+    // scales the flow down by its largest congestion, stores the solution and returns the values
+    fn finish(&mut self, mut flows: Vec<Vec<f64>>, mut values: Vec<f64>, best: (f64, Vec<f64>)) -> Vec<f64> {
+        let mut congestion: f64 = 0.0;
+        for (e, capacity) in self.capacities.iter().enumerate() {
+            let total: f64 = flows.iter().map(|flow| flow[e]).sum();
+            if total > 0.0 {
+                congestion = congestion.max(total / capacity.to_f64().unwrap());
+            }
+        }
+        if congestion > 0.0 {
+            flows.iter_mut().flatten().for_each(|f| *f /= congestion);
+            values.iter_mut().for_each(|v| *v /= congestion);
+        }
+
+        let (upper_bound, lengths) = best;
+        self.solution = Some(Solution { flows, values: values.clone(), lengths, upper_bound });
+        values
+    }
+
+    // min_j of the maximum flow of commodity j over d_j, computed exactly with Dinic
+    fn single_commodity_ratio(&self, active: &[usize], demands: &[f64]) -> f64 {
+        let mut graph = MaximumFlowGraph::new_directed();
+        graph.add_nodes(self.graph.num_nodes());
+        for (e, &capacity) in self.graph.edges().zip(self.capacities.iter()) {
+            graph.add_edge(e.u, e.v, capacity);
+        }
+        let mut solver = Dinic::new(&graph);
+        active
+            .iter()
+            .map(|&j| {
+                let (source, sink, _) = self.commodities[j];
+                solver.solve(source, sink).unwrap().to_f64().unwrap() / demands[j]
+            })
+            .fold(f64::INFINITY, f64::min)
+    }
+}
+
+// a length ordered by total_cmp, so that Dijkstra can run on floating point lengths
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Length(f64);
+
+impl Eq for Length {}
+
+impl PartialOrd for Length {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Length {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.total_cmp(&other.0)
+    }
+}
+
+impl Zero for Length {
+    fn zero() -> Self {
+        Length(0.0)
+    }
+
+    fn is_zero(&self) -> bool {
+        self.0 == 0.0
+    }
+}
+
+impl Bounded for Length {
+    fn min_value() -> Self {
+        Length(f64::NEG_INFINITY)
+    }
+
+    fn max_value() -> Self {
+        Length(f64::INFINITY)
+    }
+}
+
+impl Add for Length {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self {
+        Length(self.0 + rhs.0)
+    }
+}
+
+impl Sub for Length {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self {
+        Length(self.0 - rhs.0)
+    }
+}
+
+impl AddAssign for Length {
+    fn add_assign(&mut self, rhs: Self) {
+        self.0 += rhs.0;
+    }
+}
+
+impl SubAssign for Length {
+    fn sub_assign(&mut self, rhs: Self) {
+        self.0 -= rhs.0;
+    }
+}
@@ -0,0 +1 @@
+pub use super::garg_konemann::MultiCommodityFlow;
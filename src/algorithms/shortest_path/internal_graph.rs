@@ -1,7 +1,7 @@
 use crate::{
     core::numeric::FlowNum,
     graph::{direction::Directed, edge::Edge, graph::Graph},
-    ids::{INVALID_NODE_ID, NodeId},
+    ids::{EdgeId, INVALID_NODE_ID, NodeId},
 };
 
 #[derive(Default)]
@@ -11,6 +11,10 @@ pub struct InternalGraph<W> {
     pub start: Box<[usize]>,
     pub to: Box<[NodeId]>,
     pub weight: Box<[W]>,
+    // the id of the edge of the input graph behind each arc
+    pub edge_ids: Box<[EdgeId]>,
+    // the arc of each edge of the input graph
+    pub arcs: Box<[usize]>,
 }
 
 impl<W> InternalGraph<W>
@@ -30,6 +34,8 @@ where
             start: vec![0; num_nodes + 1].into_boxed_slice(),
             to: vec![INVALID_NODE_ID; num_edges].into_boxed_slice(),
             weight: vec![W::zero(); num_edges].into_boxed_slice(),
+            edge_ids: vec![EdgeId(0); num_edges].into_boxed_slice(),
+            arcs: vec![0; num_edges].into_boxed_slice(),
         };
         csr.build(graph, weight_fn);
         csr
@@ -49,11 +55,13 @@ where
         }
 
         let mut counter = vec![0; self.num_nodes];
-        for edge in graph.edges() {
+        for (edge_id, edge) in graph.edges().enumerate() {
             let (u, v) = (edge.u, edge.v);
             let idx = self.start[u.index()] + counter[u.index()];
             self.to[idx] = v;
             self.weight[idx] = weight_fn(edge);
+            self.edge_ids[idx] = EdgeId(edge_id);
+            self.arcs[edge_id] = idx;
             counter[u.index()] += 1;
         }
     }
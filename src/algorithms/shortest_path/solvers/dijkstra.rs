@@ -11,7 +11,7 @@ use crate::{
         direction::Directed,
         edge::Edge,
         graph::Graph,
-        ids::{EdgeId, INVALID_NODE_ID, NodeId},
+        ids::{EdgeId, NodeId},
     },
 };
 use std::{cmp::Reverse, collections::BinaryHeap};
@@ -20,6 +20,8 @@ pub struct Dijkstra<W> {
     ig: InternalGraph<W>,
     reached: BitVector,
     distances: Box<[W]>,
+    // the arc reaching each node on its shortest path
+    prev: Box<[usize]>,
 }
 
 impl<W> Dijkstra<W>
@@ -41,7 +43,41 @@ where
 
     fn new_with_internal_graph(ig: InternalGraph<W>) -> Self {
         let num_nodes = ig.num_nodes;
-        Self { ig, reached: BitVector::new(num_nodes), distances: vec![W::max_value(); num_nodes].into_boxed_slice() }
+        Self {
+            ig,
+            reached: BitVector::new(num_nodes),
+            distances: vec![W::max_value(); num_nodes].into_boxed_slice(),
+            prev: vec![usize::MAX; num_nodes].into_boxed_slice(),
+        }
+    }
+
+    // changes the weight of an edge without rebuilding the graph, for callers running many searches
+    // under changing weights
+    pub(crate) fn set_weight(&mut self, edge_id: EdgeId, weight: W) {
+        self.ig.weight[self.ig.arcs[edge_id.index()]] = weight;
+    }
+
+    // the edges of a shortest path from the last source to u, None if u is not reached
+    pub fn path(&self, u: NodeId) -> Option<Vec<EdgeId>> {
+        if !self.reached.get(u.index()) {
+            return None;
+        }
+        let mut path = Vec::new();
+        let mut v = u;
+        while self.prev[v.index()] != usize::MAX {
+            let arc = self.prev[v.index()];
+            path.push(self.ig.edge_ids[arc]);
+            // the tail of an arc is the node whose range contains it
+            v = NodeId(self.ig.start.partition_point(|&start| start <= arc) - 1);
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    // the last edge of a shortest path from the last source to u, None if u is the source or not reached
+    pub(crate) fn last_edge(&self, u: NodeId) -> Option<EdgeId> {
+        let arc = self.prev[u.index()];
+        (self.reached.get(u.index()) && arc != usize::MAX).then(|| self.ig.edge_ids[arc])
     }
 
     fn run(&mut self, source: NodeId) -> Result<(), Status> {
@@ -52,8 +88,8 @@ where
         let mut heap = BinaryHeap::new();
         heap.push((Reverse(W::zero()), source));
 
-        let mut prev = vec![INVALID_NODE_ID; self.ig.num_nodes];
         self.reached.clear();
+        self.prev.fill(usize::MAX);
         self.distances.fill(W::max_value());
         self.distances[source.index()] = W::zero();
 
@@ -74,7 +110,7 @@ where
                 let new_dist = d.0 + w;
                 if new_dist < self.distances[to.index()] {
                     self.distances[to.index()] = new_dist;
-                    prev[to.index()] = u;
+                    self.prev[to.index()] = edge_id.index();
                     heap.push((Reverse(new_dist), to));
                 }
             }
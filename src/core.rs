@@ -1 +1,3 @@
-pub mod numeric;
\ No newline at end of file
+pub mod numeric;
+#[cfg(test)]
+pub(crate) mod random;
@@ -0,0 +1,5 @@
+// a linear congruential generator, so that the randomized unit tests are reproducible without a dependency
+pub(crate) fn lcg_next(x: &mut u64) -> u64 {
+    *x = x.wrapping_mul(6364136223846793005).wrapping_add(1);
+    *x >> 33
+}
@@ -1,12 +1,18 @@
 pub mod bit_vector;
+pub mod fibonacci_heap;
+pub mod link_cut_tree;
 pub mod radix_heap;
 pub mod simple_queue;
 pub mod union_find;
 pub mod skew_heap;
 pub mod rollback_union_find;
+pub mod undirected_link_cut_tree;
 
 pub use self::bit_vector::BitVector;
+pub use self::fibonacci_heap::FibonacciHeap;
+pub use self::link_cut_tree::LinkCutTree;
 pub use self::radix_heap::RadixHeap;
 pub use self::simple_queue::SimpleQueue;
 pub use self::union_find::UnionFind;
-pub use self::skew_heap::SkewHeap;
\ No newline at end of file
+pub use self::skew_heap::SkewHeap;
+pub use self::undirected_link_cut_tree::UndirectedLinkCutTree;
\ No newline at end of file
@@ -0,0 +1,269 @@
+const NIL: usize = usize::MAX;
+
+// Fibonacci heap (Fredman and Tarjan) over the items 0..n, a min-heap with decrease-key
+// every item is in the heap at most once; trees are circular doubly linked lists of siblings
+pub struct FibonacciHeap<K> {
+    key: Vec<K>,
+    parent: Vec<usize>,
+    child: Vec<usize>, // any child, the children form a circular list
+    left: Vec<usize>,
+    right: Vec<usize>,
+    degree: Vec<usize>,
+    marked: Vec<bool>, // lost a child since it became the child of its parent
+    in_heap: Vec<bool>,
+    min: usize,
+    len: usize,
+    by_degree: Vec<usize>,
+}
+
+impl<K> FibonacciHeap<K>
+where
+    K: Copy + Ord,
+{
+    pub fn new(num_items: usize) -> Self {
+        Self {
+            key: Vec::with_capacity(num_items),
+            parent: vec![NIL; num_items],
+            child: vec![NIL; num_items],
+            left: (0..num_items).collect(),
+            right: (0..num_items).collect(),
+            degree: vec![0; num_items],
+            marked: vec![false; num_items],
+            in_heap: vec![false; num_items],
+            min: NIL,
+            len: 0,
+            by_degree: Vec::new(),
+        }
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    #[inline]
+    pub fn contains(&self, u: usize) -> bool {
+        self.in_heap[u]
+    }
+
+    // O(n)
+    pub fn clear(&mut self) {
+        self.in_heap.fill(false);
+        self.min = NIL;
+        self.len = 0;
+    }
+
+    // O(1)
+    // u must not be in the heap
+    pub fn push(&mut self, u: usize, key: K) {
+        debug_assert!(!self.in_heap[u], "u must not be in the heap");
+        if u >= self.key.len() {
+            // the keys of items that were never pushed are never read
+            self.key.resize(u + 1, key);
+        }
+        self.key[u] = key;
+        self.parent[u] = NIL;
+        self.child[u] = NIL;
+        self.degree[u] = 0;
+        self.marked[u] = false;
+        self.in_heap[u] = true;
+        self.len += 1;
+        self.add_root(u);
+    }
+
+    // O(1)
+    pub fn peek(&self) -> Option<(K, usize)> {
+        (self.min != NIL).then(|| (self.key[self.min], self.min))
+    }
+
+    // O(log n) amortised
+    pub fn pop(&mut self) -> Option<(K, usize)> {
+        let z = self.min;
+        if z == NIL {
+            return None;
+        }
+
+        // the children of z become roots
+        let mut c = self.child[z];
+        while c != NIL {
+            let next = if self.right[c] == c { NIL } else { self.right[c] };
+            self.unlink(c);
+            self.parent[c] = NIL;
+            self.marked[c] = false;
+            self.add_root(c);
+            c = next;
+        }
+        self.child[z] = NIL;
+
+        let next = self.right[z];
+        self.unlink(z);
+        self.in_heap[z] = false;
+        self.len -= 1;
+        self.min = if next == z { NIL } else { next };
+        if self.min != NIL {
+            self.consolidate();
+        }
+        Some((self.key[z], z))
+    }
+
+    // O(1) amortised
+    // u must be in the heap and key must not exceed its current key
+    pub fn decrease_key(&mut self, u: usize, key: K) {
+        debug_assert!(self.in_heap[u] && key <= self.key[u], "key must not increase");
+        self.key[u] = key;
+        let p = self.parent[u];
+        if p != NIL && key < self.key[p] {
+            self.cut(u);
+            // cascading cut: a node losing its second child is cut as well
+            let mut y = p;
+            while self.parent[y] != NIL {
+                if !self.marked[y] {
+                    self.marked[y] = true;
+                    break;
+                }
+                let z = self.parent[y];
+                self.cut(y);
+                y = z;
+            }
+        }
+        if key < self.key[self.min] {
+            self.min = u;
+        }
+    }
+
+    // adds u to the root list, as the minimum if its key is smaller
+    fn add_root(&mut self, u: usize) {
+        if self.min == NIL {
+            self.left[u] = u;
+            self.right[u] = u;
+            self.min = u;
+        } else {
+            self.insert_after(self.min, u);
+            if self.key[u] < self.key[self.min] {
+                self.min = u;
+            }
+        }
+    }
+
+    // moves u from the children of its parent to the root list
+    fn cut(&mut self, u: usize) {
+        let p = self.parent[u];
+        if self.child[p] == u {
+            self.child[p] = if self.right[u] == u { NIL } else { self.right[u] };
+        }
+        self.unlink(u);
+        self.degree[p] -= 1;
+        self.parent[u] = NIL;
+        self.marked[u] = false;
+        self.add_root(u);
+    }
+
+    // links the roots of equal degree until all degrees differ, then finds the minimum
+    fn consolidate(&mut self) {
+        let mut roots = Vec::new();
+        let mut x = self.min;
+        loop {
+            roots.push(x);
+            x = self.right[x];
+            if x == self.min {
+                break;
+            }
+        }
+
+        for mut x in roots {
+            self.unlink(x);
+            let mut d = self.degree[x];
+            loop {
+                if d >= self.by_degree.len() {
+                    self.by_degree.resize(d + 1, NIL);
+                }
+                let y = self.by_degree[d];
+                if y == NIL {
+                    break;
+                }
+                self.by_degree[d] = NIL;
+                let (root, other) = if self.key[y] < self.key[x] { (y, x) } else { (x, y) };
+                self.link(other, root);
+                x = root;
+                d += 1;
+            }
+            self.by_degree[d] = x;
+        }
+
+        self.min = NIL;
+        for d in 0..self.by_degree.len() {
+            let x = std::mem::replace(&mut self.by_degree[d], NIL);
+            if x != NIL {
+                self.add_root(x);
+            }
+        }
+    }
+
+    // makes the root y a child of the root x
+    fn link(&mut self, y: usize, x: usize) {
+        self.parent[y] = x;
+        self.marked[y] = false;
+        if self.child[x] == NIL {
+            self.left[y] = y;
+            self.right[y] = y;
+            self.child[x] = y;
+        } else {
+            self.insert_after(self.child[x], y);
+        }
+        self.degree[x] += 1;
+    }
+
+    #[inline]
+    fn insert_after(&mut self, a: usize, u: usize) {
+        let b = self.right[a];
+        self.left[u] = a;
+        self.right[u] = b;
+        self.right[a] = u;
+        self.left[b] = u;
+    }
+
+    #[inline]
+    fn unlink(&mut self, u: usize) {
+        let (l, r) = (self.left[u], self.right[u]);
+        self.right[l] = r;
+        self.left[r] = l;
+        self.left[u] = u;
+        self.right[u] = u;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test() {
+        let mut heap = FibonacciHeap::<i32>::new(6);
+        for (u, key) in [(0, 5), (1, 3), (2, 8), (3, 1), (4, 9), (5, 7)] {
+            heap.push(u, key);
+        }
+        assert_eq!(heap.pop(), Some((1, 3)));
+
+        // the remaining items now form trees, so decreasing keys cuts them from their parents
+        heap.decrease_key(4, 2);
+        heap.decrease_key(2, 1);
+        heap.decrease_key(5, 0);
+        assert_eq!(heap.len(), 5);
+        assert_eq!(heap.pop(), Some((0, 5)));
+        assert_eq!(heap.pop(), Some((1, 2)));
+        assert_eq!(heap.pop(), Some((2, 4)));
+        assert!(!heap.contains(4) && heap.contains(1));
+
+        heap.push(3, 4);
+        assert_eq!(heap.pop(), Some((3, 1)));
+        assert_eq!(heap.pop(), Some((4, 3)));
+        assert_eq!(heap.pop(), Some((5, 0)));
+        assert_eq!(heap.pop(), None);
+        assert!(heap.is_empty());
+    }
+}
@@ -0,0 +1,312 @@
+use core::ops::{Add, Sub};
+use num_traits::Zero;
+
+const NIL: usize = usize::MAX;
+
+// link-cut tree (Sleator and Tarjan) over a forest of rooted trees
+// every node except the roots has a key, and path queries cover the path from a node up to, but excluding, its root
+pub struct LinkCutTree<K> {
+    left: Vec<usize>,
+    right: Vec<usize>,
+    parent: Vec<usize>, // parent in the splay tree, or the path-parent for the root of a splay tree
+    key: Vec<K>,
+    min: Vec<(K, usize)>, // minimum key in the splay subtree and the node nearest the tree root attaining it
+    lazy: Vec<K>,         // pending amount to subtract from all keys in the splay subtree
+    stack: Vec<usize>,
+}
+
+impl<K> LinkCutTree<K>
+where
+    K: Copy + Ord + Zero + Add<Output = K> + Sub<Output = K>,
+{
+    pub fn new(num_nodes: usize) -> Self {
+        Self {
+            left: vec![NIL; num_nodes],
+            right: vec![NIL; num_nodes],
+            parent: vec![NIL; num_nodes],
+            key: vec![K::zero(); num_nodes],
+            min: (0..num_nodes).map(|u| (K::zero(), u)).collect(),
+            lazy: vec![K::zero(); num_nodes],
+            stack: Vec::new(),
+        }
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.key.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.key.is_empty()
+    }
+
+    // O(n)
+    // makes every node a singleton tree
+    pub fn reset(&mut self) {
+        self.left.fill(NIL);
+        self.right.fill(NIL);
+        self.parent.fill(NIL);
+        self.key.fill(K::zero());
+        self.lazy.fill(K::zero());
+        for (u, min) in self.min.iter_mut().enumerate() {
+            *min = (K::zero(), u);
+        }
+    }
+
+    // O(log n) amortised
+    pub fn find_root(&mut self, u: usize) -> usize {
+        self.access(u);
+        let mut root = u;
+        loop {
+            self.push(root);
+            if self.left[root] == NIL {
+                break;
+            }
+            root = self.left[root];
+        }
+        self.splay(root);
+        root
+    }
+
+    // O(log n) amortised
+    // u must be a root; v becomes its parent and key becomes the key of u
+    pub fn link(&mut self, u: usize, v: usize, key: K) {
+        self.access(u);
+        debug_assert!(self.left[u] == NIL, "u must be a root");
+        self.key[u] = key;
+        self.update(u);
+        self.parent[u] = v;
+    }
+
+    // O(log n) amortised
+    // detaches u from its parent and returns the key of u
+    pub fn cut(&mut self, u: usize) -> K {
+        self.access(u);
+        let l = self.left[u];
+        debug_assert!(l != NIL, "u must not be a root");
+        self.parent[l] = NIL;
+        self.left[u] = NIL;
+        self.update(u);
+        self.key[u]
+    }
+
+    // O(log n) amortised
+    pub fn key(&mut self, u: usize) -> K {
+        self.access(u);
+        self.key[u]
+    }
+
+    // O(log n) amortised
+    // minimum key on the path from u to its root, or None if u is a root
+    pub fn path_min(&mut self, u: usize) -> Option<(K, usize)> {
+        let root = self.find_root(u);
+        let path = self.right[root];
+        (path != NIL).then(|| self.min[path])
+    }
+
+    // O(log n) amortised
+    // subtracts delta from every key on the path from u to its root
+    pub fn path_sub(&mut self, u: usize, delta: K) {
+        let root = self.find_root(u);
+        let path = self.right[root];
+        if path != NIL {
+            self.apply(path, delta);
+        }
+    }
+
+    // makes the path from the root to u preferred, leaving u at the root of its splay tree without a right child
+    fn access(&mut self, u: usize) {
+        let mut last = NIL;
+        let mut x = u;
+        while x != NIL {
+            self.splay(x);
+            self.right[x] = last;
+            self.update(x);
+            last = x;
+            x = self.parent[x];
+        }
+        self.splay(u);
+    }
+
+    #[inline]
+    fn is_splay_root(&self, x: usize) -> bool {
+        let p = self.parent[x];
+        p == NIL || (self.left[p] != x && self.right[p] != x)
+    }
+
+    #[inline]
+    fn apply(&mut self, x: usize, delta: K) {
+        self.key[x] = self.key[x] - delta;
+        self.min[x].0 = self.min[x].0 - delta;
+        self.lazy[x] = self.lazy[x] + delta;
+    }
+
+    #[inline]
+    fn push(&mut self, x: usize) {
+        let delta = self.lazy[x];
+        if !delta.is_zero() {
+            for child in [self.left[x], self.right[x]] {
+                if child != NIL {
+                    self.apply(child, delta);
+                }
+            }
+            self.lazy[x] = K::zero();
+        }
+    }
+
+    // nodes nearer the tree root are on the left, so ties are broken towards the left
+    #[inline]
+    fn update(&mut self, x: usize) {
+        let mut min = (self.key[x], x);
+        if self.left[x] != NIL && self.min[self.left[x]].0 <= min.0 {
+            min = self.min[self.left[x]];
+        }
+        if self.right[x] != NIL && self.min[self.right[x]].0 < min.0 {
+            min = self.min[self.right[x]];
+        }
+        self.min[x] = min;
+    }
+
+    fn rotate(&mut self, x: usize) {
+        let p = self.parent[x];
+        let g = self.parent[p];
+        if !self.is_splay_root(p) {
+            if self.left[g] == p {
+                self.left[g] = x;
+            } else {
+                self.right[g] = x;
+            }
+        }
+        self.parent[x] = g;
+
+        if self.left[p] == x {
+            let b = self.right[x];
+            self.left[p] = b;
+            if b != NIL {
+                self.parent[b] = p;
+            }
+            self.right[x] = p;
+        } else {
+            let b = self.left[x];
+            self.right[p] = b;
+            if b != NIL {
+                self.parent[b] = p;
+            }
+            self.left[x] = p;
+        }
+        self.parent[p] = x;
+        self.update(p);
+        self.update(x);
+    }
+
+    fn splay(&mut self, x: usize) {
+        // push pending subtractions from the root of the splay tree down to x
+        let mut stack = std::mem::take(&mut self.stack);
+        stack.push(x);
+        let mut y = x;
+        while !self.is_splay_root(y) {
+            y = self.parent[y];
+            stack.push(y);
+        }
+        while let Some(y) = stack.pop() {
+            self.push(y);
+        }
+        self.stack = stack;
+
+        while !self.is_splay_root(x) {
+            let p = self.parent[x];
+            if !self.is_splay_root(p) {
+                let g = self.parent[p];
+                if (self.left[g] == p) == (self.left[p] == x) {
+                    self.rotate(p);
+                } else {
+                    self.rotate(x);
+                }
+            }
+            self.rotate(x);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::random::lcg_next;
+
+    // the forest as parent pointers, with the key of every node
+    struct NaiveForest {
+        parent: Vec<Option<usize>>,
+        key: Vec<i64>,
+    }
+
+    impl NaiveForest {
+        fn root(&self, mut u: usize) -> usize {
+            while let Some(p) = self.parent[u] {
+                u = p;
+            }
+            u
+        }
+
+        // the nodes from u up to, but excluding, its root
+        fn path(&self, mut u: usize) -> Vec<usize> {
+            let mut path = Vec::new();
+            while let Some(p) = self.parent[u] {
+                path.push(u);
+                u = p;
+            }
+            path
+        }
+
+        // ties go to the node nearest the root
+        fn path_min(&self, u: usize) -> Option<(i64, usize)> {
+            self.path(u)
+                .into_iter()
+                .rev()
+                .map(|v| (self.key[v], v))
+                .min_by_key(|&(key, _)| key)
+        }
+    }
+
+    #[test]
+    fn against_naive_forest() {
+        let mut seed = 2718281828_u64;
+        for n in [1, 2, 5, 30] {
+            let mut lct = LinkCutTree::<i64>::new(n);
+            let mut naive = NaiveForest { parent: vec![None; n], key: vec![0; n] };
+            for _ in 0..3000 {
+                let u = lcg_next(&mut seed) as usize % n;
+                let v = lcg_next(&mut seed) as usize % n;
+                match lcg_next(&mut seed) % 6 {
+                    0 => {
+                        if naive.parent[u].is_none() && naive.root(v) != u {
+                            let key = (lcg_next(&mut seed) % 100) as i64;
+                            lct.link(u, v, key);
+                            naive.parent[u] = Some(v);
+                            naive.key[u] = key;
+                        }
+                    }
+                    1 => {
+                        if naive.parent[u].is_some() {
+                            assert_eq!(lct.cut(u), naive.key[u]);
+                            naive.parent[u] = None;
+                        }
+                    }
+                    2 => assert_eq!(lct.find_root(u), naive.root(u)),
+                    3 => assert_eq!(lct.path_min(u), naive.path_min(u)),
+                    4 => {
+                        let delta = (lcg_next(&mut seed) % 10) as i64;
+                        lct.path_sub(u, delta);
+                        for w in naive.path(u) {
+                            naive.key[w] -= delta;
+                        }
+                    }
+                    _ => assert_eq!(lct.key(u), naive.key[u]),
+                }
+            }
+
+            lct.reset();
+            assert!((0..n).all(|u| lct.find_root(u) == u && lct.path_min(u).is_none()));
+        }
+    }
+}
@@ -0,0 +1,404 @@
+use core::ops::{Add, Sub};
+use num_traits::Zero;
+
+const NIL: usize = usize::MAX;
+
+// link-cut tree (Sleator and Tarjan) over a forest of unrooted trees, rerooted by evert
+// edge e is the node num_nodes + e and carries a key in each direction, so that reversing a path swaps the keys of
+// its edges; path queries read the keys in the direction from one end of the path to the other
+pub struct UndirectedLinkCutTree<K> {
+    num_nodes: usize,
+    left: Vec<usize>,
+    right: Vec<usize>,
+    parent: Vec<usize>, // parent in the splay tree, or the path-parent for the root of a splay tree
+    flip: Vec<bool>,    // the splay subtree is to be reversed
+    up: Vec<K>,         // key of an edge towards the root of its tree
+    down: Vec<K>,       // key of an edge away from the root of its tree
+    // minimum key towards and away from the root over the edges in the splay subtree and an edge attaining it,
+    // NIL if there is none
+    min_up: Vec<(K, usize)>,
+    min_down: Vec<(K, usize)>,
+    // pending amounts to move from the keys towards the root to those away from it and back in the splay subtree
+    lazy: Vec<(K, K)>,
+    ends: Vec<(usize, usize)>,
+    stack: Vec<usize>,
+}
+
+impl<K> UndirectedLinkCutTree<K>
+where
+    K: Copy + Ord + Zero + Add<Output = K> + Sub<Output = K>,
+{
+    pub fn new(num_nodes: usize, num_edges: usize) -> Self {
+        let len = num_nodes + num_edges;
+        Self {
+            num_nodes,
+            left: vec![NIL; len],
+            right: vec![NIL; len],
+            parent: vec![NIL; len],
+            flip: vec![false; len],
+            up: vec![K::zero(); len],
+            down: vec![K::zero(); len],
+            min_up: vec![(K::zero(), NIL); len],
+            min_down: vec![(K::zero(), NIL); len],
+            lazy: vec![(K::zero(), K::zero()); len],
+            ends: vec![(NIL, NIL); num_edges],
+            stack: Vec::new(),
+        }
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.num_nodes
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.num_nodes == 0
+    }
+
+    // O(log n) amortised
+    pub fn connected(&mut self, u: usize, v: usize) -> bool {
+        self.find_root(u) == self.find_root(v)
+    }
+
+    // O(log n) amortised
+    // u and v must lie in different trees and e must not be linked; e joins them with key forward in the direction
+    // from u to v and key backward in the direction from v to u
+    pub fn link(&mut self, e: usize, u: usize, v: usize, forward: K, backward: K) {
+        debug_assert!(self.ends[e].0 == NIL && !self.connected(u, v), "u and v must lie in different trees");
+        let x = self.num_nodes + e;
+        self.up[x] = forward;
+        self.down[x] = backward;
+        self.flip[x] = false;
+        self.lazy[x] = (K::zero(), K::zero());
+        self.update(x);
+        self.ends[e] = (u, v);
+
+        self.evert(u);
+        self.parent[u] = x;
+        self.parent[x] = v;
+    }
+
+    // O(log n) amortised
+    // removes e and returns its keys in the direction from u to v and back, where u and v are the nodes it linked
+    pub fn cut(&mut self, e: usize) -> (K, K) {
+        let keys = self.keys(e);
+        let (x, v) = (self.num_nodes + e, self.ends[e].1);
+        // after keys, the path from u to x is the splay tree of x, with u on the left
+        let l = self.left[x];
+        self.parent[l] = NIL;
+        self.left[x] = NIL;
+        self.update(x);
+        // x is now the root of the tree of v
+        self.access(v);
+        let l = self.left[v];
+        self.parent[l] = NIL;
+        self.left[v] = NIL;
+        self.update(v);
+        self.ends[e] = (NIL, NIL);
+        keys
+    }
+
+    // O(log n) amortised
+    // the keys of e in the direction from u to v and back, where u and v are the nodes it linked
+    pub fn keys(&mut self, e: usize) -> (K, K) {
+        let (x, u) = (self.num_nodes + e, self.ends[e].0);
+        debug_assert!(u != NIL, "e must be linked");
+        self.evert(u);
+        self.access(x);
+        (self.down[x], self.up[x])
+    }
+
+    // O(log n) amortised
+    // minimum key in the direction from u to v on the path between them and an edge attaining it, or None if u == v;
+    // u and v must lie in the same tree
+    pub fn path_min(&mut self, u: usize, v: usize) -> Option<(K, usize)> {
+        debug_assert!(self.connected(u, v), "u and v must lie in the same tree");
+        self.evert(v);
+        self.access(u);
+        let (key, x) = self.min_up[u];
+        (x != NIL).then(|| (key, x - self.num_nodes))
+    }
+
+    // O(log n) amortised
+    // subtracts delta from the keys in the direction from u to v on the path between them and adds it to the keys in
+    // the direction from v to u; u and v must lie in the same tree
+    pub fn path_sub(&mut self, u: usize, v: usize, delta: K) {
+        debug_assert!(self.connected(u, v), "u and v must lie in the same tree");
+        self.evert(v);
+        self.access(u);
+        self.shift(u, (delta, K::zero()));
+    }
+
+    fn find_root(&mut self, u: usize) -> usize {
+        self.access(u);
+        let mut root = u;
+        loop {
+            self.push(root);
+            if self.left[root] == NIL {
+                break;
+            }
+            root = self.left[root];
+        }
+        self.splay(root);
+        root
+    }
+
+    // makes u the root of its tree
+    fn evert(&mut self, u: usize) {
+        self.access(u);
+        self.reverse(u);
+    }
+
+    // makes the path from the root to u preferred, leaving u at the root of its splay tree without a right child
+    fn access(&mut self, u: usize) {
+        let mut last = NIL;
+        let mut x = u;
+        while x != NIL {
+            self.splay(x);
+            self.right[x] = last;
+            self.update(x);
+            last = x;
+            x = self.parent[x];
+        }
+        self.splay(u);
+    }
+
+    #[inline]
+    fn is_splay_root(&self, x: usize) -> bool {
+        let p = self.parent[x];
+        p == NIL || (self.left[p] != x && self.right[p] != x)
+    }
+
+    // moves a from the keys towards the root to those away from it, and b back
+    #[inline]
+    fn shift(&mut self, x: usize, (a, b): (K, K)) {
+        if x >= self.num_nodes {
+            self.up[x] = self.up[x] + b - a;
+            self.down[x] = self.down[x] + a - b;
+        }
+        if self.min_up[x].1 != NIL {
+            self.min_up[x].0 = self.min_up[x].0 + b - a;
+        }
+        if self.min_down[x].1 != NIL {
+            self.min_down[x].0 = self.min_down[x].0 + a - b;
+        }
+        self.lazy[x] = (self.lazy[x].0 + a, self.lazy[x].1 + b);
+    }
+
+    // reverses the path in the splay subtree of x, which swaps the directions of its edges
+    #[inline]
+    fn reverse(&mut self, x: usize) {
+        std::mem::swap(&mut self.left[x], &mut self.right[x]);
+        std::mem::swap(&mut self.up[x], &mut self.down[x]);
+        std::mem::swap(&mut self.min_up[x], &mut self.min_down[x]);
+        let (a, b) = self.lazy[x];
+        self.lazy[x] = (b, a);
+        self.flip[x] = !self.flip[x];
+    }
+
+    #[inline]
+    fn push(&mut self, x: usize) {
+        if self.flip[x] {
+            for child in [self.left[x], self.right[x]] {
+                if child != NIL {
+                    self.reverse(child);
+                }
+            }
+            self.flip[x] = false;
+        }
+        let lazy = self.lazy[x];
+        if !lazy.0.is_zero() || !lazy.1.is_zero() {
+            for child in [self.left[x], self.right[x]] {
+                if child != NIL {
+                    self.shift(child, lazy);
+                }
+            }
+            self.lazy[x] = (K::zero(), K::zero());
+        }
+    }
+
+    #[inline]
+    fn update(&mut self, x: usize) {
+        let own = if x >= self.num_nodes { x } else { NIL };
+        let (mut min_up, mut min_down) = ((self.up[x], own), (self.down[x], own));
+        for child in [self.left[x], self.right[x]] {
+            if child != NIL {
+                if self.min_up[child].1 != NIL && (min_up.1 == NIL || self.min_up[child].0 < min_up.0) {
+                    min_up = self.min_up[child];
+                }
+                if self.min_down[child].1 != NIL && (min_down.1 == NIL || self.min_down[child].0 < min_down.0) {
+                    min_down = self.min_down[child];
+                }
+            }
+        }
+        self.min_up[x] = min_up;
+        self.min_down[x] = min_down;
+    }
+
+    fn rotate(&mut self, x: usize) {
+        let p = self.parent[x];
+        let g = self.parent[p];
+        if !self.is_splay_root(p) {
+            if self.left[g] == p {
+                self.left[g] = x;
+            } else {
+                self.right[g] = x;
+            }
+        }
+        self.parent[x] = g;
+
+        if self.left[p] == x {
+            let b = self.right[x];
+            self.left[p] = b;
+            if b != NIL {
+                self.parent[b] = p;
+            }
+            self.right[x] = p;
+        } else {
+            let b = self.left[x];
+            self.right[p] = b;
+            if b != NIL {
+                self.parent[b] = p;
+            }
+            self.left[x] = p;
+        }
+        self.parent[p] = x;
+        self.update(p);
+        self.update(x);
+    }
+
+    fn splay(&mut self, x: usize) {
+        // push pending reversals and shifts from the root of the splay tree down to x
+        let mut stack = std::mem::take(&mut self.stack);
+        stack.push(x);
+        let mut y = x;
+        while !self.is_splay_root(y) {
+            y = self.parent[y];
+            stack.push(y);
+        }
+        while let Some(y) = stack.pop() {
+            self.push(y);
+        }
+        self.stack = stack;
+
+        while !self.is_splay_root(x) {
+            let p = self.parent[x];
+            if !self.is_splay_root(p) {
+                let g = self.parent[p];
+                if (self.left[g] == p) == (self.left[p] == x) {
+                    self.rotate(p);
+                } else {
+                    self.rotate(x);
+                }
+            }
+            self.rotate(x);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::random::lcg_next;
+
+    // the forest as a list of edges (e, u, v, key from u to v, key from v to u)
+    struct NaiveForest {
+        num_nodes: usize,
+        edges: Vec<(usize, usize, usize, i64, i64)>,
+    }
+
+    impl NaiveForest {
+        // the edges on the path from u to v, each with the index of its key in the direction from u to v
+        fn path(&self, u: usize, v: usize) -> Option<Vec<(usize, bool)>> {
+            let mut prev = vec![None; self.num_nodes];
+            let mut seen = vec![false; self.num_nodes];
+            let mut stack = vec![u];
+            seen[u] = true;
+            while let Some(x) = stack.pop() {
+                for (i, &(_, a, b, _, _)) in self.edges.iter().enumerate() {
+                    for (from, to, forward) in [(a, b, true), (b, a, false)] {
+                        if from == x && !seen[to] {
+                            seen[to] = true;
+                            prev[to] = Some((i, forward, from));
+                            stack.push(to);
+                        }
+                    }
+                }
+            }
+            if !seen[v] {
+                return None;
+            }
+            let mut path = Vec::new();
+            let mut x = v;
+            while let Some((i, forward, from)) = prev[x] {
+                path.push((i, forward));
+                x = from;
+            }
+            Some(path)
+        }
+
+        fn key(&self, (i, forward): (usize, bool)) -> i64 {
+            if forward { self.edges[i].3 } else { self.edges[i].4 }
+        }
+    }
+
+    #[test]
+    fn against_naive_forest() {
+        let mut seed = 1414213562_u64;
+        for (n, m) in [(1, 1), (2, 3), (6, 10), (30, 40)] {
+            let mut lct = UndirectedLinkCutTree::<i64>::new(n, m);
+            let mut naive = NaiveForest { num_nodes: n, edges: Vec::new() };
+            for _ in 0..3000 {
+                let u = lcg_next(&mut seed) as usize % n;
+                let v = lcg_next(&mut seed) as usize % n;
+                let path = naive.path(u, v);
+                match lcg_next(&mut seed) % 5 {
+                    0 => {
+                        let e = lcg_next(&mut seed) as usize % m;
+                        if path.is_none() && naive.edges.iter().all(|&(f, _, _, _, _)| f != e) {
+                            let (forward, backward) =
+                                ((lcg_next(&mut seed) % 100) as i64, (lcg_next(&mut seed) % 100) as i64);
+                            lct.link(e, u, v, forward, backward);
+                            naive.edges.push((e, u, v, forward, backward));
+                        }
+                    }
+                    1 => {
+                        if !naive.edges.is_empty() {
+                            let i = lcg_next(&mut seed) as usize % naive.edges.len();
+                            let (e, _, _, forward, backward) = naive.edges.swap_remove(i);
+                            assert_eq!(lct.cut(e), (forward, backward));
+                        }
+                    }
+                    2 => assert_eq!(lct.connected(u, v), path.is_some()),
+                    3 => {
+                        if let Some(path) = path {
+                            let expected = path.iter().map(|&step| naive.key(step)).min();
+                            let actual = lct.path_min(u, v);
+                            assert_eq!(actual.map(|(key, _)| key), expected);
+                            if let Some((key, e)) = actual {
+                                let step = path.iter().find(|&&(i, _)| naive.edges[i].0 == e).unwrap();
+                                assert_eq!(naive.key(*step), key);
+                            }
+                        }
+                    }
+                    _ => {
+                        if let Some(path) = path {
+                            let delta = (lcg_next(&mut seed) % 10) as i64;
+                            lct.path_sub(u, v, delta);
+                            for (i, forward) in path {
+                                let (_, _, _, ref mut a, ref mut b) = naive.edges[i];
+                                let (from, to) = if forward { (a, b) } else { (b, a) };
+                                *from -= delta;
+                                *to += delta;
+                            }
+                        }
+                    }
+                }
+            }
+            for &(e, _, _, forward, backward) in naive.edges.iter() {
+                assert_eq!(lct.keys(e), (forward, backward));
+            }
+        }
+    }
+}
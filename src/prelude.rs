@@ -1,7 +1,16 @@
 pub use crate::graph::{bipartite_graph::BipartiteGraph, graph::Graph, ids};
 
+pub use crate::algorithms::closure::prelude as closure;
+pub use crate::algorithms::connectivity::prelude as connectivity;
+pub use crate::algorithms::densest_subgraph::prelude as densest_subgraph;
+pub use crate::algorithms::disjoint_paths::prelude as disjoint_paths;
+pub use crate::algorithms::dynamic_flow::prelude as dynamic_flow;
+pub use crate::algorithms::generalized_flow::prelude as generalized_flow;
+pub use crate::algorithms::gomory_hu::prelude as gomory_hu;
 pub use crate::algorithms::maximum_flow::prelude as maximum_flow;
 pub use crate::algorithms::maximum_matching::prelude as matching;
 pub use crate::algorithms::minimum_cost_flow::prelude as minimum_cost_flow;
+pub use crate::algorithms::minimum_cut::prelude as minimum_cut;
+pub use crate::algorithms::multi_commodity_flow::prelude as multi_commodity_flow;
 pub use crate::algorithms::shortest_path::prelude as shortest_path;
 pub use crate::algorithms::branching::prelude as branching;
@@ -0,0 +1,89 @@
+mod common;
+
+use common::lcg_next;
+use network_algorithms::algorithms::closure::prelude::*;
+use network_algorithms::algorithms::maximum_flow::prelude::{BoykovKolmogorov, Dinic, PushRelabelHighestLabel};
+use network_algorithms::{Graph, direction::Directed};
+use rstest::rstest;
+
+fn random_graph(seed: &mut u64, n: usize, m: usize) -> Graph<Directed, i64, ()> {
+    let mut graph = Graph::<Directed, i64, ()>::default();
+    let nodes = graph.add_nodes_with((0..n).map(|_| (lcg_next(seed) % 21) as i64 - 10));
+    for _ in 0..m {
+        let (u, v) = ((lcg_next(seed) % n as u64) as usize, (lcg_next(seed) % n as u64) as usize);
+        graph.add_edge(nodes[u], nodes[v], ());
+    }
+    graph
+}
+
+fn is_closure(graph: &Graph<Directed, i64, ()>, side: &[bool]) -> bool {
+    graph.edges().all(|e| !side[e.u.index()] || side[e.v.index()])
+}
+
+fn profit(graph: &Graph<Directed, i64, ()>, side: &[bool]) -> i64 {
+    graph
+        .nodes()
+        .zip(side)
+        .filter(|(_, s)| **s)
+        .map(|(node, _)| node.data)
+        .sum()
+}
+
+fn brute_force(graph: &Graph<Directed, i64, ()>) -> i64 {
+    let n = graph.num_nodes();
+    (0..1_usize << n)
+        .map(|mask| (0..n).map(|u| (mask >> u) & 1 == 1).collect::<Vec<_>>())
+        .filter(|side| is_closure(graph, side))
+        .map(|side| profit(graph, &side))
+        .max()
+        .unwrap()
+}
+
+#[rstest]
+#[case::sparse(10, 8)]
+#[case::dense(10, 30)]
+#[case::no_edges(6, 0)]
+fn random(#[case] n: usize, #[case] m: usize) {
+    let mut seed = 3141592653_u64 + (n * m) as u64;
+    for _ in 0..30 {
+        let graph = random_graph(&mut seed, n, m);
+        let expected = brute_force(&graph);
+
+        let results = [
+            MaximumWeightClosure::<i64, Dinic<i64>>::new(&graph).solve().unwrap(),
+            MaximumWeightClosure::<i64, BoykovKolmogorov<i64>>::new(&graph)
+                .solve()
+                .unwrap(),
+            MaximumWeightClosure::<i64, PushRelabelHighestLabel<i64>>::new(&graph)
+                .solve()
+                .unwrap(),
+        ];
+        for (value, side) in results {
+            assert_eq!(value, expected);
+            assert!(is_closure(&graph, &side));
+            assert_eq!(profit(&graph, &side), expected);
+        }
+    }
+}
+
+#[test]
+fn projects_and_tools() {
+    // projects 0 and 1 pay 10 and 4, tools 2 and 3 cost 6 and 5; project 0 needs both tools, project 1 only tool 3
+    let mut graph = Graph::<Directed, (&str, i64), ()>::default();
+    let nodes = graph.add_nodes_with([("a", 10), ("b", 4), ("x", -6), ("y", -5)]);
+    graph.add_edge(nodes[0], nodes[2], ());
+    graph.add_edge(nodes[0], nodes[3], ());
+    graph.add_edge(nodes[1], nodes[3], ());
+
+    let (value, side) = MaximumWeightClosure::<i64>::new_with(&graph, |node| node.data.1)
+        .solve()
+        .unwrap();
+    assert_eq!((value, side), (3, vec![true, true, true, true]));
+
+    // the smallest optimal closure is returned
+    let mut graph = Graph::<Directed, i64, ()>::default();
+    assert_eq!(MaximumWeightClosure::<i64>::new(&graph).solve().unwrap(), (0, vec![]));
+    let nodes = graph.add_nodes_with([5, -5, 0]);
+    graph.add_edge(nodes[0], nodes[1], ());
+    assert_eq!(MaximumWeightClosure::<i64>::new(&graph).solve().unwrap(), (0, vec![false, false, false]));
+}
@@ -0,0 +1,195 @@
+mod common;
+
+use common::lcg_next;
+use network_algorithms::algorithms::connectivity::prelude::*;
+use network_algorithms::algorithms::maximum_flow::prelude::*;
+use network_algorithms::{
+    Graph,
+    direction::{Directed, Direction, Undirected},
+    ids::NodeId,
+};
+use rstest::rstest;
+
+fn random_graph<D: Direction>(seed: &mut u64, n: usize, m: usize) -> (Graph<D, (), ()>, Vec<NodeId>) {
+    let mut graph = Graph::<D, (), ()>::default();
+    let nodes = graph.add_nodes(n);
+    for _ in 0..m {
+        let (u, v) = ((lcg_next(seed) % n as u64) as usize, (lcg_next(seed) % n as u64) as usize);
+        graph.add_edge(nodes[u], nodes[v], ());
+    }
+    (graph, nodes)
+}
+
+// nodes reachable from s after removing the nodes in removed
+fn reachable<D: Direction>(graph: &Graph<D, (), ()>, s: usize, removed: usize) -> Vec<bool> {
+    let mut seen = vec![false; graph.num_nodes()];
+    let mut stack = vec![s];
+    seen[s] = true;
+    while let Some(u) = stack.pop() {
+        for e in graph.edges() {
+            let (a, b) = (e.u.index(), e.v.index());
+            for (a, b) in [(a, b), (b, a)].into_iter().take(if D::IS_DIRECTED { 1 } else { 2 }) {
+                if a == u && (removed >> b) & 1 == 0 && !seen[b] {
+                    seen[b] = true;
+                    stack.push(b);
+                }
+            }
+        }
+    }
+    seen
+}
+
+fn brute_force_edge_connectivity<D: Direction>(graph: &Graph<D, (), ()>) -> usize {
+    let n = graph.num_nodes();
+    (1..(1_usize << n) - 1)
+        .map(|mask| {
+            graph
+                .edges()
+                .filter(|e| {
+                    let (u, v) = ((mask >> e.u.index()) & 1 == 1, (mask >> e.v.index()) & 1 == 1);
+                    u != v && (u || !D::IS_DIRECTED)
+                })
+                .count()
+        })
+        .min()
+        .unwrap()
+}
+
+// removing the nodes in mask leaves two nodes s, t with no path from s to t
+fn separates<D: Direction>(graph: &Graph<D, (), ()>, mask: usize) -> bool {
+    let n = graph.num_nodes();
+    (0..n).filter(|&s| (mask >> s) & 1 == 0).any(|s| {
+        reachable(graph, s, mask)
+            .iter()
+            .enumerate()
+            .any(|(t, &seen)| !seen && (mask >> t) & 1 == 0)
+    })
+}
+
+fn brute_force_vertex_connectivity<D: Direction>(graph: &Graph<D, (), ()>) -> usize {
+    let n = graph.num_nodes();
+    (0..1_usize << n)
+        .filter(|&mask| separates(graph, mask))
+        .map(|mask| mask.count_ones() as usize)
+        .min()
+        .unwrap_or(n - 1)
+}
+
+fn to_mask(separator: &[NodeId]) -> usize {
+    separator.iter().map(|u| 1 << u.index()).sum()
+}
+
+fn check<D: Direction, S: MaximumFlowSolver<usize>>(seed: &mut u64, n: usize, m: usize) {
+    let (graph, nodes) = random_graph::<D>(seed, n, m);
+    let connectivity = Connectivity::<S>::new(&graph);
+
+    assert_eq!(connectivity.edge_connectivity().unwrap(), brute_force_edge_connectivity(&graph));
+
+    let expected = brute_force_vertex_connectivity(&graph);
+    assert_eq!(connectivity.vertex_connectivity().unwrap(), expected);
+    match connectivity.global_minimum_vertex_separator().unwrap() {
+        Some(separator) => {
+            assert_eq!(separator.len(), expected);
+            assert!(separates(&graph, to_mask(&separator)));
+        }
+        None => assert!(!(0..1_usize << n).any(|mask| separates(&graph, mask))),
+    }
+
+    let (s, t) = (nodes[0], nodes[n - 1]);
+    match connectivity.minimum_vertex_separator(s, t).unwrap() {
+        Some(separator) => {
+            let mask = to_mask(&separator);
+            assert!(mask & (1 << s.index() | 1 << t.index()) == 0);
+            assert!(!reachable(&graph, s.index(), mask)[t.index()]);
+            let expected = (0..1_usize << n)
+                .filter(|&mask| mask & (1 << s.index() | 1 << t.index()) == 0)
+                .filter(|&mask| !reachable(&graph, s.index(), mask)[t.index()])
+                .map(|mask| mask.count_ones() as usize)
+                .min()
+                .unwrap();
+            assert_eq!(separator.len(), expected);
+        }
+        None => assert!(
+            graph
+                .edges()
+                .any(|e| (e.u, e.v) == (s, t) || (!D::IS_DIRECTED && (e.u, e.v) == (t, s)))
+        ),
+    }
+}
+
+#[rstest]
+#[case::sparse(6, 8)]
+#[case::dense(6, 30)]
+#[case::very_dense(5, 60)]
+#[case::disconnected(7, 3)]
+fn random(#[case] n: usize, #[case] m: usize) {
+    let mut seed = 24680_u64 + (n * m) as u64;
+    for _ in 0..30 {
+        check::<Directed, BoykovKolmogorov<usize>>(&mut seed, n, m);
+        check::<Undirected, BoykovKolmogorov<usize>>(&mut seed, n, m);
+    }
+}
+
+// every solver answers the many maximum flows of one query with a single instance
+#[rstest]
+#[case::boykov_kolmogorov(check::<Directed, BoykovKolmogorov<usize>>, check::<Undirected, BoykovKolmogorov<usize>>)]
+#[case::capacity_scaling(check::<Directed, CapacityScaling<usize>>, check::<Undirected, CapacityScaling<usize>>)]
+#[case::dinic(check::<Directed, Dinic<usize>>, check::<Undirected, Dinic<usize>>)]
+#[case::dinic_dynamic_tree(check::<Directed, DinicDynamicTree<usize>>, check::<Undirected, DinicDynamicTree<usize>>)]
+#[case::edmonds_karp(check::<Directed, EdmondsKarp<usize>>, check::<Undirected, EdmondsKarp<usize>>)]
+#[case::excess_scaling(check::<Directed, ExcessScaling<usize>>, check::<Undirected, ExcessScaling<usize>>)]
+#[case::ford_fulkerson(check::<Directed, FordFulkerson<usize>>, check::<Undirected, FordFulkerson<usize>>)]
+#[case::mpm(check::<Directed, Mpm<usize>>, check::<Undirected, Mpm<usize>>)]
+#[cfg_attr(
+    feature = "parallel",
+    case::parallel_push_relabel(
+        check::<Directed, ParallelPushRelabel<usize>>,
+        check::<Undirected, ParallelPushRelabel<usize>>
+    )
+)]
+#[case::pseudoflow(check::<Directed, Pseudoflow<usize>>, check::<Undirected, Pseudoflow<usize>>)]
+#[case::push_relabel_fifo(check::<Directed, PushRelabelFifo<usize>>, check::<Undirected, PushRelabelFifo<usize>>)]
+#[case::push_relabel_highest_label(
+    check::<Directed, PushRelabelHighestLabel<usize>>,
+    check::<Undirected, PushRelabelHighestLabel<usize>>
+)]
+#[case::shortest_augmenting_path(
+    check::<Directed, ShortestAugmentingPath<usize>>,
+    check::<Undirected, ShortestAugmentingPath<usize>>
+)]
+fn every_solver(#[case] directed: fn(&mut u64, usize, usize), #[case] undirected: fn(&mut u64, usize, usize)) {
+    let mut seed = 97531_u64;
+    for _ in 0..10 {
+        directed(&mut seed, 6, 10);
+        undirected(&mut seed, 6, 10);
+    }
+}
+
+#[test]
+fn cycle_and_small_graphs() {
+    let mut graph = Graph::<Undirected, (), ()>::default();
+    let connectivity: Connectivity = Connectivity::new(&graph);
+    assert_eq!(connectivity.edge_connectivity(), Ok(0));
+    assert_eq!(connectivity.vertex_connectivity(), Ok(0));
+    assert_eq!(connectivity.global_minimum_vertex_separator(), Ok(None));
+
+    let nodes = graph.add_nodes(5);
+    for i in 0..5 {
+        graph.add_edge(nodes[i], nodes[(i + 1) % 5], ());
+    }
+    let connectivity: Connectivity = Connectivity::new(&graph);
+    assert_eq!(connectivity.edge_connectivity(), Ok(2));
+    assert_eq!(connectivity.vertex_connectivity(), Ok(2));
+    let mut separator = connectivity
+        .minimum_vertex_separator(nodes[0], nodes[2])
+        .unwrap()
+        .unwrap();
+    separator.sort();
+    assert_eq!(separator.len(), 2);
+    assert_eq!(separator[0], nodes[1]);
+    assert_eq!(connectivity.minimum_vertex_separator(nodes[0], nodes[1]), Ok(None));
+    assert_eq!(
+        connectivity.minimum_vertex_separator(nodes[0], nodes[0]),
+        Err(MaximumFlowError::InvalidTerminal { source: nodes[0], sink: nodes[0], num_nodes: 5 })
+    );
+}
@@ -0,0 +1,111 @@
+mod common;
+
+use common::lcg_next;
+use network_algorithms::algorithms::densest_subgraph::prelude::*;
+use network_algorithms::algorithms::maximum_flow::prelude::{Dinic, MaximumFlowError, PushRelabelFifo};
+use network_algorithms::{Graph, direction::Undirected};
+use rstest::rstest;
+
+type WeightedGraph = Graph<Undirected, i64, i64>;
+
+fn random_graph(seed: &mut u64, n: usize, m: usize) -> WeightedGraph {
+    let mut graph = WeightedGraph::default();
+    let nodes = graph.add_nodes_with((0..n).map(|_| (lcg_next(seed) % 5) as i64 + 1));
+    for _ in 0..m {
+        let (u, v) = ((lcg_next(seed) % n as u64) as usize, (lcg_next(seed) % n as u64) as usize);
+        graph.add_edge(nodes[u], nodes[v], (lcg_next(seed) % 6) as i64);
+    }
+    graph
+}
+
+// (w(E(S)), c(S)) with unit weights if weighted is false
+fn density(graph: &WeightedGraph, side: &[bool], weighted: bool) -> (i64, i64) {
+    let edges = graph
+        .edges()
+        .filter(|e| side[e.u.index()] && side[e.v.index()])
+        .map(|e| if weighted { e.data } else { 1 })
+        .sum();
+    let nodes = graph
+        .nodes()
+        .zip(side)
+        .filter(|(_, s)| **s)
+        .map(|(node, _)| if weighted { node.data } else { 1 })
+        .sum();
+    (edges, nodes)
+}
+
+fn brute_force(graph: &WeightedGraph, weighted: bool) -> (i64, i64) {
+    let n = graph.num_nodes();
+    (1..1_usize << n)
+        .map(|mask| density(graph, &(0..n).map(|u| (mask >> u) & 1 == 1).collect::<Vec<_>>(), weighted))
+        .max_by(|a, b| (a.0 * b.1).cmp(&(b.0 * a.1)))
+        .unwrap()
+}
+
+fn check((side, p, q): (Vec<bool>, i64, i64), graph: &WeightedGraph, weighted: bool) {
+    let expected = brute_force(graph, weighted);
+    assert_eq!(p * expected.1, expected.0 * q);
+    assert_eq!(density(graph, &side, weighted), (p, q));
+}
+
+#[rstest]
+#[case::sparse(8, 8)]
+#[case::dense(8, 30)]
+#[case::no_edges(5, 0)]
+fn random(#[case] n: usize, #[case] m: usize) {
+    let mut seed = 2718281828_u64 + (n * m) as u64;
+    for _ in 0..30 {
+        let graph = random_graph(&mut seed, n, m);
+
+        let unweighted = DensestSubgraph::<i64, Dinic<i64>>::new(&graph)
+            .solve()
+            .unwrap()
+            .unwrap();
+        check(unweighted, &graph, false);
+
+        let weighted = DensestSubgraph::<i64, PushRelabelFifo<i64>>::new_with(&graph, |node| node.data, |e| e.data)
+            .solve()
+            .unwrap()
+            .unwrap();
+        check(weighted, &graph, true);
+    }
+}
+
+#[test]
+fn clique_with_tail() {
+    // K4 has density 6/4, which the path hanging from it only lowers
+    let mut graph = Graph::<Undirected, (), ()>::default();
+    let nodes = graph.add_nodes(6);
+    for u in 0..4 {
+        for v in u + 1..4 {
+            graph.add_edge(nodes[u], nodes[v], ());
+        }
+    }
+    graph.add_edge(nodes[3], nodes[4], ());
+    graph.add_edge(nodes[4], nodes[5], ());
+
+    let (side, edge_weight, node_weight) = DensestSubgraph::<i64>::new(&graph).solve().unwrap().unwrap();
+    assert_eq!(side, vec![true, true, true, true, false, false]);
+    assert_eq!((edge_weight, node_weight), (6, 4));
+
+    assert_eq!(DensestSubgraph::<i64>::new(&Graph::<Undirected, (), ()>::default()).solve(), Ok(None));
+}
+
+#[test]
+fn invalid_weights() {
+    let mut graph = WeightedGraph::default();
+    let nodes = graph.add_nodes_with([1, 2, 3]);
+    graph.add_edge(nodes[0], nodes[1], 4);
+    graph.add_edge(nodes[1], nodes[2], 5);
+
+    // node weights must be positive and edge weights non-negative
+    for (node_weight, edge_weight) in [(0, 1), (-1, 1), (1, -1)] {
+        let result =
+            DensestSubgraph::<i64>::new_with(&graph, |node| node.data * node_weight, |e| e.data * edge_weight).solve();
+        assert_eq!(result, Err(MaximumFlowError::InvalidParameter));
+    }
+
+    // q W overflows
+    let result = DensestSubgraph::<i64>::new_with(&graph, |node| node.data, |_| i64::MAX / 8).solve();
+    assert_eq!(result, Err(MaximumFlowError::InvalidParameter));
+}
@@ -0,0 +1,160 @@
+mod common;
+
+use common::lcg_next;
+use network_algorithms::algorithms::disjoint_paths::prelude::*;
+use network_algorithms::algorithms::maximum_flow::prelude::{MaximumFlowError, PushRelabelFifo};
+use network_algorithms::{
+    Graph,
+    direction::{Directed, Direction, Undirected},
+    ids::{EdgeId, NodeId},
+};
+use rstest::rstest;
+use std::collections::HashSet;
+
+fn random_graph<D: Direction>(seed: &mut u64, n: usize, m: usize) -> (Graph<D, (), ()>, Vec<NodeId>) {
+    let mut graph = Graph::<D, (), ()>::default();
+    let nodes = graph.add_nodes(n);
+    for _ in 0..m {
+        let (u, v) = ((lcg_next(seed) % n as u64) as usize, (lcg_next(seed) % n as u64) as usize);
+        graph.add_edge(nodes[u], nodes[v], ());
+    }
+    (graph, nodes)
+}
+
+// checks that every path is a simple source-sink path and returns the inner nodes of each path
+fn inner_nodes<D: Direction>(graph: &Graph<D, (), ()>, s: NodeId, t: NodeId, paths: &[Vec<EdgeId>]) -> Vec<Vec<usize>> {
+    paths
+        .iter()
+        .map(|path| {
+            let mut u = s;
+            let mut visited = vec![s.index()];
+            for &edge_id in path {
+                let e = graph.get_edge(edge_id).unwrap();
+                u = if e.u == u {
+                    e.v
+                } else {
+                    assert!(!D::IS_DIRECTED && e.v == u);
+                    e.u
+                };
+                assert!(!visited.contains(&u.index()));
+                visited.push(u.index());
+            }
+            assert_eq!(u, t);
+            visited[1..visited.len() - 1].to_vec()
+        })
+        .collect()
+}
+
+fn crosses<D: Direction>(side: &[bool], u: usize, v: usize) -> bool {
+    side[u] != side[v] && (side[u] || !D::IS_DIRECTED)
+}
+
+// minimum number of edges leaving a node set containing s but not t
+fn brute_force_edge<D: Direction>(graph: &Graph<D, (), ()>, s: usize, t: usize) -> usize {
+    let n = graph.num_nodes();
+    (0..1_usize << n)
+        .filter(|mask| (mask >> s) & 1 == 1 && (mask >> t) & 1 == 0)
+        .map(|mask| {
+            let side: Vec<bool> = (0..n).map(|u| (mask >> u) & 1 == 1).collect();
+            graph
+                .edges()
+                .filter(|e| crosses::<D>(&side, e.u.index(), e.v.index()))
+                .count()
+        })
+        .min()
+        .unwrap()
+}
+
+// direct s-t edges plus the minimum number of inner nodes whose removal separates s from t
+fn brute_force_vertex<D: Direction>(graph: &Graph<D, (), ()>, s: usize, t: usize) -> usize {
+    let n = graph.num_nodes();
+    let direct = graph
+        .edges()
+        .filter(|e| (e.u.index(), e.v.index()) == (s, t) || (!D::IS_DIRECTED && (e.u.index(), e.v.index()) == (t, s)))
+        .count();
+    let separator = (0..1_usize << n)
+        .filter(|mask| (mask >> s) & 1 == 0 && (mask >> t) & 1 == 0)
+        .filter(|&mask| {
+            let mut seen = vec![false; n];
+            let mut stack = vec![s];
+            seen[s] = true;
+            while let Some(u) = stack.pop() {
+                for e in graph.edges() {
+                    let (a, b) = (e.u.index(), e.v.index());
+                    for (a, b) in [(a, b), (b, a)].into_iter().take(if D::IS_DIRECTED { 1 } else { 2 }) {
+                        if a == u && (a, b) != (s, t) && (mask >> b) & 1 == 0 && !seen[b] {
+                            seen[b] = true;
+                            stack.push(b);
+                        }
+                    }
+                }
+            }
+            !seen[t]
+        })
+        .map(|mask: usize| mask.count_ones() as usize)
+        .min()
+        .unwrap();
+    direct + separator
+}
+
+fn check<D: Direction>(seed: &mut u64, n: usize, m: usize) {
+    let (graph, nodes) = random_graph::<D>(seed, n, m);
+    let (s, t) = (nodes[0], nodes[n - 1]);
+    let solver = DisjointPaths::<PushRelabelFifo<usize>>::new(&graph);
+
+    let expected = brute_force_edge(&graph, s.index(), t.index());
+    let paths = solver.edge_disjoint(s, t, None).unwrap();
+    assert_eq!(paths.len(), expected);
+    inner_nodes(&graph, s, t, &paths);
+    let edges: HashSet<_> = paths.iter().flatten().map(|e| e.index()).collect();
+    assert_eq!(edges.len(), paths.iter().map(|path| path.len()).sum::<usize>());
+
+    let expected = brute_force_vertex(&graph, s.index(), t.index());
+    let paths = solver.vertex_disjoint(s, t, None).unwrap();
+    assert_eq!(paths.len(), expected);
+    let inner: Vec<_> = inner_nodes(&graph, s, t, &paths).into_iter().flatten().collect();
+    assert_eq!(inner.iter().collect::<HashSet<_>>().len(), inner.len());
+    let edges: HashSet<_> = paths.iter().flatten().map(|e| e.index()).collect();
+    assert_eq!(edges.len(), paths.iter().map(|path| path.len()).sum::<usize>());
+
+    // a target caps the number of paths
+    for k in 0..=expected + 1 {
+        let paths = solver.vertex_disjoint(s, t, Some(k)).unwrap();
+        assert_eq!(paths.len(), k.min(expected));
+        inner_nodes(&graph, s, t, &paths);
+    }
+}
+
+#[rstest]
+#[case::sparse(7, 10)]
+#[case::dense(7, 30)]
+#[case::disconnected(8, 3)]
+fn random(#[case] n: usize, #[case] m: usize) {
+    let mut seed = 97531_u64 + (n * m) as u64;
+    for _ in 0..30 {
+        check::<Directed>(&mut seed, n, m);
+        check::<Undirected>(&mut seed, n, m);
+    }
+}
+
+#[test]
+fn default_solver_and_invalid_terminals() {
+    let mut graph = Graph::<Undirected, (), ()>::default();
+    let nodes = graph.add_nodes(4);
+    let e0 = graph.add_edge(nodes[0], nodes[1], ()).unwrap();
+    let e1 = graph.add_edge(nodes[1], nodes[3], ()).unwrap();
+    let e2 = graph.add_edge(nodes[2], nodes[0], ()).unwrap();
+    let e3 = graph.add_edge(nodes[3], nodes[2], ()).unwrap();
+    graph.add_edge(nodes[1], nodes[2], ()).unwrap();
+
+    let solver: DisjointPaths = DisjointPaths::new(&graph);
+    let mut paths = solver.vertex_disjoint(nodes[0], nodes[3], None).unwrap();
+    paths.sort();
+    assert_eq!(paths, vec![vec![e0, e1], vec![e2, e3]]);
+    assert_eq!(solver.edge_disjoint(nodes[0], nodes[3], Some(1)).unwrap().len(), 1);
+
+    assert_eq!(
+        solver.edge_disjoint(nodes[0], nodes[0], None),
+        Err(MaximumFlowError::InvalidTerminal { source: nodes[0], sink: nodes[0], num_nodes: 4 })
+    );
+}
@@ -0,0 +1,191 @@
+mod common;
+
+use common::lcg_next;
+use network_algorithms::algorithms::dynamic_flow::prelude::*;
+use network_algorithms::algorithms::maximum_flow::prelude::{Dinic, MaximumFlowGraph, MaximumFlowSolver};
+use network_algorithms::algorithms::minimum_cost_flow::{
+    error::MinimumCostFlowError,
+    prelude::{DualNetworkSimplex, PrimalNetworkSimplex},
+};
+use network_algorithms::{Graph, direction::Directed, ids::NodeId};
+use rstest::rstest;
+
+// (capacity, transit time)
+type DynamicGraph = Graph<Directed, (), (i64, i64)>;
+
+fn random_graph(seed: &mut u64, n: usize, m: usize) -> (DynamicGraph, Vec<NodeId>) {
+    let mut graph = DynamicGraph::default();
+    let nodes = graph.add_nodes(n);
+    for _ in 0..m {
+        let (u, v) = ((lcg_next(seed) % n as u64) as usize, (lcg_next(seed) % n as u64) as usize);
+        graph.add_edge(nodes[u], nodes[v], ((lcg_next(seed) % 5) as i64, (lcg_next(seed) % 4) as i64));
+    }
+    (graph, nodes)
+}
+
+// maximum flow within the horizon in the time-expanded network without holdover arcs
+fn time_expanded(graph: &DynamicGraph, source: NodeId, sink: NodeId, horizon: usize) -> i64 {
+    let n = graph.num_nodes();
+    let mut expanded = MaximumFlowGraph::new_directed();
+    let nodes = expanded.add_nodes(n * horizon);
+    let (s, t) = (expanded.add_node(), expanded.add_node());
+    for step in 0..horizon {
+        for e in graph.edges() {
+            let arrival = step + e.data.1 as usize;
+            if arrival < horizon {
+                expanded.add_edge(nodes[step * n + e.u.index()], nodes[arrival * n + e.v.index()], e.data.0);
+            }
+        }
+        expanded.add_edge(s, nodes[step * n + source.index()], 1_000_000);
+        expanded.add_edge(nodes[step * n + sink.index()], t, 1_000_000);
+    }
+    Dinic::new(&expanded).solve(s, t).unwrap()
+}
+
+fn new_solver(graph: &DynamicGraph) -> FlowsOverTime<i64> {
+    FlowsOverTime::new_with(graph, |e| e.data.0, |e| e.data.1)
+}
+
+#[rstest]
+#[case::sparse(6, 10)]
+#[case::dense(6, 25)]
+fn maximum_flow(#[case] n: usize, #[case] m: usize) {
+    let mut seed = 577215664_u64 + (n * m) as u64;
+    for _ in 0..20 {
+        let (graph, nodes) = random_graph(&mut seed, n, m);
+        let (source, sink) = (nodes[0], nodes[n - 1]);
+        let primal = new_solver(&graph);
+        let dual = FlowsOverTime::<i64, DualNetworkSimplex<i64>>::new_with(&graph, |e| e.data.0, |e| e.data.1);
+        for horizon in 0..8 {
+            let expected = time_expanded(&graph, source, sink, horizon);
+            for flow in [
+                primal.maximum_flow(source, sink, horizon).unwrap(),
+                dual.maximum_flow(source, sink, horizon).unwrap(),
+            ] {
+                assert_eq!(flow.value, expected);
+
+                // the paths repeated over time give the value
+                let mut repeated = 0;
+                let mut static_flows = vec![0; graph.num_edges()];
+                for (path, rate, transit) in flow.paths.iter() {
+                    assert!(*transit <= horizon as i64 && *rate > 0);
+                    assert_eq!(graph.get_edge(path[0]).unwrap().u, source);
+                    assert_eq!(graph.get_edge(*path.last().unwrap()).unwrap().v, sink);
+                    for w in path.windows(2) {
+                        assert_eq!(graph.get_edge(w[0]).unwrap().v, graph.get_edge(w[1]).unwrap().u);
+                    }
+                    assert_eq!(*transit, path.iter().map(|&e| graph.get_edge(e).unwrap().data.1).sum::<i64>());
+                    path.iter().for_each(|e| static_flows[e.index()] += rate);
+                    repeated += (horizon as i64 - transit) * rate;
+                }
+                assert_eq!(repeated, expected);
+                for (e, (f, path_flow)) in graph.edges().zip(flow.flows.iter().zip(static_flows)) {
+                    assert!(path_flow <= *f && *f <= e.data.0);
+                }
+            }
+        }
+    }
+}
+
+#[rstest]
+#[case::sparse(6, 10)]
+#[case::dense(6, 25)]
+fn earliest_arrival(#[case] n: usize, #[case] m: usize) {
+    let mut seed = 141421356_u64 + (n * m) as u64;
+    for _ in 0..20 {
+        let (graph, nodes) = random_graph(&mut seed, n, m);
+        let (source, sink) = (nodes[0], nodes[n - 1]);
+        let solver = new_solver(&graph);
+        let horizon = 7;
+        let flow = solver.earliest_arrival_flow(source, sink, horizon).unwrap();
+
+        // every prefix is a maximum flow within its own horizon
+        for step in 0..horizon {
+            assert_eq!(flow.arrivals[step], time_expanded(&graph, source, sink, step + 1));
+        }
+        assert_eq!(flow.value, *flow.arrivals.last().unwrap());
+
+        // capacities, and no node but the source sends flow it has not received
+        let mut stored = vec![0; n];
+        for step in 0..horizon {
+            for (e, edge) in graph.edges().enumerate() {
+                let f = flow.flows[e][step];
+                assert!(0 <= f && f <= edge.data.0);
+                if f > 0 {
+                    assert!(step + (edge.data.1 as usize) < horizon);
+                }
+            }
+            // zero transit edges deliver within the step, so the check is on the net amount
+            for (e, edge) in graph.edges().enumerate() {
+                stored[edge.u.index()] -= flow.flows[e][step];
+                if step >= edge.data.1 as usize {
+                    let departure = step - edge.data.1 as usize;
+                    stored[edge.v.index()] += flow.flows[e][departure];
+                }
+            }
+            stored[sink.index()] -= flow.arrivals[step] - if step > 0 { flow.arrivals[step - 1] } else { 0 };
+            for u in (0..n).filter(|&u| u != source.index()) {
+                assert!(stored[u] >= 0);
+            }
+        }
+    }
+}
+
+#[rstest]
+#[case::sparse(6, 10)]
+#[case::dense(6, 25)]
+fn quickest_flow(#[case] n: usize, #[case] m: usize) {
+    let mut seed = 271828182_u64 + (n * m) as u64;
+    for _ in 0..20 {
+        let (graph, nodes) = random_graph(&mut seed, n, m);
+        let (source, sink) = (nodes[0], nodes[n - 1]);
+        let solver = FlowsOverTime::<i64, PrimalNetworkSimplex<i64>>::new_with(&graph, |e| e.data.0, |e| e.data.1);
+        let demand = (lcg_next(&mut seed) % 30) as i64 + 1;
+        match solver.quickest_flow(source, sink, demand).unwrap() {
+            Some((horizon, flow)) => {
+                assert!(flow.value >= demand);
+                assert_eq!(flow.value, time_expanded(&graph, source, sink, horizon));
+                assert!(time_expanded(&graph, source, sink, horizon - 1) < demand);
+            }
+            None => assert_eq!(time_expanded(&graph, source, sink, 64), 0),
+        }
+    }
+}
+
+#[test]
+fn evacuation() {
+    // a short narrow corridor 0 -> 2 and a long wide one 0 -> 1 -> 2
+    let mut graph = DynamicGraph::default();
+    let nodes = graph.add_nodes(3);
+    graph.add_edge(nodes[0], nodes[2], (1, 1));
+    graph.add_edge(nodes[0], nodes[1], (3, 2));
+    graph.add_edge(nodes[1], nodes[2], (3, 2));
+    let solver = new_solver(&graph);
+
+    // 1 per step from step 1 on, and 3 more per step from step 4 on
+    let flow = solver.earliest_arrival_flow(nodes[0], nodes[2], 6).unwrap();
+    assert_eq!(flow.arrivals, vec![0, 1, 2, 3, 7, 11]);
+
+    let flow = solver.maximum_flow(nodes[0], nodes[2], 6).unwrap();
+    assert_eq!(flow.value, 11);
+    assert_eq!(flow.flows, vec![1, 3, 3]);
+
+    let (horizon, flow) = solver.quickest_flow(nodes[0], nodes[2], 12).unwrap().unwrap();
+    assert_eq!((horizon, flow.value), (7, 15));
+    assert!(solver.quickest_flow(nodes[2], nodes[0], 1).unwrap().is_none());
+    assert_eq!(solver.quickest_flow(nodes[0], nodes[2], 0).unwrap().unwrap().0, 0);
+
+    let invalid = MinimumCostFlowError::InvalidTerminal { source: nodes[1], sink: nodes[1], num_nodes: 3 };
+    assert_eq!(solver.maximum_flow(nodes[1], nodes[1], 6).err(), Some(invalid.clone()));
+    assert_eq!(solver.quickest_flow(nodes[1], nodes[1], 1).err(), Some(invalid.clone()));
+    assert_eq!(solver.earliest_arrival_flow(nodes[1], nodes[1], 6).err(), Some(invalid));
+
+    // a negative transit time
+    graph.add_edge(nodes[1], nodes[0], (1, -1));
+    let solver = new_solver(&graph);
+    assert_eq!(solver.maximum_flow(nodes[0], nodes[2], 6).err(), Some(MinimumCostFlowError::InvalidParameter));
+    assert_eq!(
+        solver.earliest_arrival_flow(nodes[0], nodes[2], 6).err(),
+        Some(MinimumCostFlowError::InvalidParameter)
+    );
+}
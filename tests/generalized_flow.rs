@@ -0,0 +1,217 @@
+mod common;
+
+use common::lcg_next;
+use network_algorithms::algorithms::generalized_flow::prelude::*;
+use network_algorithms::algorithms::minimum_cost_flow::error::MinimumCostFlowError;
+use network_algorithms::{Graph, direction::Directed, ids::NodeId};
+use rstest::rstest;
+
+const EPSILON: f64 = 1e-9;
+const TOLERANCE: f64 = 1e-6;
+
+// (upper, gain, cost)
+type GainGraph = Graph<Directed, f64, (f64, f64, f64)>;
+
+// gains are powers of two, so that products of gains are exact
+fn random_graph(seed: &mut u64, n: usize, m: usize) -> (GainGraph, Vec<NodeId>) {
+    let mut graph = GainGraph::default();
+    let nodes = graph.add_nodes_with((0..n).map(|_| 0.0));
+    for _ in 0..m {
+        let (u, v) = ((lcg_next(seed) % n as u64) as usize, (lcg_next(seed) % n as u64) as usize);
+        let upper = (lcg_next(seed) % 6) as f64;
+        let gain = [0.5, 1.0, 1.0, 2.0][(lcg_next(seed) % 4) as usize];
+        let cost = (lcg_next(seed) % 7) as f64 - 2.0;
+        graph.add_edge(nodes[u], nodes[v], (upper, gain, cost));
+    }
+    (graph, nodes)
+}
+
+// net flow leaving every node
+fn balances(graph: &GainGraph, flow_in: &[f64], flow_out: &[f64]) -> Vec<f64> {
+    let mut balance = vec![0.0; graph.num_nodes()];
+    for (e, edge) in graph.edges().enumerate() {
+        assert!(flow_in[e] >= -TOLERANCE && flow_in[e] <= edge.data.0 + TOLERANCE);
+        assert!((flow_out[e] - edge.data.1 * flow_in[e]).abs() < TOLERANCE);
+        balance[edge.u.index()] += flow_in[e];
+        balance[edge.v.index()] -= flow_out[e];
+    }
+    balance
+}
+
+// the maximum flow as a generalized minimum cost flow: the source has ample supply, and a self-loop of gain 1/2 at
+// every node throws away its excess, at a cost of -1/2 per unit of flow entering the loop at the sink, that is -1 per unit of value
+fn maximum_flow_by_simplex(graph: &GainGraph, nodes: &[NodeId], source: NodeId, sink: NodeId) -> f64 {
+    let supply = graph.edges().map(|e| e.data.0).sum::<f64>() + 1.0;
+    let mut lp = GainGraph::default();
+    lp.add_nodes_with(nodes.iter().map(|&u| if u == source { supply } else { 0.0 }));
+    for e in graph.edges() {
+        lp.add_edge(e.u, e.v, (e.data.0, e.data.1, 0.0));
+    }
+    for &u in nodes {
+        let cost = if u == sink { -0.5 } else { 0.0 };
+        lp.add_edge(u, u, (4.0 * supply, 0.5, cost));
+    }
+    let result = GeneralizedMinimumCostFlow::new_with(&lp, |v| v.data, |e| e.data.0, |e| e.data.1, |e| e.data.2)
+        .set_epsilon(EPSILON)
+        .solve()
+        .unwrap();
+    -result.cost
+}
+
+#[rstest]
+#[case::sparse(5, 8)]
+#[case::dense(6, 20)]
+fn maximum_flow(#[case] n: usize, #[case] m: usize) {
+    let mut seed = 3141592653_u64 + (n * m) as u64;
+    for _ in 0..30 {
+        let (graph, nodes) = random_graph(&mut seed, n, m);
+        let (source, sink) = (nodes[0], nodes[n - 1]);
+        let result = GeneralizedMaximumFlow::new_with(&graph, |e| e.data.0, |e| e.data.1)
+            .set_epsilon(EPSILON)
+            .solve(source, sink)
+            .unwrap();
+
+        // only the source may send more than it receives, and the sink keeps the value
+        let balance = balances(&graph, &result.flow_in, &result.flow_out);
+        for u in (0..n).filter(|&u| u != source.index()) {
+            assert!(balance[u] <= TOLERANCE);
+        }
+        assert!((result.value + balance[sink.index()]).abs() < TOLERANCE);
+
+        // no residual path is left from the source to the sink
+        assert!(result.labels[source.index()].is_none());
+        assert_eq!(result.labels[sink.index()], Some(1.0));
+
+        let expected = maximum_flow_by_simplex(&graph, &nodes, source, sink);
+        assert!((result.value - expected).abs() < TOLERANCE * expected.max(1.0));
+    }
+}
+
+#[rstest]
+#[case::sparse(5, 10)]
+#[case::dense(6, 24)]
+fn minimum_cost_flow(#[case] n: usize, #[case] m: usize) {
+    let mut seed = 2718281828_u64 + (n * m) as u64;
+    let mut solved = 0;
+    for _ in 0..40 {
+        let (mut graph, nodes) = random_graph(&mut seed, n, m);
+        for &u in nodes.iter() {
+            graph.get_node_mut(u).unwrap().data = (lcg_next(&mut seed) % 7) as f64 - 3.0;
+        }
+        let result =
+            match GeneralizedMinimumCostFlow::new_with(&graph, |v| v.data, |e| e.data.0, |e| e.data.1, |e| e.data.2)
+                .set_epsilon(EPSILON)
+                .solve()
+            {
+                Ok(result) => result,
+                Err(err) => {
+                    assert_eq!(err, MinimumCostFlowError::Infeasible);
+                    continue;
+                }
+            };
+        solved += 1;
+
+        let balance = balances(&graph, &result.flow_in, &result.flow_out);
+        for (u, node) in graph.nodes().enumerate() {
+            assert!((balance[u] - node.data).abs() < TOLERANCE);
+        }
+        let cost: f64 = graph
+            .edges()
+            .zip(result.flow_in.iter())
+            .map(|(e, f)| e.data.2 * f)
+            .sum();
+        assert!((result.cost - cost).abs() < TOLERANCE);
+
+        // complementary slackness, and the dual objective matches the cost
+        let p = &result.potentials;
+        let mut dual: f64 = graph.nodes().enumerate().map(|(u, node)| node.data * p[u]).sum();
+        for (e, edge) in graph.edges().enumerate() {
+            let reduced = edge.data.2 - p[edge.u.index()] + edge.data.1 * p[edge.v.index()];
+            if result.flow_in[e] < edge.data.0 - TOLERANCE {
+                assert!(reduced >= -TOLERANCE);
+            }
+            if result.flow_in[e] > TOLERANCE {
+                assert!(reduced <= TOLERANCE);
+            }
+            dual += edge.data.0 * reduced.min(0.0);
+        }
+        assert!((dual - result.cost).abs() < TOLERANCE * result.cost.abs().max(1.0));
+    }
+    assert!(solved > 0);
+}
+
+#[test]
+fn currency_exchange() {
+    // dollars at 0, euros at 1 and yen at 2; exchanging around 0 -> 1 -> 2 -> 0 doubles the money
+    let mut graph = GainGraph::default();
+    let nodes = graph.add_nodes_with([0.0, 0.0, 0.0, 0.0]);
+    graph.add_edge(nodes[0], nodes[1], (4.0, 0.5, 0.0));
+    graph.add_edge(nodes[1], nodes[2], (2.0, 8.0, 0.0));
+    graph.add_edge(nodes[2], nodes[0], (16.0, 0.5, 0.0));
+    graph.add_edge(nodes[2], nodes[3], (3.0, 1.0, 0.0));
+    let solver = GeneralizedMaximumFlow::new_with(&graph, |e| e.data.0, |e| e.data.1);
+
+    // at most 3 yen leave for the sink
+    let result = solver.solve(nodes[0], nodes[3]).unwrap();
+    assert_eq!(result.value, 3.0);
+    assert_eq!(result.flow_out[3], 3.0);
+    assert_eq!(result.labels[nodes[3].index()], Some(1.0));
+    assert!(result.labels[nodes[0].index()].is_none());
+
+    // lossy edges toward the sink
+    let mut pipeline = GainGraph::default();
+    let nodes = pipeline.add_nodes_with([0.0, 0.0, 0.0]);
+    pipeline.add_edge(nodes[0], nodes[1], (8.0, 0.5, 0.0));
+    pipeline.add_edge(nodes[1], nodes[2], (8.0, 0.5, 0.0));
+    pipeline.add_edge(nodes[0], nodes[2], (1.0, 0.25, 0.0));
+    let result = GeneralizedMaximumFlow::new_with(&pipeline, |e| e.data.0, |e| e.data.1)
+        .solve(nodes[0], nodes[2])
+        .unwrap();
+    assert_eq!(result.value, 2.25);
+    assert_eq!(result.flow_in, vec![8.0, 4.0, 1.0]);
+
+    // the sink cannot be reached
+    let result = GeneralizedMaximumFlow::new_with(&pipeline, |e| e.data.0, |e| e.data.1)
+        .solve(nodes[2], nodes[0])
+        .unwrap();
+    assert_eq!(result.value, 0.0);
+    assert_eq!(result.flow_in, vec![0.0; 3]);
+
+    assert!(solver.solve(nodes[0], nodes[0]).is_err());
+    assert!(
+        GeneralizedMaximumFlow::new_with(&graph, |e| e.data.0, |_| 0.0)
+            .solve(nodes[0], nodes[2])
+            .is_err()
+    );
+}
+
+#[test]
+fn minimum_cost_with_losses() {
+    // 4 units at 0 must deliver 2 units to 2, directly at cost 4 or through a lossy detour of cost 1 + 1
+    let mut graph = GainGraph::default();
+    let nodes = graph.add_nodes_with([4.0, 0.0, -2.0]);
+    graph.add_edge(nodes[0], nodes[2], (1.0, 1.0, 4.0));
+    graph.add_edge(nodes[0], nodes[1], (4.0, 0.5, 1.0));
+    graph.add_edge(nodes[1], nodes[2], (4.0, 1.0, 1.0));
+    // throwing flow away at 0 is free
+    graph.add_edge(nodes[0], nodes[0], (8.0, 0.5, 0.0));
+    let solver = GeneralizedMinimumCostFlow::new_with(&graph, |v| v.data, |e| e.data.0, |e| e.data.1, |e| e.data.2);
+    let result = solver.solve().unwrap();
+    assert_eq!(result.flow_in, vec![0.0, 4.0, 2.0, 0.0]);
+    assert_eq!(result.flow_out, vec![0.0, 2.0, 2.0, 0.0]);
+    assert_eq!(result.cost, 6.0);
+
+    // 3 units cannot be delivered
+    graph.get_node_mut(nodes[2]).unwrap().data = -3.5;
+    let solver = GeneralizedMinimumCostFlow::new_with(&graph, |v| v.data, |e| e.data.0, |e| e.data.1, |e| e.data.2);
+    assert_eq!(solver.solve().err(), Some(MinimumCostFlowError::Infeasible));
+
+    // a negative capacity or a non-positive gain is rejected
+    for data in [(-1.0, 1.0, 0.0), (1.0, 0.0, 0.0), (1.0, -0.5, 0.0)] {
+        let mut invalid = graph.clone();
+        invalid.add_edge(nodes[1], nodes[0], data);
+        let solver =
+            GeneralizedMinimumCostFlow::new_with(&invalid, |v| v.data, |e| e.data.0, |e| e.data.1, |e| e.data.2);
+        assert_eq!(solver.solve().err(), Some(MinimumCostFlowError::InvalidParameter));
+    }
+}
@@ -0,0 +1,112 @@
+mod common;
+
+use common::lcg_next;
+use network_algorithms::algorithms::gomory_hu::prelude::*;
+use network_algorithms::algorithms::maximum_flow::prelude::*;
+use network_algorithms::{Graph, algorithms::maximum_flow::edge::MaximumFlowEdge, direction::Undirected, ids::NodeId};
+use rstest::rstest;
+
+type UndirectedGraph = Graph<Undirected, (), MaximumFlowEdge<i64>>;
+
+fn random_graph(seed: &mut u64, n: usize, m: usize) -> (UndirectedGraph, Vec<NodeId>) {
+    let mut graph = UndirectedGraph::default();
+    let nodes = graph.add_nodes(n);
+    for _ in 0..m {
+        let (u, v) = ((lcg_next(seed) % n as u64) as usize, (lcg_next(seed) % n as u64) as usize);
+        graph.add_edge(nodes[u], nodes[v], MaximumFlowEdge { upper: (lcg_next(seed) % 10) as i64 });
+    }
+    (graph, nodes)
+}
+
+fn cut_capacity(graph: &UndirectedGraph, side: &[bool]) -> i64 {
+    graph
+        .edges()
+        .filter(|e| side[e.u.index()] != side[e.v.index()])
+        .map(|e| e.data.upper)
+        .sum()
+}
+
+// all pairs minimum cut values by enumerating every cut
+fn brute_force(graph: &UndirectedGraph) -> Vec<Vec<i64>> {
+    let n = graph.num_nodes();
+    let mut values = vec![vec![i64::MAX; n]; n];
+    for mask in 1..(1_usize << n) - 1 {
+        let side: Vec<bool> = (0..n).map(|u| (mask >> u) & 1 == 1).collect();
+        let capacity = cut_capacity(graph, &side);
+        for s in (0..n).filter(|&s| side[s]) {
+            for t in (0..n).filter(|&t| !side[t]) {
+                values[s][t] = values[s][t].min(capacity);
+                values[t][s] = values[t][s].min(capacity);
+            }
+        }
+    }
+    values
+}
+
+fn check<S: MaximumFlowSolver<i64>>(graph: &UndirectedGraph, nodes: &[NodeId]) -> Result<(), MaximumFlowError> {
+    let n = graph.num_nodes();
+    let expected = brute_force(graph);
+
+    let tree = GomoryHu::<i64, S>::new(graph).build()?;
+    assert_eq!(tree.edges().len(), n - 1);
+    assert_eq!(tree.all_pairs_minimum_cut_values(), expected);
+    for s in 0..n {
+        assert_eq!(tree.minimum_cut_value(nodes[s], nodes[s]), None);
+        assert!(tree.minimum_cut(nodes[s], nodes[s]).is_none());
+        for t in (0..n).filter(|&t| t != s) {
+            assert_eq!(tree.minimum_cut_value(nodes[s], nodes[t]), Some(expected[s][t]));
+            let side = tree.minimum_cut(nodes[s], nodes[t]).unwrap();
+            assert!(side[s] && !side[t]);
+            assert_eq!(cut_capacity(graph, &side), expected[s][t]);
+        }
+    }
+
+    let tree = GomoryHu::<i64, S>::new(graph).build_equivalent_flow_tree()?;
+    assert_eq!(tree.all_pairs_minimum_cut_values(), expected);
+    for s in 0..n {
+        for t in (0..n).filter(|&t| t != s) {
+            assert_eq!(tree.minimum_cut_value(nodes[s], nodes[t]), Some(expected[s][t]));
+        }
+    }
+    Ok(())
+}
+
+#[rstest]
+#[case::boykov_kolmogorov(check::<BoykovKolmogorov<i64>>)]
+#[case::capacity_scaling(check::<CapacityScaling<i64>>)]
+#[case::dinic(check::<Dinic<i64>>)]
+#[case::dinic_dynamic_tree(check::<DinicDynamicTree<i64>>)]
+#[case::edmonds_karp(check::<EdmondsKarp<i64>>)]
+#[case::excess_scaling(check::<ExcessScaling<i64>>)]
+#[case::ford_fulkerson(check::<FordFulkerson<i64>>)]
+#[case::mpm(check::<Mpm<i64>>)]
+#[cfg_attr(feature = "parallel", case::parallel_push_relabel(check::<ParallelPushRelabel<i64>>))]
+#[case::pseudoflow(check::<Pseudoflow<i64>>)]
+#[case::push_relabel_fifo(check::<PushRelabelFifo<i64>>)]
+#[case::push_relabel_highest_label(check::<PushRelabelHighestLabel<i64>>)]
+#[case::shortest_augmenting_path(check::<ShortestAugmentingPath<i64>>)]
+fn random(#[case] check: fn(&UndirectedGraph, &[NodeId]) -> Result<(), MaximumFlowError>) {
+    let mut seed = 1122334455_u64;
+    for (n, m) in [(2, 1), (6, 8), (8, 20), (9, 5)] {
+        for _ in 0..10 {
+            let (graph, nodes) = random_graph(&mut seed, n, m);
+            check(&graph, &nodes).unwrap();
+        }
+    }
+}
+
+#[test]
+fn default_solver_and_small_graphs() {
+    let mut graph = UndirectedGraph::default();
+    let tree = GomoryHu::<i64>::new(&graph).build().unwrap();
+    assert!(tree.edges().is_empty());
+    assert!(tree.all_pairs_minimum_cut_values().is_empty());
+
+    let nodes = graph.add_nodes(3);
+    graph.add_edge(nodes[0], nodes[1], MaximumFlowEdge { upper: 4 });
+    graph.add_edge(nodes[1], nodes[2], MaximumFlowEdge { upper: 2 });
+    let tree = GomoryHu::<i64>::new(&graph).build().unwrap();
+    assert_eq!(tree.minimum_cut_value(nodes[0], nodes[2]), Some(2));
+    assert_eq!(tree.minimum_cut(nodes[2], nodes[0]), Some(vec![false, false, true]));
+    assert_eq!(tree.minimum_cut_value(nodes[0], graph.add_node()), None);
+}
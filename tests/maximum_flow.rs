@@ -1,24 +1,40 @@
-use network_algorithms::{algorithms::maximum_flow::prelude::*, ids::NodeId};
+mod common;
+
+use common::lcg_next;
+use network_algorithms::{Graph, algorithms::maximum_flow::prelude::*, direction::Direction, ids::{EdgeId, NodeId}};
 use rstest::rstest;
 use rstest_reuse::*;
 use std::{fs::read_to_string, path::PathBuf};
 
 #[template]
 #[rstest]
+#[case::boykov_kolmogorov(Solver::BoykovKolmogorov)]
 #[case::capacity_scaling(Solver::CapacityScaling)]
 #[case::dinic(Solver::Dinic)]
+#[case::dinic_dynamic_tree(Solver::DinicDynamicTree)]
 #[case::edmonds_karp(Solver::EdmondsKarp)]
+#[case::excess_scaling(Solver::ExcessScaling)]
 #[case::ford_fulkerson(Solver::FordFulkerson)]
+#[case::mpm(Solver::Mpm)]
+#[cfg_attr(feature = "parallel", case::parallel_push_relabel(Solver::ParallelPushRelabel))]
+#[case::pseudoflow(Solver::Pseudoflow)]
 #[case::push_relabel_fifo(Solver::PushRelabelFIFO)]
 #[case::push_relabel_highest_label(Solver::PushRelabelHighestLabel)]
 #[case::shortest_augmenting_path(Solver::ShortestAugmentingPath)]
 fn all_solvers(#[case] solver: Solver) {}
 
 enum Solver {
+    BoykovKolmogorov,
     CapacityScaling,
     Dinic,
+    DinicDynamicTree,
     EdmondsKarp,
+    ExcessScaling,
     FordFulkerson,
+    Mpm,
+    #[cfg(feature = "parallel")]
+    ParallelPushRelabel,
+    Pseudoflow,
     PushRelabelFIFO,
     PushRelabelHighestLabel,
     ShortestAugmentingPath,
@@ -31,11 +47,25 @@ impl Solver {
     }
 
     pub fn get(&self, graph: &MaximumFlowGraph<i64>) -> Box<dyn MaximumFlowSolver<i64>> {
+        self.get_with_direction(graph)
+    }
+
+    pub fn get_with_direction<D: Direction>(
+        &self,
+        graph: &MaximumFlowGraph<i64, D>,
+    ) -> Box<dyn MaximumFlowSolver<i64>> {
         match self {
+            Solver::BoykovKolmogorov => Box::new(<BoykovKolmogorov<i64> as MaximumFlowSolver<i64>>::new(graph)),
             Solver::CapacityScaling => Box::new(<CapacityScaling<i64> as MaximumFlowSolver<i64>>::new(graph)),
             Solver::Dinic => Box::new(<Dinic<i64> as MaximumFlowSolver<i64>>::new(graph)),
+            Solver::DinicDynamicTree => Box::new(<DinicDynamicTree<i64> as MaximumFlowSolver<i64>>::new(graph)),
             Solver::EdmondsKarp => Box::new(<EdmondsKarp<i64> as MaximumFlowSolver<i64>>::new(graph)),
+            Solver::ExcessScaling => Box::new(<ExcessScaling<i64> as MaximumFlowSolver<i64>>::new(graph)),
             Solver::FordFulkerson => Box::new(<FordFulkerson<i64> as MaximumFlowSolver<i64>>::new(graph)),
+            Solver::Mpm => Box::new(<Mpm<i64> as MaximumFlowSolver<i64>>::new(graph)),
+            #[cfg(feature = "parallel")]
+            Solver::ParallelPushRelabel => Box::new(<ParallelPushRelabel<i64> as MaximumFlowSolver<i64>>::new(graph)),
+            Solver::Pseudoflow => Box::new(<Pseudoflow<i64> as MaximumFlowSolver<i64>>::new(graph)),
             Solver::PushRelabelFIFO => Box::new(<PushRelabelFifo<i64> as MaximumFlowSolver<i64>>::new(graph)),
             Solver::ShortestAugmentingPath => {
                 Box::new(<ShortestAugmentingPath<i64> as MaximumFlowSolver<i64>>::new(graph))
@@ -147,3 +177,1070 @@ fn not_solved(#[case] solver: Solver) {
     assert_eq!(solver.flows().err().unwrap(), MaximumFlowError::NotSolved);
     assert_eq!(solver.minimum_cut().err().unwrap(), MaximumFlowError::NotSolved);
 }
+
+#[test]
+fn push_relabel_fifo_excess_back_to_source() {
+    // nodes[1] cannot reach the sink, so its excess returns to the source over both parallel edges; subtracting the
+    // excess of a path twice stopped the return after the first one
+    let mut graph = MaximumFlowGraph::<i64>::default();
+    let nodes = graph.add_nodes(3);
+    graph.add_edge(nodes[0], nodes[1], 1);
+    graph.add_edge(nodes[0], nodes[1], 3);
+
+    let mut solver = PushRelabelFifo::new_with(&graph, |e| e.data.upper);
+    assert_eq!(solver.solve(nodes[0], nodes[2]), Ok(0));
+    assert_eq!(solver.flows(), Ok(vec![0, 0]));
+}
+
+#[apply(all_solvers)]
+fn minimum_cut_dag(#[case] solver: Solver) {
+    let mut seed = 123456789_u64;
+    for _ in 0..20 {
+        let n = 7;
+        let mut graph = MaximumFlowGraph::default();
+        let nodes = graph.add_nodes(n);
+        let mut edges = Vec::new();
+        for _ in 0..12 {
+            let (u, v) = ((lcg_next(&mut seed) % n as u64) as usize, (lcg_next(&mut seed) % n as u64) as usize);
+            let upper = (lcg_next(&mut seed) % 4) as i64;
+            edges.push(graph.add_edge(nodes[u], nodes[v], upper).unwrap());
+        }
+
+        let mut s = solver.get(&graph);
+        let objective_value = s.solve(nodes[0], nodes[n - 1]).unwrap();
+        let dag = s.minimum_cut_dag().unwrap();
+
+        let mut expected = Vec::new();
+        for mask in 0..(1_usize << n) {
+            let cut: Vec<bool> = (0..n).map(|u| (mask >> u) & 1 == 1).collect();
+            if cut[0] && !cut[n - 1] && check(&graph, &cut) == objective_value {
+                expected.push(cut);
+            }
+        }
+        expected.sort();
+
+        let mut actual: Vec<Vec<bool>> = dag.minimum_cuts().collect();
+        assert_eq!(actual[0], s.minimum_cut().unwrap());
+        actual.sort();
+        assert_eq!(actual, expected);
+
+        for edge_id in edges {
+            let e = graph.get_edge(edge_id).unwrap();
+            let separated = |cut: &Vec<bool>| cut[e.u.index()] && !cut[e.v.index()];
+            assert_eq!(dag.is_edge_in_every_minimum_cut(edge_id).unwrap(), expected.iter().all(separated));
+            assert_eq!(dag.is_edge_in_some_minimum_cut(edge_id).unwrap(), expected.iter().any(separated));
+        }
+    }
+}
+
+#[apply(all_solvers)]
+fn verify_certificate(#[case] solver: Solver) {
+    let mut seed = 271828182_u64;
+    for _ in 0..20 {
+        let n = 8;
+        let mut graph = MaximumFlowGraph::default();
+        let mut undirected = MaximumFlowGraph::new_undirected();
+        let nodes = graph.add_nodes(n);
+        undirected.add_nodes(n);
+        for _ in 0..16 {
+            let (u, v) = ((lcg_next(&mut seed) % n as u64) as usize, (lcg_next(&mut seed) % n as u64) as usize);
+            let upper = (lcg_next(&mut seed) % 10) as i64;
+            graph.add_edge(nodes[u], nodes[v], upper);
+            undirected.add_edge(nodes[u], nodes[v], upper);
+        }
+
+        let mut s = solver.get(&graph);
+        let value = s.solve(nodes[0], nodes[n - 1]).unwrap();
+        let (flows, cut) = (s.flows().unwrap(), s.minimum_cut().unwrap());
+        assert_eq!(verify(&graph, |e| e.data.upper, nodes[0], nodes[n - 1], &flows, &cut), Ok(value));
+
+        let mut s = solver.get_with_direction(&undirected);
+        let value = s.solve(nodes[0], nodes[n - 1]).unwrap();
+        let (flows, cut) = (s.flows().unwrap(), s.minimum_cut().unwrap());
+        assert_eq!(verify(&undirected, |e| e.data.upper, nodes[0], nodes[n - 1], &flows, &cut), Ok(value));
+    }
+}
+
+#[test]
+fn verify_violations() {
+    let mut graph = MaximumFlowGraph::new_directed();
+    let nodes = graph.add_nodes(3);
+    let edges = [
+        graph.add_edge(nodes[0], nodes[1], 3).unwrap(),
+        graph.add_edge(nodes[1], nodes[2], 2).unwrap(),
+        graph.add_edge(nodes[0], nodes[2], 1).unwrap(),
+    ];
+    let (s, t) = (nodes[0], nodes[2]);
+    let cut = [true, true, false];
+    let certify = |flows: &[i64], cut: &[bool]| verify(&graph, |e| e.data.upper, s, t, flows, cut);
+
+    assert_eq!(certify(&[2, 2, 1], &cut), Ok(3));
+    assert_eq!(
+        verify(&graph, |e| e.data.upper, s, s, &[2, 2, 1], &cut),
+        Err(Violation::InvalidTerminal { source: s, sink: s, num_nodes: 3 })
+    );
+    assert_eq!(certify(&[2, 2], &cut), Err(Violation::InvalidFlowsLength { expected: 3, actual: 2 }));
+    assert_eq!(certify(&[2, 2, 1], &cut[..2]), Err(Violation::InvalidCutLength { expected: 3, actual: 2 }));
+    assert_eq!(certify(&[4, 2, 1], &[false, true, false]), Err(Violation::NotSeparating));
+    assert_eq!(certify(&[2, 3, 1], &cut), Err(Violation::CapacityBound { edge_id: edges[1], flow: 3, upper: 2 }));
+    assert_eq!(certify(&[-1, -1, 1], &cut), Err(Violation::CapacityBound { edge_id: edges[0], flow: -1, upper: 3 }));
+    assert_eq!(certify(&[3, 2, 1], &cut), Err(Violation::Conservation { node: nodes[1], inflow: 3, outflow: 2 }));
+    assert_eq!(certify(&[1, 1, 1], &cut), Err(Violation::ValueMismatch { inflow: 2, outflow: 0, cut_capacity: 3 }));
+
+    // flows against the direction of undirected edges are negative
+    let mut undirected = MaximumFlowGraph::new_undirected();
+    let nodes = undirected.add_nodes(3);
+    undirected.add_edge(nodes[1], nodes[0], 3);
+    undirected.add_edge(nodes[2], nodes[1], 2);
+    assert_eq!(verify(&undirected, |e| e.data.upper, nodes[0], nodes[2], &[-2, -2], &cut), Ok(2));
+}
+
+#[apply(all_solvers)]
+fn undirected(#[case] solver: Solver) {
+    let mut seed = 987654321_u64;
+    for _ in 0..20 {
+        let n = 8;
+        let mut graph = MaximumFlowGraph::new_undirected();
+        let nodes = graph.add_nodes(n);
+        for _ in 0..16 {
+            let (u, v) = ((lcg_next(&mut seed) % n as u64) as usize, (lcg_next(&mut seed) % n as u64) as usize);
+            graph.add_edge(nodes[u], nodes[v], (lcg_next(&mut seed) % 10) as i64);
+        }
+
+        let mut expected = i64::MAX;
+        for mask in 0..(1_usize << n) {
+            if mask & 1 == 1 && (mask >> (n - 1)) & 1 == 0 {
+                let cut = graph
+                    .edges()
+                    .filter(|e| ((mask >> e.u.index()) & 1) != ((mask >> e.v.index()) & 1))
+                    .map(|e| e.data.upper)
+                    .sum();
+                expected = expected.min(cut);
+            }
+        }
+
+        let mut s = solver.get_with_direction(&graph);
+        assert_eq!(s.solve(nodes[0], nodes[n - 1]).unwrap(), expected);
+
+        let flows = s.flows().unwrap();
+        let mut balance = vec![0; n];
+        for (e, &flow) in graph.edges().zip(flows.iter()) {
+            assert!(flow.abs() <= e.data.upper);
+            balance[e.u.index()] -= flow;
+            balance[e.v.index()] += flow;
+        }
+        assert_eq!(balance[0], -expected);
+        assert_eq!(balance[n - 1], expected);
+        assert!(balance[1..n - 1].iter().all(|&b| b == 0));
+
+        let reach = s.minimum_cut().unwrap();
+        let cut: i64 = graph
+            .edges()
+            .filter(|e| reach[e.u.index()] != reach[e.v.index()])
+            .map(|e| e.data.upper)
+            .sum();
+        assert_eq!(cut, expected);
+    }
+}
+
+#[test]
+fn undirected_unsigned() {
+    // flow from v to u on an undirected edge is negative, which u64 cannot hold
+    let mut graph = MaximumFlowGraph::<u64, _>::new_undirected();
+    let nodes = graph.add_nodes(2);
+    graph.add_edge(nodes[0], nodes[1], 5);
+    let solvers: Vec<Box<dyn MaximumFlowSolver<u64>>> = vec![
+        Box::new(<BoykovKolmogorov<u64> as MaximumFlowSolver<u64>>::new(&graph)),
+        Box::new(<Dinic<u64> as MaximumFlowSolver<u64>>::new(&graph)),
+        Box::new(<Pseudoflow<u64> as MaximumFlowSolver<u64>>::new(&graph)),
+        Box::new(<PushRelabelHighestLabel<u64> as MaximumFlowSolver<u64>>::new(&graph)),
+    ];
+    for mut s in solvers {
+        assert_eq!(s.solve(nodes[1], nodes[0]), Err(MaximumFlowError::UnsignedFlowOnUndirectedGraph));
+        assert_eq!(s.flows(), Err(MaximumFlowError::NotSolved));
+    }
+
+    let mut graph = MaximumFlowGraph::<i64, _>::new_undirected();
+    let nodes = graph.add_nodes(2);
+    graph.add_edge(nodes[0], nodes[1], 5);
+    let mut s = <Dinic<i64> as MaximumFlowSolver<i64>>::new(&graph);
+    assert_eq!(s.solve(nodes[1], nodes[0]), Ok(5));
+    assert_eq!(s.flows(), Ok(vec![-5]));
+}
+
+#[apply(all_solvers)]
+fn solve_with_limit(#[case] solver: Solver) {
+    let mut seed = 314159265_u64;
+    for _ in 0..20 {
+        let n = 8;
+        let mut graph = MaximumFlowGraph::default();
+        let nodes = graph.add_nodes(n);
+        for _ in 0..18 {
+            let (u, v) = ((lcg_next(&mut seed) % n as u64) as usize, (lcg_next(&mut seed) % n as u64) as usize);
+            graph.add_edge(nodes[u], nodes[v], (lcg_next(&mut seed) % 8) as i64);
+        }
+        let maximum_flow = Dinic::new(&graph).solve(nodes[0], nodes[n - 1]).unwrap();
+
+        for limit in [0, maximum_flow / 2, maximum_flow, maximum_flow + 3] {
+            let expected = limit.min(maximum_flow);
+            let mut s = solver.get(&graph);
+            assert_eq!(s.solve_with_limit(nodes[0], nodes[n - 1], limit).unwrap(), expected);
+
+            let flows = s.flows().unwrap();
+            let mut balance = vec![0; n];
+            for (e, &flow) in graph.edges().zip(flows.iter()) {
+                assert!(0 <= flow && flow <= e.data.upper);
+                balance[e.u.index()] -= flow;
+                balance[e.v.index()] += flow;
+            }
+            assert_eq!(balance[0], -expected);
+            assert_eq!(balance[n - 1], expected);
+            assert!(balance[1..n - 1].iter().all(|&b| b == 0));
+
+            if limit > maximum_flow {
+                assert_eq!(check(&graph, &s.minimum_cut().unwrap()), maximum_flow);
+            }
+        }
+
+        for mut s in value_only_solvers(&graph) {
+            assert_eq!(s.solve_with_limit(nodes[0], nodes[n - 1], maximum_flow / 2).unwrap(), maximum_flow / 2);
+        }
+    }
+}
+
+// one solver answers a sequence of solves and limited solves with different terminals
+#[apply(all_solvers)]
+fn reuse_solver(#[case] solver: Solver) {
+    let mut seed = 271828182_u64;
+    for _ in 0..20 {
+        let n = 8;
+        let mut graph = MaximumFlowGraph::default();
+        let nodes = graph.add_nodes(n);
+        for _ in 0..18 {
+            let (u, v) = ((lcg_next(&mut seed) % n as u64) as usize, (lcg_next(&mut seed) % n as u64) as usize);
+            graph.add_edge(nodes[u], nodes[v], (lcg_next(&mut seed) % 8) as i64);
+        }
+
+        let mut s = solver.get(&graph);
+        for (source, sink) in [(0, n - 1), (0, n - 1), (1, 2), (n - 1, 0)] {
+            let (source, sink) = (nodes[source], nodes[sink]);
+            let expected = Dinic::new(&graph).solve(source, sink).unwrap();
+            assert_eq!(s.solve(source, sink), Ok(expected));
+            assert_eq!(check(&graph, &s.minimum_cut().unwrap()), expected);
+            assert_eq!(s.solve_with_limit(source, sink, expected / 2), Ok(expected / 2));
+        }
+    }
+}
+
+#[test]
+fn pseudoflow_solve_twice() {
+    let mut seed = 161803398_u64;
+    for _ in 0..20 {
+        let n = 8;
+        let mut graph = MaximumFlowGraph::default();
+        let nodes = graph.add_nodes(n);
+        for _ in 0..18 {
+            let (u, v) = ((lcg_next(&mut seed) % n as u64) as usize, (lcg_next(&mut seed) % n as u64) as usize);
+            graph.add_edge(nodes[u], nodes[v], (lcg_next(&mut seed) % 8) as i64);
+        }
+        let expected = Dinic::new(&graph).solve(nodes[0], nodes[n - 1]).unwrap();
+
+        for value_only in [false, true] {
+            let mut s = Pseudoflow::new_with(&graph, |e| e.data.upper).set_value_only(value_only);
+            assert_eq!(s.solve(nodes[0], nodes[n - 1]), Ok(expected));
+            assert_eq!(s.solve(nodes[0], nodes[n - 1]), Ok(expected));
+            assert_eq!(s.solve_with_limit(nodes[0], nodes[n - 1], expected / 2), Ok(expected / 2));
+            assert_eq!(s.solve(nodes[0], nodes[n - 1]), Ok(expected));
+            assert_eq!(check(&graph, &s.minimum_cut().unwrap()), expected);
+        }
+    }
+}
+
+// checks the flow and the cut against each other in terms of the original nodes and edges
+fn check_node_capacitated<D: Direction, S: MaximumFlowSolver<i64>>(
+    graph: &MaximumFlowGraph<i64, D>,
+    nodes: &[NodeId],
+    capacities: &[Option<i64>],
+) {
+    let (s, t) = (nodes[0], nodes[nodes.len() - 1]);
+    let mut solver = NodeCapacitated::<i64, S>::new(graph);
+    for (&u, &capacity) in nodes.iter().zip(capacities.iter()) {
+        if let Some(capacity) = capacity {
+            solver.set_node_capacity(u, capacity).unwrap();
+        }
+    }
+    let value = solver.solve(s, t).unwrap();
+    // the solver on the split graph is kept between solves
+    assert_eq!(solver.solve(t, s).unwrap(), solver.solve(t, s).unwrap());
+    assert_eq!(solver.solve(s, t), Ok(value));
+    let flows = solver.flows().unwrap();
+    let (side, cut_nodes) = solver.minimum_cut().unwrap();
+
+    let mut inflow = vec![0; nodes.len()];
+    let mut outflow = vec![0; nodes.len()];
+    for (e, &flow) in graph.edges().zip(flows.iter()) {
+        assert!(flow.abs() <= e.data.upper && (flow >= 0 || !D::IS_DIRECTED));
+        let (u, v) = if flow >= 0 { (e.u, e.v) } else { (e.v, e.u) };
+        outflow[u.index()] += flow.abs();
+        inflow[v.index()] += flow.abs();
+    }
+    for &u in nodes {
+        let through = if u == s { outflow[u.index()] } else { inflow[u.index()] };
+        assert!(capacities[u.index()].is_none_or(|capacity| through <= capacity));
+        if u != s && u != t {
+            assert_eq!(inflow[u.index()], outflow[u.index()]);
+        }
+    }
+    assert_eq!(outflow[s.index()] - inflow[s.index()], value);
+
+    assert!(side[s.index()] && (!side[t.index()] || cut_nodes.contains(&t)));
+    let node_part: i64 = cut_nodes.iter().map(|&u| capacities[u.index()].unwrap()).sum();
+    let edge_part: i64 = graph
+        .edges()
+        .filter(|e| {
+            let leaves = |u: NodeId, v: NodeId| side[u.index()] && !cut_nodes.contains(&u) && !side[v.index()];
+            leaves(e.u, e.v) || (!D::IS_DIRECTED && leaves(e.v, e.u))
+        })
+        .map(|e| e.data.upper)
+        .sum();
+    assert_eq!(node_part + edge_part, value);
+}
+
+#[test]
+fn node_capacities() {
+    let mut seed = 1618033988_u64;
+    for _ in 0..30 {
+        let n = 8;
+        let mut graph = MaximumFlowGraph::new_directed();
+        let mut undirected = MaximumFlowGraph::new_undirected();
+        let nodes = graph.add_nodes(n);
+        undirected.add_nodes(n);
+        for _ in 0..20 {
+            let (u, v) = ((lcg_next(&mut seed) % n as u64) as usize, (lcg_next(&mut seed) % n as u64) as usize);
+            let upper = (lcg_next(&mut seed) % 10) as i64;
+            graph.add_edge(nodes[u], nodes[v], upper);
+            undirected.add_edge(nodes[u], nodes[v], upper);
+        }
+        let capacities: Vec<_> = nodes
+            .iter()
+            .map(|_| lcg_next(&mut seed).is_multiple_of(2).then(|| (lcg_next(&mut seed) % 12) as i64))
+            .collect();
+
+        check_node_capacitated::<_, Dinic<i64>>(&graph, &nodes, &capacities);
+        check_node_capacitated::<_, PushRelabelHighestLabel<i64>>(&graph, &nodes, &capacities);
+        check_node_capacitated::<_, Dinic<i64>>(&undirected, &nodes, &capacities);
+        check_node_capacitated::<_, Pseudoflow<i64>>(&undirected, &nodes, &capacities);
+    }
+
+    let mut graph = MaximumFlowGraph::new_directed();
+    let nodes = graph.add_nodes(3);
+    graph.add_edge(nodes[0], nodes[1], 5);
+    graph.add_edge(nodes[1], nodes[2], 5);
+    let mut solver = NodeCapacitated::<i64>::new(&graph);
+    assert_eq!(solver.set_node_capacity(nodes[1], -1), Err(MaximumFlowError::InvalidParameter));
+    let invalid = MaximumFlowGraph::<i64>::new_directed().add_nodes(4)[3];
+    assert_eq!(solver.set_node_capacity(invalid, 2), Err(MaximumFlowError::InvalidNodeId { node_id: invalid }));
+    assert_eq!(solver.set_node_capacity(nodes[1], 2), Ok(()));
+    assert_eq!(solver.flows(), Err(MaximumFlowError::NotSolved));
+    assert_eq!(solver.solve(nodes[0], nodes[2]), Ok(2));
+    assert_eq!(solver.flows(), Ok(vec![2, 2]));
+    assert_eq!(solver.minimum_cut(), Ok((vec![true, true, false], vec![nodes[1]])));
+    assert!(matches!(solver.solve(nodes[0], nodes[0]), Err(MaximumFlowError::InvalidTerminal { .. })));
+
+    // solving again reuses the solver on the split graph
+    let mut solver = NodeCapacitated::<i64, PushRelabelHighestLabel<i64>>::new(&graph);
+    solver.set_node_capacity(nodes[1], 4).unwrap();
+    assert_eq!(solver.solve(nodes[0], nodes[2]), Ok(4));
+    assert_eq!(solver.solve(nodes[0], nodes[2]), Ok(4));
+    assert_eq!(solver.solve_with_limit(nodes[0], nodes[2], 3), Ok(3));
+    assert_eq!(solver.solve(nodes[0], nodes[2]), Ok(4));
+    assert_eq!(solver.flows(), Ok(vec![4, 4]));
+}
+
+// augmenting paths as long as the graph must not overflow the stack
+#[rstest]
+#[case::dinic(Solver::Dinic)]
+#[case::ford_fulkerson(Solver::FordFulkerson)]
+fn long_path(#[case] solver: Solver) {
+    let n = 200_000;
+    let mut graph = MaximumFlowGraph::default();
+    let nodes = graph.add_nodes(n);
+    for u in 0..n - 1 {
+        graph.add_edge(nodes[u], nodes[u + 1], 2 + (u % 3) as i64);
+    }
+    assert_eq!(solver.get(&graph).solve(nodes[0], nodes[n - 1]).unwrap(), 2);
+}
+
+fn value_only_solvers(graph: &MaximumFlowGraph<i64>) -> Vec<Box<dyn MaximumFlowSolver<i64>>> {
+    #[cfg_attr(not(feature = "parallel"), allow(unused_mut))]
+    let mut solvers: Vec<Box<dyn MaximumFlowSolver<i64>>> = vec![
+        Box::new(ExcessScaling::new_with(graph, |e| e.data.upper).set_value_only(true)),
+        Box::new(
+            ExcessScaling::new_with(graph, |e| e.data.upper)
+                .set_value_only(true)
+                .set_global_relabel_freq(0.0),
+        ),
+        Box::new(Pseudoflow::new_with(graph, |e| e.data.upper).set_value_only(true)),
+        Box::new(PushRelabelFifo::new_with(graph, |e| e.data.upper).set_value_only(true)),
+        Box::new(
+            PushRelabelFifo::new_with(graph, |e| e.data.upper)
+                .set_value_only(true)
+                .set_gap_relabeling(false)
+                .set_global_relabel_freq(0.0),
+        ),
+        Box::new(PushRelabelHighestLabel::new_with(graph, |e| e.data.upper).set_value_only(true)),
+        Box::new(
+            PushRelabelHighestLabel::new_with(graph, |e| e.data.upper)
+                .set_value_only(true)
+                .set_gap_relabeling(false)
+                .set_global_relabel_freq(0.0),
+        ),
+    ];
+    #[cfg(feature = "parallel")]
+    solvers.push(Box::new(ParallelPushRelabel::new_with(graph, |e| e.data.upper).set_value_only(true)));
+    solvers
+}
+
+#[rstest]
+fn value_only(#[files("tests/maximum_flow/*/*.txt")] path: PathBuf) {
+    let (source, sink, expected, graph) = load_graph(&path);
+    for mut s in value_only_solvers(&graph) {
+        assert_eq!(s.solve(source, sink).unwrap(), expected);
+
+        let reach = s.minimum_cut().unwrap();
+        assert_eq!(check(&graph, &reach), expected);
+        assert_eq!(s.minimum_cut_dag().unwrap().minimum_cuts().next().unwrap(), reach);
+    }
+}
+
+#[test]
+fn value_only_minimum_cut_dag() {
+    let mut seed = 246813579_u64;
+    for _ in 0..50 {
+        let n = 7;
+        let mut graph = MaximumFlowGraph::default();
+        let nodes = graph.add_nodes(n);
+        for _ in 0..14 {
+            let (u, v) = ((lcg_next(&mut seed) % n as u64) as usize, (lcg_next(&mut seed) % n as u64) as usize);
+            graph.add_edge(nodes[u], nodes[v], (lcg_next(&mut seed) % 5) as i64);
+        }
+
+        let mut expected = Vec::new();
+        let objective_value = Dinic::new_with(&graph, |e| e.data.upper)
+            .solve(nodes[0], nodes[n - 1])
+            .unwrap();
+        for mask in 0..(1_usize << n) {
+            let cut: Vec<bool> = (0..n).map(|u| (mask >> u) & 1 == 1).collect();
+            if cut[0] && !cut[n - 1] && check(&graph, &cut) == objective_value {
+                expected.push(cut);
+            }
+        }
+        expected.sort();
+
+        for mut s in value_only_solvers(&graph) {
+            assert_eq!(s.solve(nodes[0], nodes[n - 1]).unwrap(), objective_value);
+
+            let mut actual: Vec<Vec<bool>> = s.minimum_cut_dag().unwrap().minimum_cuts().collect();
+            actual.sort();
+            assert_eq!(actual, expected);
+        }
+    }
+}
+
+#[test]
+fn push_relabel_without_heuristics() {
+    let mut seed = 135792468_u64;
+    for _ in 0..50 {
+        let n = 10;
+        let mut graph = MaximumFlowGraph::default();
+        let nodes = graph.add_nodes(n);
+        for _ in 0..30 {
+            let (u, v) = ((lcg_next(&mut seed) % n as u64) as usize, (lcg_next(&mut seed) % n as u64) as usize);
+            graph.add_edge(nodes[u], nodes[v], (lcg_next(&mut seed) % 10) as i64);
+        }
+
+        let expected = Dinic::new_with(&graph, |e| e.data.upper)
+            .solve(nodes[0], nodes[n - 1])
+            .unwrap();
+        let solvers: Vec<Box<dyn MaximumFlowSolver<i64>>> = vec![
+            Box::new(
+                PushRelabelFifo::new_with(&graph, |e| e.data.upper)
+                    .set_gap_relabeling(false)
+                    .set_global_relabel_freq(0.0),
+            ),
+            Box::new(
+                PushRelabelHighestLabel::new_with(&graph, |e| e.data.upper)
+                    .set_gap_relabeling(false)
+                    .set_global_relabel_freq(0.0),
+            ),
+        ];
+        for mut s in solvers {
+            assert_eq!(s.solve(nodes[0], nodes[n - 1]).unwrap(), expected);
+
+            let flows = s.flows().unwrap();
+            let mut balance = vec![0; n];
+            for (e, &flow) in graph.edges().zip(flows.iter()) {
+                assert!(0 <= flow && flow <= e.data.upper);
+                balance[e.u.index()] -= flow;
+                balance[e.v.index()] += flow;
+            }
+            assert_eq!(balance[n - 1], expected);
+            assert!(balance[1..n - 1].iter().all(|&b| b == 0));
+            assert_eq!(check(&graph, &s.minimum_cut().unwrap()), expected);
+        }
+    }
+}
+
+#[test]
+fn push_relabel_fifo_global_relabel() {
+    // a global relabel can make the arcs before the current edge of a node admissible again; skipping them let the
+    // gap heuristic cut off nodes which could still reach the sink
+    let edges = [
+        (0, 4, 5),
+        (4, 3, 2),
+        (5, 1, 9),
+        (3, 4, 8),
+        (4, 0, 1),
+        (5, 4, 6),
+        (4, 2, 5),
+        (2, 0, 8),
+        (1, 2, 2),
+        (0, 3, 1),
+        (2, 1, 1),
+        (1, 4, 4),
+        (3, 4, 1),
+        (1, 3, 8),
+        (3, 5, 4),
+        (4, 3, 4),
+        (0, 5, 5),
+        (1, 5, 8),
+    ];
+    let mut graph = MaximumFlowGraph::new_directed();
+    let nodes = graph.add_nodes(6);
+    for (u, v, upper) in edges {
+        graph.add_edge(nodes[u], nodes[v], upper);
+    }
+    for global_relabel_freq in [1.0, 10.0, 50.0] {
+        let mut solver = PushRelabelFifo::new_with(&graph, |e| e.data.upper).set_global_relabel_freq(global_relabel_freq);
+        assert_eq!(solver.solve(nodes[0], nodes[5]), Ok(10));
+        assert_eq!(check(&graph, &solver.minimum_cut().unwrap()), 10);
+    }
+}
+
+#[cfg(feature = "parallel")]
+#[test]
+fn parallel_push_relabel_threads() {
+    let mut seed = 314159265_u64;
+    let (h, w) = (60, 60);
+    let mut graph = MaximumFlowGraph::new_directed();
+    let nodes = graph.add_nodes(h * w + 2);
+    let (source, sink) = (nodes[h * w], nodes[h * w + 1]);
+    for i in 0..h {
+        for j in 0..w {
+            let u = nodes[i * w + j];
+            if j + 1 < w {
+                graph.add_edge(u, nodes[i * w + j + 1], (lcg_next(&mut seed) % 20) as i64);
+                graph.add_edge(nodes[i * w + j + 1], u, (lcg_next(&mut seed) % 20) as i64);
+            }
+            if i + 1 < h {
+                graph.add_edge(u, nodes[(i + 1) * w + j], (lcg_next(&mut seed) % 20) as i64);
+                graph.add_edge(nodes[(i + 1) * w + j], u, (lcg_next(&mut seed) % 20) as i64);
+            }
+            graph.add_edge(source, u, (lcg_next(&mut seed) % 50) as i64);
+            graph.add_edge(u, sink, (lcg_next(&mut seed) % 50) as i64);
+        }
+    }
+
+    let expected = Dinic::new_with(&graph, |e| e.data.upper).solve(source, sink).unwrap();
+    for num_threads in [1, 2, 4] {
+        for value_only in [false, true] {
+            let mut s = ParallelPushRelabel::new_with(&graph, |e| e.data.upper)
+                .set_num_threads(num_threads)
+                .set_value_only(value_only);
+            assert_eq!(s.solve(source, sink).unwrap(), expected);
+            assert_eq!(check(&graph, &s.minimum_cut().unwrap()), expected);
+
+            if !value_only {
+                let flows = s.flows().unwrap();
+                let mut balance = vec![0; h * w + 2];
+                for (e, &flow) in graph.edges().zip(flows.iter()) {
+                    assert!(0 <= flow && flow <= e.data.upper);
+                    balance[e.u.index()] -= flow;
+                    balance[e.v.index()] += flow;
+                }
+                assert_eq!(balance[sink.index()], expected);
+                assert!(balance[..h * w].iter().all(|&b| b == 0));
+            }
+        }
+    }
+}
+
+#[test]
+fn boykov_kolmogorov_terminal_capacities() {
+    let mut seed = 192837465_u64;
+    for neighbors in [&[(0, 1), (1, 0)][..], &[(0, 1), (1, 0), (1, 1), (1, -1)][..]] {
+        let (h, w) = (6, 7);
+        let mut graph = MaximumFlowGraph::new_directed();
+        let nodes = graph.add_nodes(h * w + 2);
+        let (source, sink) = (nodes[h * w], nodes[h * w + 1]);
+        for i in 0..h as i64 {
+            for j in 0..w as i64 {
+                for &(di, dj) in neighbors {
+                    let (ni, nj) = (i + di, j + dj);
+                    if 0 <= ni && ni < h as i64 && 0 <= nj && nj < w as i64 {
+                        let (u, v) = (nodes[(i * w as i64 + j) as usize], nodes[(ni * w as i64 + nj) as usize]);
+                        graph.add_edge(u, v, (lcg_next(&mut seed) % 5) as i64);
+                        graph.add_edge(v, u, (lcg_next(&mut seed) % 5) as i64);
+                    }
+                }
+            }
+        }
+        let terminals: Vec<(i64, i64)> = (0..h * w)
+            .map(|_| ((lcg_next(&mut seed) % 10) as i64, (lcg_next(&mut seed) % 10) as i64))
+            .collect();
+
+        let mut bk = BoykovKolmogorov::new(&graph);
+        let mut materialized = MaximumFlowGraph::new_directed();
+        materialized.add_nodes(graph.num_nodes());
+        for e in graph.edges() {
+            materialized.add_edge(e.u, e.v, e.data.upper);
+        }
+        for (u, &(source_upper, sink_upper)) in terminals.iter().enumerate() {
+            bk.add_terminal_capacities(nodes[u], source_upper, sink_upper).unwrap();
+            materialized.add_edge(source, nodes[u], source_upper);
+            materialized.add_edge(nodes[u], sink, sink_upper);
+        }
+
+        let expected = Dinic::new(&materialized).solve(source, sink).unwrap();
+        assert_eq!(bk.solve(source, sink).unwrap(), expected);
+
+        let reach = bk.minimum_cut().unwrap();
+        assert_eq!(check(&materialized, &reach), expected);
+        assert_eq!(bk.minimum_cut_dag().unwrap().minimum_cuts().next().unwrap(), reach);
+
+        assert_eq!(bk.add_terminal_capacities(nodes[0], -1, 0), Err(MaximumFlowError::InvalidParameter));
+        let invalid = MaximumFlowGraph::<i64>::new_directed().add_nodes(h * w + 3)[h * w + 2];
+        assert_eq!(
+            bk.add_terminal_capacities(invalid, 1, 1),
+            Err(MaximumFlowError::InvalidNodeId { node_id: invalid })
+        );
+        assert_eq!(bk.solve(source, sink), Ok(expected));
+    }
+}
+
+// (u, a, b, c, d): arcs s -> u with capacity a + λb and u -> t with capacity c - λd,
+// or an edge a -> b with capacity c if u == usize::MAX
+type ParametricArcs = Vec<(usize, i64, i64, i64, i64)>;
+
+// random instance with parametric terminal arcs, feasible for 0 <= λ <= 6
+fn parametric_instance(seed: &mut u64, n: usize) -> (ParametricMaximumFlow<i64>, ParametricArcs, Vec<NodeId>) {
+    let mut graph = MaximumFlowGraph::new_directed();
+    let nodes = graph.add_nodes(n);
+    for _ in 0..2 * n {
+        let (u, v) = ((lcg_next(seed) % n as u64) as usize, (lcg_next(seed) % n as u64) as usize);
+        graph.add_edge(nodes[u], nodes[v], (lcg_next(seed) % 6) as i64);
+    }
+
+    let mut parametric = ParametricMaximumFlow::new(&graph);
+    let mut terminals = Vec::new();
+    for (u, &node) in nodes.iter().enumerate().skip(1).take(n - 2) {
+        let (a, b) = ((lcg_next(seed) % 5) as i64, (lcg_next(seed) % 3) as i64);
+        let d = (lcg_next(seed) % 3) as i64;
+        let c = 6 * d + (lcg_next(seed) % 5) as i64;
+        parametric.add_source_arc(node, a, b).unwrap();
+        parametric.add_sink_arc(node, c, d).unwrap();
+        terminals.push((u, a, b, c, d));
+    }
+    for e in graph.edges() {
+        terminals.push((usize::MAX, e.u.index() as i64, e.v.index() as i64, e.data.upper, 0));
+    }
+    (parametric, terminals, nodes)
+}
+
+// capacity of the cut at λ = p / q, scaled by q
+fn parametric_cut_capacity(terminals: &[(usize, i64, i64, i64, i64)], cut: &[bool], (p, q): (i64, i64)) -> i64 {
+    let mut capacity = 0;
+    for &(u, a, b, c, d) in terminals {
+        if u == usize::MAX {
+            if cut[a as usize] && !cut[b as usize] {
+                capacity += q * c;
+            }
+        } else if !cut[u] {
+            capacity += q * a + p * b;
+        } else {
+            capacity += q * c - p * d;
+        }
+    }
+    capacity
+}
+
+#[test]
+fn parametric_solve() {
+    let mut seed = 97531_u64;
+    for _ in 0..100 {
+        let n = 2 + (lcg_next(&mut seed) % 10) as usize;
+        let (parametric, terminals, nodes) = parametric_instance(&mut seed, n);
+        let (source, sink) = (nodes[0], nodes[n - 1]);
+
+        let lambdas = [0, 0, 1, 2, 4, 5, 6];
+        let results = parametric.solve(source, sink, &lambdas).unwrap();
+        for (i, (&lambda, (value, cut))) in lambdas.iter().zip(results.iter()).enumerate() {
+            let mut graph = MaximumFlowGraph::new_directed();
+            graph.add_nodes(n);
+            for &(u, a, b, c, d) in terminals.iter() {
+                if u == usize::MAX {
+                    graph.add_edge(nodes[a as usize], nodes[b as usize], c);
+                } else {
+                    graph.add_edge(source, nodes[u], a + lambda * b);
+                    graph.add_edge(nodes[u], sink, c - lambda * d);
+                }
+            }
+            let mut dinic = Dinic::new(&graph);
+            assert_eq!(*value, dinic.solve(source, sink).unwrap());
+            assert_eq!(*cut, dinic.minimum_cut().unwrap());
+            if i > 0 {
+                assert!(results[i - 1].1.iter().zip(cut.iter()).all(|(&a, &b)| !a || b));
+            }
+        }
+    }
+
+    let mut graph = MaximumFlowGraph::new_directed();
+    let nodes = graph.add_nodes(3);
+    let mut parametric = ParametricMaximumFlow::new(&graph);
+    parametric.add_sink_arc(nodes[1], 6, 1).unwrap();
+    assert_eq!(parametric.add_source_arc(nodes[1], 0, -1), Err(MaximumFlowError::InvalidParameter));
+    let outside = MaximumFlowGraph::<i64>::new_directed().add_nodes(4)[3];
+    assert_eq!(parametric.add_sink_arc(outside, 1, 0), Err(MaximumFlowError::InvalidNodeId { node_id: outside }));
+    assert_eq!(parametric.solve(nodes[0], nodes[2], &[2, 1]).err(), Some(MaximumFlowError::InvalidParameter));
+    assert_eq!(parametric.solve(nodes[0], nodes[2], &[7]).err(), Some(MaximumFlowError::InvalidParameter));
+    assert_eq!(parametric.breakpoints(nodes[0], nodes[2], 0, 7).err(), Some(MaximumFlowError::InvalidParameter));
+}
+
+#[test]
+fn parametric_breakpoints() {
+    let mut seed = 8642_u64;
+    for _ in 0..200 {
+        let n = 2 + (lcg_next(&mut seed) % 9) as usize;
+        let (parametric, terminals, nodes) = parametric_instance(&mut seed, n);
+        let result = parametric.breakpoints(nodes[0], nodes[n - 1], 0, 6).unwrap();
+        assert_eq!(result.source_sides.len(), result.breakpoints.len() + 1);
+        assert_eq!(result.capacities.len(), result.breakpoints.len() + 1);
+
+        let cuts: Vec<Vec<bool>> = (0..(1_usize << n))
+            .map(|mask| (0..n).map(|u| (mask >> u) & 1 == 1).collect::<Vec<bool>>())
+            .filter(|cut| cut[0] && !cut[n - 1])
+            .collect();
+        let minimum = |lambda: (i64, i64)| {
+            cuts.iter()
+                .map(|cut| parametric_cut_capacity(&terminals, cut, lambda))
+                .min()
+                .unwrap()
+        };
+
+        let mut ends = vec![(0, 1)];
+        ends.extend(result.breakpoints.iter().copied());
+        ends.push((6, 1));
+        for i in 0..result.source_sides.len() {
+            let ((p1, q1), (p2, q2)) = (ends[i], ends[i + 1]);
+            assert!(p1 * q2 < p2 * q1);
+            if i > 0 {
+                assert!(result.capacities[i - 1].1 > result.capacities[i].1);
+            }
+
+            for lambda in [(p1, q1), (p1 * q2 + p2 * q1, 2 * q1 * q2), (p2, q2)] {
+                let (constant, slope) = result.capacities[i];
+                assert_eq!(
+                    parametric_cut_capacity(&terminals, &result.source_sides[i], lambda),
+                    lambda.1 * constant + lambda.0 * slope
+                );
+                assert_eq!(lambda.1 * constant + lambda.0 * slope, minimum(lambda));
+            }
+
+            let middle = (p1 * q2 + p2 * q1, 2 * q1 * q2);
+            let value = minimum(middle);
+            let expected: Vec<bool> = (0..n)
+                .map(|u| {
+                    cuts.iter()
+                        .filter(|cut| parametric_cut_capacity(&terminals, cut, middle) == value)
+                        .all(|cut| cut[u])
+                })
+                .collect();
+            assert_eq!(result.source_sides[i], expected);
+        }
+
+        // the probes resume the run at lambda_min, which need not be zero
+        let inner = parametric.breakpoints(nodes[0], nodes[n - 1], 1, 5).unwrap();
+        let expected: Vec<_> = result.breakpoints.iter().filter(|&&(p, q)| q < p && p < 5 * q).copied().collect();
+        assert_eq!(inner.breakpoints, expected);
+        let piece = result.breakpoints.iter().filter(|&&(p, q)| p <= q).count();
+        assert_eq!(inner.source_sides[0], result.source_sides[piece]);
+    }
+
+    // terminals outside the graph
+    let (parametric, _, nodes) = parametric_instance(&mut seed, 4);
+    let outside = MaximumFlowGraph::<i64>::new_directed().add_nodes(5)[4];
+    let invalid = MaximumFlowError::InvalidTerminal { source: nodes[0], sink: outside, num_nodes: 4 };
+    assert_eq!(parametric.breakpoints(nodes[0], outside, 0, 6).err(), Some(invalid.clone()));
+    assert_eq!(parametric.solve(nodes[0], outside, &[0, 6]).err(), Some(invalid));
+    assert!(parametric.breakpoints(nodes[0], nodes[0], 0, 6).is_err());
+}
+
+// a grid with a diagonal in every cell and some edges left out, embedded by the angles of the edges at each node
+fn planar_instance<D: Direction>(
+    seed: &mut u64,
+    graph: &mut MaximumFlowGraph<i64, D>,
+    rows: usize,
+    cols: usize,
+) -> (Vec<NodeId>, Vec<Vec<EdgeId>>) {
+    let nodes = graph.add_nodes(rows * cols);
+    let mut pairs = Vec::new();
+    for i in 0..rows {
+        for j in 0..cols {
+            if j + 1 < cols {
+                pairs.push(((i, j), (i, j + 1)));
+            }
+            if i + 1 < rows {
+                pairs.push(((i, j), (i + 1, j)));
+            }
+            if i + 1 < rows && j + 1 < cols {
+                match lcg_next(seed) % 3 {
+                    0 => pairs.push(((i, j), (i + 1, j + 1))),
+                    1 => pairs.push(((i, j + 1), (i + 1, j))),
+                    _ => {}
+                }
+            }
+        }
+    }
+    let mut rotation = vec![Vec::new(); rows * cols];
+    for (a, b) in pairs {
+        if lcg_next(seed).is_multiple_of(5) {
+            continue;
+        }
+        let (a, b) = if lcg_next(seed).is_multiple_of(2) { (a, b) } else { (b, a) };
+        let (u, v) = (a.0 * cols + a.1, b.0 * cols + b.1);
+        let e = graph.add_edge(nodes[u], nodes[v], (lcg_next(seed) % 10) as i64).unwrap();
+        rotation[u].push((e, b));
+        rotation[v].push((e, a));
+    }
+    let rotation = rotation
+        .into_iter()
+        .enumerate()
+        .map(|(u, mut around)| {
+            let (i, j) = ((u / cols) as f64, (u % cols) as f64);
+            let angle = |&(_, (y, x)): &(EdgeId, (usize, usize))| (y as f64 - i).atan2(x as f64 - j);
+            around.sort_by(|a, b| angle(a).total_cmp(&angle(b)));
+            around.into_iter().map(|(e, _)| e).collect()
+        })
+        .collect();
+    (nodes, rotation)
+}
+
+fn check_planar<D: Direction>(graph: &MaximumFlowGraph<i64, D>, rotation: &[Vec<EdgeId>], s: NodeId, t: NodeId) {
+    let expected = Dinic::new(graph).solve(s, t).unwrap();
+    let mut solver = PlanarMaximumFlow::<i64>::new(graph, rotation).unwrap();
+    assert_eq!(solver.solve(s, t), Ok(expected));
+
+    let flows = solver.flows().unwrap();
+    let mut balance = vec![0; graph.num_nodes()];
+    for (e, &flow) in graph.edges().zip(flows.iter()) {
+        assert!(flow.abs() <= e.data.upper && (flow >= 0 || !D::IS_DIRECTED));
+        balance[e.u.index()] -= flow;
+        balance[e.v.index()] += flow;
+    }
+    for (u, &b) in balance.iter().enumerate() {
+        let expected = if u == s.index() {
+            -expected
+        } else if u == t.index() {
+            expected
+        } else {
+            0
+        };
+        assert_eq!(b, expected);
+    }
+
+    let side = solver.minimum_cut().unwrap();
+    assert!(side[s.index()] && !side[t.index()]);
+    let cut: i64 = graph
+        .edges()
+        .filter(|e| {
+            (side[e.u.index()] && !side[e.v.index()]) || (!D::IS_DIRECTED && side[e.v.index()] && !side[e.u.index()])
+        })
+        .map(|e| e.data.upper)
+        .sum();
+    assert_eq!(cut, expected);
+}
+
+#[test]
+fn planar() {
+    let mut seed = 1732050807_u64;
+    for _ in 0..30 {
+        let (rows, cols) = (2 + (lcg_next(&mut seed) % 5) as usize, 2 + (lcg_next(&mut seed) % 5) as usize);
+        let mut graph = MaximumFlowGraph::new_directed();
+        let (nodes, rotation) = planar_instance(&mut seed, &mut graph, rows, cols);
+        let mut undirected = MaximumFlowGraph::new_undirected();
+        let (_, undirected_rotation) = planar_instance(&mut seed, &mut undirected, rows, cols);
+
+        // opposite corners lie on the outer face, and other pairs may share no face
+        let n = rows * cols;
+        let mut pairs = vec![(0, n - 1), (cols - 1, n - cols)];
+        for _ in 0..5 {
+            pairs.push(((lcg_next(&mut seed) % n as u64) as usize, (lcg_next(&mut seed) % n as u64) as usize));
+        }
+        for (s, t) in pairs.into_iter().filter(|&(s, t)| s != t) {
+            check_planar(&graph, &rotation, nodes[s], nodes[t]);
+            check_planar(&undirected, &undirected_rotation, nodes[s], nodes[t]);
+        }
+    }
+
+    // in an octahedron, drawn as two nested triangles, opposite nodes share no face
+    let mut graph = MaximumFlowGraph::new_directed();
+    let nodes = graph.add_nodes(6);
+    let angles = [90.0_f64, 270.0, 210.0, 30.0, 330.0, 150.0];
+    let position = |u: usize| {
+        let (radius, angle) = (if u.is_multiple_of(2) { 1.0 } else { 3.0 }, angles[u].to_radians());
+        (radius * angle.cos(), radius * angle.sin())
+    };
+    let mut rotation = vec![Vec::new(); 6];
+    for u in 0..6_usize {
+        for v in u + 1..6 {
+            if v != u + 1 || !u.is_multiple_of(2) {
+                let e = graph.add_edge(nodes[u], nodes[v], 1 + (u + v) as i64).unwrap();
+                rotation[u].push((e, v));
+                rotation[v].push((e, u));
+            }
+        }
+    }
+    let rotation: Vec<Vec<EdgeId>> = rotation
+        .into_iter()
+        .enumerate()
+        .map(|(u, mut around)| {
+            let (x, y) = position(u);
+            let angle = |&(_, v): &(EdgeId, usize)| (position(v).1 - y).atan2(position(v).0 - x);
+            around.sort_by(|a, b| angle(a).total_cmp(&angle(b)));
+            around.into_iter().map(|(e, _)| e).collect()
+        })
+        .collect();
+    for (s, t) in [(0, 2), (3, 4), (0, 1), (5, 4)] {
+        check_planar(&graph, &rotation, nodes[s], nodes[t]);
+    }
+
+    // flow on undirected edges may be negative
+    let mut undirected = MaximumFlowGraph::<u64, _>::new_undirected();
+    let nodes = undirected.add_nodes(2);
+    let e = undirected.add_edge(nodes[0], nodes[1], 5).unwrap();
+    assert!(matches!(
+        PlanarMaximumFlow::<u64>::new(&undirected, &[vec![e], vec![e]]),
+        Err(MaximumFlowError::UnsignedFlowOnUndirectedGraph)
+    ));
+
+    // K3,3 has no planar embedding
+    let mut graph = MaximumFlowGraph::new_directed();
+    let nodes = graph.add_nodes(6);
+    let mut rotation = vec![Vec::new(); 6];
+    for u in 0..3 {
+        for v in 3..6 {
+            let e = graph.add_edge(nodes[u], nodes[v], 1).unwrap();
+            rotation[u].push(e);
+            rotation[v].push(e);
+        }
+    }
+    assert!(matches!(
+        PlanarMaximumFlow::<i64>::new(&graph, &rotation),
+        Err(MaximumFlowError::InvalidParameter)
+    ));
+    rotation[0].pop();
+    assert!(PlanarMaximumFlow::<i64>::new(&graph, &rotation).is_err());
+
+    // the path 0 -> 1 -> 2 with a self-loop at 1
+    let mut graph = MaximumFlowGraph::new_directed();
+    let nodes = graph.add_nodes(3);
+    let e = [
+        graph.add_edge(nodes[0], nodes[1], 3).unwrap(),
+        graph.add_edge(nodes[1], nodes[1], 7).unwrap(),
+        graph.add_edge(nodes[1], nodes[2], 2).unwrap(),
+    ];
+    let mut solver = PlanarMaximumFlow::<i64>::new(&graph, &[vec![e[0]], vec![e[0], e[2]], vec![e[2]]]).unwrap();
+    assert_eq!(solver.flows(), Err(MaximumFlowError::NotSolved));
+    assert_eq!(solver.solve(nodes[0], nodes[2]), Ok(2));
+    assert_eq!(solver.flows(), Ok(vec![2, 0, 2]));
+    assert_eq!(solver.minimum_cut(), Ok(vec![true, true, false]));
+    assert_eq!(solver.solve(nodes[2], nodes[0]), Ok(0));
+    assert!(matches!(solver.solve(nodes[0], nodes[0]), Err(MaximumFlowError::InvalidTerminal { .. })));
+}
+
+// (upper, weight)
+fn check_lexicographic<D: Direction>(graph: &Graph<D, (), (i64, i64)>, nodes: &[NodeId]) {
+    let n = nodes.len();
+    let (s, t) = (nodes[0], nodes[n - 1]);
+    let crosses = |mask: usize, u: NodeId, v: NodeId| {
+        let side = |x: NodeId| (mask >> x.index()) & 1 == 1;
+        (side(u) && !side(v)) || (!D::IS_DIRECTED && side(v) && !side(u))
+    };
+    let expected = (0..(1_usize << n))
+        .filter(|mask| mask & 1 == 1 && (mask >> (n - 1)) & 1 == 0)
+        .map(|mask| {
+            graph
+                .edges()
+                .filter(|e| crosses(mask, e.u, e.v))
+                .fold((0, 0), |(c, w), e| (c + e.data.0, w + e.data.1))
+        })
+        .min()
+        .unwrap();
+
+    let mut solver = LexicographicMinimumCut::<i64>::new_with(graph, |e| e.data.0, |e| e.data.1);
+    assert_eq!(solver.solve(s, t), Ok(expected));
+    let side = solver.minimum_cut().unwrap();
+    assert!(side[s.index()] && !side[t.index()]);
+    let cut = solver
+        .cut_edges()
+        .unwrap()
+        .iter()
+        .map(|&e| graph.get_edge(e).unwrap().data)
+        .fold((0, 0), |(c, w), (upper, weight)| (c + upper, w + weight));
+    assert_eq!(cut, expected);
+}
+
+#[test]
+fn lexicographic_minimum_cut() {
+    let mut seed = 2236067977_u64;
+    for _ in 0..100 {
+        let n = 2 + (lcg_next(&mut seed) % 7) as usize;
+        let mut graphs = [Graph::new_directed(), Graph::new_directed()];
+        let mut undirected = [Graph::new_undirected(), Graph::new_undirected()];
+        let nodes = graphs[0].add_nodes(n);
+        graphs[1].add_nodes(n);
+        undirected[0].add_nodes(n);
+        undirected[1].add_nodes(n);
+        for _ in 0..3 * n {
+            let (u, v) = ((lcg_next(&mut seed) % n as u64) as usize, (lcg_next(&mut seed) % n as u64) as usize);
+            let upper = (lcg_next(&mut seed) % 4) as i64;
+            let weight = (lcg_next(&mut seed) % 5) as i64;
+            // the second graphs count the edges
+            for (i, w) in [(0, weight), (1, 1)] {
+                graphs[i].add_edge(nodes[u], nodes[v], (upper, w));
+                undirected[i].add_edge(nodes[u], nodes[v], (upper, w));
+            }
+        }
+        graphs.iter().for_each(|g| check_lexicographic(g, &nodes));
+        undirected.iter().for_each(|g| check_lexicographic(g, &nodes));
+    }
+
+    // three paths s -> a_i -> t with unit capacities, where the first edges weigh so much that scaling the
+    // capacities by the total weight would overflow
+    let mut graph = MaximumFlowGraph::new_directed();
+    let nodes = graph.add_nodes(5);
+    for i in 1..4 {
+        graph.add_edge(nodes[0], nodes[i], 1);
+        graph.add_edge(nodes[i], nodes[4], 1);
+    }
+    let heavy = i64::MAX / 4;
+    let mut solver =
+        LexicographicMinimumCut::<i64>::new_with(&graph, |e| e.data.upper, |e| if e.u == nodes[0] { heavy } else { 1 });
+    assert_eq!(solver.minimum_cut(), Err(MaximumFlowError::NotSolved));
+    assert_eq!(solver.solve(nodes[0], nodes[4]), Ok((3, 3)));
+    assert_eq!(solver.minimum_cut(), Ok(vec![true, true, true, true, false]));
+    let mut solver = LexicographicMinimumCut::<i64>::new(&graph);
+    assert_eq!(solver.solve(nodes[0], nodes[4]), Ok((3, 3)));
+    let mut solver = LexicographicMinimumCut::<i64>::new_with(&graph, |e| e.data.upper, |_| -1);
+    assert_eq!(solver.solve(nodes[0], nodes[4]), Err(MaximumFlowError::InvalidParameter));
+}
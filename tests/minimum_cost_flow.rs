@@ -1,6 +1,9 @@
+mod common;
+
+use common::lcg_next;
 use network_algorithms::algorithms::minimum_cost_flow::prelude::*;
 use network_algorithms::core::numeric::CostNum;
-use network_algorithms::ids::EdgeId;
+use network_algorithms::ids::{EdgeId, NodeId};
 use rstest::rstest;
 use rstest_reuse::*;
 use std::{fmt::Debug, fs::read_to_string, path::Path, path::PathBuf};
@@ -200,3 +203,132 @@ fn not_solved(#[case] solver: Solver) {
     assert_eq!(solver.potential(nodes[0]).err().unwrap(), MinimumCostFlowError::NotSolved);
     assert_eq!(solver.potentials().err().unwrap(), MinimumCostFlowError::NotSolved);
 }
+
+// augmenting paths as long as the graph must not overflow the stack
+#[test]
+fn primal_dual_long_path() {
+    let n = 200_000;
+    let mut graph = MinimumCostFlowGraph::<i128>::default();
+    let nodes = graph.add_nodes(n);
+    for u in 0..n - 1 {
+        graph.add_edge(nodes[u], nodes[u + 1], 0, 2, 1);
+    }
+    graph.get_node_mut(nodes[0]).unwrap().data.b = 2;
+    graph.get_node_mut(nodes[n - 1]).unwrap().data.b = -2;
+
+    assert_eq!(PrimalDual::new(&graph).solve().unwrap(), 2 * (n as i128 - 1));
+}
+
+// solves with the (capacity, cost) of every node and checks the flow in terms of the original nodes and edges
+fn solve_node_capacitated<S: MinimumCostFlowSolver<i128>>(
+    graph: &MinimumCostFlowGraph<i128>,
+    nodes: &[NodeId],
+    node_data: &[(Option<i128>, Option<i128>)],
+) -> Option<i128> {
+    let mut solver = NodeCapacitated::<i128, S>::new(graph);
+    for (&u, &(capacity, cost)) in nodes.iter().zip(node_data.iter()) {
+        capacity.map(|capacity| solver.set_node_capacity(u, capacity));
+        cost.map(|cost| solver.set_node_cost(u, cost));
+    }
+    let cost = match solver.solve() {
+        Ok(cost) => cost,
+        Err(err) => {
+            assert_eq!(err, MinimumCostFlowError::Infeasible);
+            return None;
+        }
+    };
+    let flows = solver.flows().unwrap();
+    assert_eq!(solver.potentials().unwrap().len(), graph.num_nodes());
+
+    let mut inflow = vec![0; graph.num_nodes()];
+    let mut outflow = vec![0; graph.num_nodes()];
+    let mut expected = 0;
+    for (e, &flow) in graph.edges().zip(flows.iter()) {
+        assert!(e.data.lower <= flow && flow <= e.data.upper);
+        outflow[e.u.index()] += flow;
+        inflow[e.v.index()] += flow;
+        expected += e.data.cost * flow;
+    }
+    for (u, node) in graph.nodes().enumerate() {
+        let b = node.data.b;
+        assert_eq!(outflow[u] - inflow[u], b);
+        let through = inflow[u] + b.max(0);
+        let node_id = nodes[u];
+        assert!(solver.node_capacity(node_id).is_none_or(|capacity| through <= capacity));
+        if let Some(node_flow) = solver.node_flow(node_id).unwrap() {
+            assert_eq!(node_flow, through);
+        }
+        expected += solver.node_cost(node_id).unwrap_or(0) * through;
+    }
+    assert_eq!(cost, expected);
+    Some(cost)
+}
+
+#[test]
+fn node_capacities_and_costs() {
+    let mut seed = 1414213562_u64;
+    for _ in 0..50 {
+        let n = 7;
+        let mut graph = MinimumCostFlowGraph::<i128>::default();
+        let nodes = graph.add_nodes(n);
+        for _ in 0..30 {
+            let (u, v) = ((lcg_next(&mut seed) % n as u64) as usize, (lcg_next(&mut seed) % n as u64) as usize);
+            let upper = (lcg_next(&mut seed) % 8) as i128;
+            let cost = (lcg_next(&mut seed) % 11) as i128 - 3;
+            graph.add_edge(nodes[u], nodes[v], 0, upper, cost);
+        }
+        for _ in 0..2 {
+            let (u, v) = ((lcg_next(&mut seed) % n as u64) as usize, (lcg_next(&mut seed) % n as u64) as usize);
+            let b = (lcg_next(&mut seed) % 3) as i128;
+            graph.get_node_mut(nodes[u]).unwrap().data.b += b;
+            graph.get_node_mut(nodes[v]).unwrap().data.b -= b;
+        }
+        let node_data: Vec<_> = nodes
+            .iter()
+            .map(|_| match lcg_next(&mut seed) % 4 {
+                0 => (Some((lcg_next(&mut seed) % 8) as i128), None),
+                1 => (None, Some((lcg_next(&mut seed) % 5) as i128)),
+                _ => (None, None),
+            })
+            .collect();
+
+        let expected = solve_node_capacitated::<PrimalNetworkSimplex<i128>>(&graph, &nodes, &node_data);
+        assert_eq!(solve_node_capacitated::<SuccessiveShortestPath<i128>>(&graph, &nodes, &node_data), expected);
+        assert_eq!(solve_node_capacitated::<CostScalingPushRelabel<i128>>(&graph, &nodes, &node_data), expected);
+        assert_eq!(solve_node_capacitated::<OutOfKilter<i128>>(&graph, &nodes, &node_data), expected);
+    }
+}
+
+#[test]
+fn node_cost_changes_route() {
+    // two routes of edge cost 2, one through a node of cost 3 and one through a node of capacity 1
+    let mut graph = MinimumCostFlowGraph::<i128>::default();
+    let nodes = graph.add_nodes(4);
+    let edges = [
+        graph.add_edge(nodes[0], nodes[1], 0, 5, 1).unwrap(),
+        graph.add_edge(nodes[1], nodes[3], 0, 5, 1).unwrap(),
+        graph.add_edge(nodes[0], nodes[2], 0, 5, 1).unwrap(),
+        graph.add_edge(nodes[2], nodes[3], 0, 5, 1).unwrap(),
+    ];
+    graph.set_excess(nodes[0], 3);
+    graph.set_excess(nodes[3], -3);
+
+    let mut solver = NodeCapacitated::<i128>::new(&graph);
+    solver.set_node_cost(nodes[1], 3);
+    solver.set_node_capacity(nodes[2], 1);
+    assert_eq!(solver.flow(edges[0]), Err(MinimumCostFlowError::NotSolved));
+    assert_eq!(solver.solve(), Ok(2 * 3 + 3 * 2));
+    assert_eq!(solver.flows().unwrap(), vec![2, 2, 1, 1]);
+    assert_eq!(solver.flow(edges[2]), Ok(1));
+    assert_eq!(solver.node_flow(nodes[1]), Ok(Some(2)));
+    assert_eq!(solver.node_flow(nodes[0]), Ok(None));
+
+    // ids outside the graph
+    let mut other = MinimumCostFlowGraph::<i128>::default();
+    let node_id = other.add_nodes(5)[4];
+    let edge_id = (0..5).map(|_| other.add_edge(node_id, node_id, 0, 1, 0).unwrap()).last().unwrap();
+    assert_eq!(solver.flow(edge_id), Err(MinimumCostFlowError::InvalidEdgeId { edge_id }));
+    assert_eq!(solver.node_flow(node_id), Err(MinimumCostFlowError::InvalidNodeId { node_id }));
+    assert_eq!(solver.potential(node_id), Err(MinimumCostFlowError::InvalidNodeId { node_id }));
+    assert_eq!(solver.set_node_cost(node_id, 1), None);
+}
@@ -0,0 +1,155 @@
+mod common;
+
+use common::lcg_next;
+use network_algorithms::algorithms::minimum_cut::prelude::*;
+use network_algorithms::{
+    Graph,
+    algorithms::maximum_flow::edge::MaximumFlowEdge,
+    direction::{Directed, Undirected},
+};
+use rstest::rstest;
+
+type UndirectedGraph = Graph<Undirected, (), MaximumFlowEdge<i64>>;
+type DirectedGraph = Graph<Directed, (), MaximumFlowEdge<i64>>;
+
+fn random_graph(seed: &mut u64, n: usize, m: usize, max_capacity: u64) -> UndirectedGraph {
+    let mut graph = UndirectedGraph::default();
+    let nodes = graph.add_nodes(n);
+    for _ in 0..m {
+        let (u, v) = ((lcg_next(seed) % n as u64) as usize, (lcg_next(seed) % n as u64) as usize);
+        graph.add_edge(nodes[u], nodes[v], MaximumFlowEdge { upper: (lcg_next(seed) % max_capacity) as i64 });
+    }
+    graph
+}
+
+fn cut_capacity(graph: &UndirectedGraph, side: &[bool]) -> i64 {
+    graph
+        .edges()
+        .filter(|e| side[e.u.index()] != side[e.v.index()])
+        .map(|e| e.data.upper)
+        .sum()
+}
+
+fn brute_force(graph: &UndirectedGraph) -> i64 {
+    let n = graph.num_nodes();
+    (1..(1_usize << (n - 1)))
+        .map(|mask| cut_capacity(graph, &(0..n).map(|u| (mask >> u) & 1 == 1).collect::<Vec<_>>()))
+        .min()
+        .unwrap()
+}
+
+fn check(graph: &UndirectedGraph, (value, side): (i64, Vec<bool>), expected: i64) {
+    assert_eq!(value, expected);
+    assert_eq!(cut_capacity(graph, &side), expected);
+    assert!(side.iter().any(|&s| s) && side.iter().any(|&s| !s));
+}
+
+#[rstest]
+#[case::sparse(9, 12)]
+#[case::dense(9, 40)]
+#[case::disconnected(10, 4)]
+fn random(#[case] n: usize, #[case] m: usize) {
+    let mut seed = 1357924680_u64 + (n * m) as u64;
+    for _ in 0..30 {
+        let graph = random_graph(&mut seed, n, m, 10);
+        let expected = brute_force(&graph);
+
+        check(&graph, StoerWagner::new(&graph).solve().unwrap(), expected);
+        check(&graph, KargerStein::new(&graph).set_seed(seed).solve().unwrap(), expected);
+    }
+}
+
+#[test]
+fn capacity_accessor() {
+    let mut graph = Graph::<Undirected, (), (i64, i64)>::default();
+    let nodes = graph.add_nodes(4);
+    graph.add_edge(nodes[0], nodes[1], (5, 1));
+    graph.add_edge(nodes[1], nodes[2], (1, 5));
+    graph.add_edge(nodes[2], nodes[3], (5, 1));
+    graph.add_edge(nodes[3], nodes[0], (1, 5));
+
+    let (value, side) = StoerWagner::new_with(&graph, |e| e.data.0).solve().unwrap();
+    assert_eq!((value, side[0] == side[1], side[1] == side[2]), (2, true, false));
+    let (value, side) = KargerStein::new_with(&graph, |e| e.data.1).solve().unwrap();
+    assert_eq!((value, side[0] == side[1], side[1] == side[2]), (2, false, true));
+}
+
+#[test]
+fn karger_stein_larger_graphs() {
+    let mut seed = 97531_u64;
+    for _ in 0..5 {
+        let graph = random_graph(&mut seed, 40, 200, 20);
+        let expected = StoerWagner::new(&graph).solve().unwrap().0;
+        check(&graph, KargerStein::new(&graph).set_seed(seed).solve().unwrap(), expected);
+    }
+}
+
+#[test]
+fn too_few_nodes() {
+    let mut graph = UndirectedGraph::default();
+    assert!(StoerWagner::new(&graph).solve().is_none());
+    assert!(KargerStein::new(&graph).solve().is_none());
+
+    graph.add_nodes(1);
+    assert!(StoerWagner::new(&graph).solve().is_none());
+    assert!(KargerStein::new(&graph).set_num_trials(3).solve().is_none());
+}
+
+fn random_directed_graph(seed: &mut u64, n: usize, m: usize, max_capacity: u64) -> DirectedGraph {
+    let mut graph = DirectedGraph::default();
+    let nodes = graph.add_nodes(n);
+    for _ in 0..m {
+        let (u, v) = ((lcg_next(seed) % n as u64) as usize, (lcg_next(seed) % n as u64) as usize);
+        graph.add_edge(nodes[u], nodes[v], MaximumFlowEdge { upper: (lcg_next(seed) % max_capacity) as i64 });
+    }
+    graph
+}
+
+// capacity of the arcs leaving the true side
+fn directed_cut_capacity(graph: &DirectedGraph, side: &[bool]) -> i64 {
+    graph
+        .edges()
+        .filter(|e| side[e.u.index()] && !side[e.v.index()])
+        .map(|e| e.data.upper)
+        .sum()
+}
+
+#[rstest]
+#[case::sparse(8, 12)]
+#[case::dense(8, 40)]
+#[case::disconnected(9, 4)]
+fn hao_orlin_random(#[case] n: usize, #[case] m: usize) {
+    let mut seed = 2468013579_u64 + (n * m) as u64;
+    for _ in 0..30 {
+        let graph = random_directed_graph(&mut seed, n, m, 10);
+        let expected = (1..(1_usize << n) - 1)
+            .map(|mask| directed_cut_capacity(&graph, &(0..n).map(|u| (mask >> u) & 1 == 1).collect::<Vec<_>>()))
+            .min()
+            .unwrap();
+
+        let (value, side, (s, t)) = HaoOrlin::new(&graph).solve().unwrap();
+        assert_eq!(value, expected);
+        assert_eq!(directed_cut_capacity(&graph, &side), expected);
+        assert!(side[s.index()] && !side[t.index()]);
+    }
+}
+
+#[test]
+fn hao_orlin_one_way() {
+    // every cut with node 0 on the source side is expensive, the cheapest ones separate the other nodes from 0
+    let mut graph = DirectedGraph::default();
+    let nodes = graph.add_nodes(3);
+    graph.add_edge(nodes[0], nodes[1], MaximumFlowEdge { upper: 10 });
+    graph.add_edge(nodes[1], nodes[2], MaximumFlowEdge { upper: 10 });
+    graph.add_edge(nodes[2], nodes[0], MaximumFlowEdge { upper: 1 });
+    graph.add_edge(nodes[0], nodes[2], MaximumFlowEdge { upper: 10 });
+
+    let mut solver = HaoOrlin::new(&graph);
+    let (value, side, (s, t)) = solver.solve().unwrap();
+    assert_eq!((value, side[0], side[2], t), (1, false, true, nodes[0]));
+    assert!(side[s.index()]);
+    // the solver can be run again
+    assert_eq!(solver.solve().unwrap().0, 1);
+
+    assert!(HaoOrlin::new(&DirectedGraph::default()).solve().is_none());
+}
@@ -0,0 +1,192 @@
+mod common;
+
+use common::lcg_next;
+use network_algorithms::algorithms::maximum_flow::prelude::{Dinic, MaximumFlowError, MaximumFlowGraph, MaximumFlowSolver};
+use network_algorithms::algorithms::multi_commodity_flow::prelude::*;
+use network_algorithms::ids::NodeId;
+use rstest::rstest;
+
+const TOLERANCE: f64 = 1e-9;
+
+fn random_instance(
+    seed: &mut u64,
+    n: usize,
+    m: usize,
+    k: usize,
+) -> (MaximumFlowGraph<i64>, Vec<(NodeId, NodeId, i64)>) {
+    let mut graph = MaximumFlowGraph::new_directed();
+    let nodes = graph.add_nodes(n);
+    for _ in 0..m {
+        let (u, v) = ((lcg_next(seed) % n as u64) as usize, (lcg_next(seed) % n as u64) as usize);
+        graph.add_edge(nodes[u], nodes[v], (lcg_next(seed) % 10) as i64);
+    }
+    let mut commodities = Vec::new();
+    while commodities.len() < k {
+        let (s, t) = ((lcg_next(seed) % n as u64) as usize, (lcg_next(seed) % n as u64) as usize);
+        if s != t {
+            commodities.push((nodes[s], nodes[t], (lcg_next(seed) % 5) as i64 + 1));
+        }
+    }
+    (graph, commodities)
+}
+
+// checks the capacities and the conservation of every commodity
+fn check_feasible(
+    graph: &MaximumFlowGraph<i64>,
+    solver: &MultiCommodityFlow<i64>,
+    commodities: &[(NodeId, NodeId, i64)],
+) {
+    let mut total = vec![0.0; graph.num_edges()];
+    for (j, &(s, t, _)) in commodities.iter().enumerate() {
+        let flows = solver.flows(j).unwrap();
+        let mut excess = vec![0.0; graph.num_nodes()];
+        for (i, e) in graph.edges().enumerate() {
+            assert!(flows[i] >= 0.0);
+            total[i] += flows[i];
+            excess[e.u.index()] -= flows[i];
+            excess[e.v.index()] += flows[i];
+        }
+        let value = solver.value(j).unwrap();
+        for (u, x) in excess.iter().enumerate() {
+            let expected = if u == s.index() {
+                -value
+            } else if u == t.index() {
+                value
+            } else {
+                0.0
+            };
+            assert!((x - expected).abs() < 1e-6);
+        }
+    }
+    for (e, f) in graph.edges().zip(total) {
+        assert!(f <= e.data.upper as f64 + 1e-6);
+    }
+}
+
+// shortest path distances under the dual lengths, with Bellman-Ford since the lengths are floating point
+fn distance(graph: &MaximumFlowGraph<i64>, lengths: &[f64], s: NodeId, t: NodeId) -> f64 {
+    let mut dist = vec![f64::INFINITY; graph.num_nodes()];
+    dist[s.index()] = 0.0;
+    for _ in 0..graph.num_nodes() {
+        for (e, l) in graph.edges().zip(lengths) {
+            dist[e.v.index()] = dist[e.v.index()].min(dist[e.u.index()] + l);
+        }
+    }
+    dist[t.index()]
+}
+
+fn volume(graph: &MaximumFlowGraph<i64>, lengths: &[f64]) -> f64 {
+    graph
+        .edges()
+        .zip(lengths)
+        .filter(|(e, _)| e.data.upper > 0)
+        .map(|(e, l)| e.data.upper as f64 * l)
+        .sum()
+}
+
+#[rstest]
+#[case(0.1)]
+#[case(0.05)]
+fn single_commodity(#[case] epsilon: f64) {
+    let mut seed = 1618033988_u64;
+    for _ in 0..20 {
+        let (graph, commodities) = random_instance(&mut seed, 8, 20, 1);
+        let (s, t, d) = commodities[0];
+        let expected = Dinic::new(&graph).solve(s, t).unwrap() as f64;
+
+        let mut solver = MultiCommodityFlow::new(&graph).set_epsilon(epsilon);
+        solver.add_commodity(s, t, d);
+        let value = solver.maximum_multicommodity_flow().unwrap();
+        assert!(value <= expected + TOLERANCE && value >= (1.0 - epsilon).powi(2) * expected - TOLERANCE);
+        assert!(solver.upper_bound().unwrap() >= expected - 1e-6);
+        check_feasible(&graph, &solver, &commodities);
+
+        let lambda = solver.maximum_concurrent_flow().unwrap();
+        let expected = expected / d as f64;
+        assert!(lambda <= expected + TOLERANCE && lambda >= (1.0 - epsilon).powi(3) * expected - TOLERANCE);
+        assert!(solver.upper_bound().unwrap() >= expected - 1e-6);
+        check_feasible(&graph, &solver, &commodities);
+    }
+}
+
+#[rstest]
+#[case::few(8, 24, 3)]
+#[case::many(10, 40, 12)]
+fn random(#[case] n: usize, #[case] m: usize, #[case] k: usize) {
+    let epsilon = 0.1;
+    let mut seed = 1414213562_u64 + (n * m * k) as u64;
+    for _ in 0..10 {
+        let (graph, commodities) = random_instance(&mut seed, n, m, k);
+        let mut solver = MultiCommodityFlow::new(&graph).set_epsilon(epsilon);
+        for &(s, t, d) in commodities.iter() {
+            solver.add_commodity(s, t, d).unwrap();
+        }
+
+        // the lengths certify the upper bound: every path has length at least 1
+        let value = solver.maximum_multicommodity_flow().unwrap();
+        let (lengths, upper_bound) = (solver.lengths().unwrap(), solver.upper_bound().unwrap());
+        check_feasible(&graph, &solver, &commodities);
+        assert!(
+            commodities
+                .iter()
+                .all(|&(s, t, _)| distance(&graph, &lengths, s, t) >= 1.0 - 1e-6)
+        );
+        assert!((volume(&graph, &lengths) - upper_bound).abs() <= 1e-6 * upper_bound.max(1.0));
+        assert!(value <= upper_bound + 1e-6 && value >= (1.0 - epsilon).powi(2) * upper_bound - 1e-6);
+
+        // every commodity gets λ times its demand, and sum_j d_j dist_j is at least 1
+        let lambda = solver.maximum_concurrent_flow().unwrap();
+        let (lengths, upper_bound) = (solver.lengths().unwrap(), solver.upper_bound().unwrap());
+        check_feasible(&graph, &solver, &commodities);
+        for (j, &(_, _, d)) in commodities.iter().enumerate() {
+            assert!(solver.value(j).unwrap() >= lambda * d as f64 - 1e-6);
+        }
+        if upper_bound > 0.0 {
+            let alpha: f64 = commodities
+                .iter()
+                .map(|&(s, t, d)| d as f64 * distance(&graph, &lengths, s, t))
+                .sum();
+            assert!(alpha >= 1.0 - 1e-6);
+            assert!((volume(&graph, &lengths) - upper_bound).abs() <= 1e-6 * upper_bound.max(1.0));
+        }
+        assert!(lambda <= upper_bound + 1e-6 && lambda >= (1.0 - epsilon).powi(3) * upper_bound - 1e-6);
+    }
+}
+
+#[test]
+fn shared_links() {
+    // commodities (0, 2), (0, 1) and (1, 2) on the path 0 -> 1 -> 2 of unit capacities
+    let mut graph = MaximumFlowGraph::new_directed();
+    let nodes = graph.add_nodes(3);
+    let edge = graph.add_edge(nodes[0], nodes[1], 1).unwrap();
+    graph.add_edge(nodes[1], nodes[2], 1);
+
+    let mut solver = MultiCommodityFlow::new(&graph).set_epsilon(0.05);
+    assert_eq!(solver.add_commodity(nodes[0], nodes[2], 1), Some(0));
+    assert_eq!(solver.add_commodity(nodes[0], nodes[1], 1), Some(1));
+    assert_eq!(solver.add_commodity(nodes[1], nodes[2], 3), Some(2));
+    assert_eq!(solver.add_commodity(nodes[1], nodes[1], 1), None);
+    assert!(solver.flows(0).is_err());
+
+    // the long commodity is not worth routing
+    let value = solver.maximum_multicommodity_flow().unwrap();
+    assert!(value >= 0.95_f64.powi(2) * 2.0 && value <= 2.0 + TOLERANCE);
+    assert!(solver.upper_bound().unwrap() >= 2.0 - TOLERANCE);
+
+    // λ (d_0 + d_2) <= 1 on the second link
+    let lambda = solver.maximum_concurrent_flow().unwrap();
+    assert!(lambda >= 0.95_f64.powi(3) * 0.25 && lambda <= 0.25 + TOLERANCE);
+    assert!(solver.upper_bound().unwrap() >= 0.25 - TOLERANCE);
+
+    let invalid = MaximumFlowError::InvalidCommodity { commodity: 3 };
+    assert_eq!(solver.flows(3), Err(invalid.clone()));
+    assert_eq!(solver.flow(3, edge), Err(invalid.clone()));
+    assert_eq!(solver.value(3), Err(invalid));
+
+    assert!(
+        MultiCommodityFlow::new(&graph)
+            .set_epsilon(1.5)
+            .maximum_multicommodity_flow()
+            .is_err()
+    );
+}